@@ -20,6 +20,14 @@ pub struct Config {
     pub jupiter_twap: Option<JupiterTwapConfig>,
     pub jupiter_usd: Option<JupiterUsdConfig>,
 
+    pub meteora_candle: Option<MeteoraCandleConfig>,
+    pub meteora_summary: Option<MeteoraSummaryConfig>,
+    pub meteora_twap: Option<MeteoraTwapConfig>,
+
+    pub orca_candle: Option<OrcaCandleConfig>,
+    pub orca_mcap: Option<OrcaMcapConfig>,
+    pub orca_usd: Option<OrcaUsdConfig>,
+
     pub pumpfun_candle: Option<PumpfunCandleConfig>,
     pub pumpfun_mcap: Option<PumpfunMcapConfig>,
     pub pumpfun_progress: Option<PumpfunProgressConfig>,
@@ -28,6 +36,7 @@ pub struct Config {
     pub pumpfun_usd: Option<PumpfunUsdConfig>,
 
     pub pumpswap_candle: Option<PumpswapCandleConfig>,
+    pub pumpswap_liquidity: Option<PumpswapLiquidityConfig>,
     pub pumpswap_mcap: Option<PumpswapMcapConfig>,
     pub pumpswap_summary: Option<PumpswapSummaryConfig>,
     pub pumpswap_twap: Option<PumpswapTwapConfig>,
@@ -40,6 +49,9 @@ pub struct Config {
     pub pumpup_twap: Option<PumpupTwapConfig>,
     pub pumpup_usd: Option<PumpupUsdConfig>,
 
+    pub solana_creator: Option<SolanaCreatorConfig>,
+    pub solana_failed: Option<SolanaFailedConfig>,
+    pub solana_recalculate: Option<SolanaRecalculateConfig>,
     pub solana_sol: Option<SolanaSolConfig>,
 }
 
@@ -223,6 +235,216 @@ impl Default for JupiterUsdConfig {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeteoraCandleConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<MeteoraCandleConfig> for PostgresConfig {
+    fn from(value: MeteoraCandleConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for MeteoraCandleConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeteoraSummaryConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<MeteoraSummaryConfig> for PostgresConfig {
+    fn from(value: MeteoraSummaryConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for MeteoraSummaryConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeteoraTwapConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<MeteoraTwapConfig> for PostgresConfig {
+    fn from(value: MeteoraTwapConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for MeteoraTwapConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrcaCandleConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<OrcaCandleConfig> for PostgresConfig {
+    fn from(value: OrcaCandleConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for OrcaCandleConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrcaMcapConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<OrcaMcapConfig> for PostgresConfig {
+    fn from(value: OrcaMcapConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for OrcaMcapConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrcaUsdConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<OrcaUsdConfig> for PostgresConfig {
+    fn from(value: OrcaUsdConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for OrcaUsdConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct PumpfunCandleConfig {
     pub active: ConfigValue,
@@ -468,6 +690,41 @@ impl Default for PumpswapCandleConfig {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct PumpswapLiquidityConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<PumpswapLiquidityConfig> for PostgresConfig {
+    fn from(value: PumpswapLiquidityConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for PumpswapLiquidityConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct PumpswapMcapConfig {
     pub active: ConfigValue,
@@ -608,6 +865,111 @@ impl Default for PumpswapUsdConfig {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolanaCreatorConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<SolanaCreatorConfig> for PostgresConfig {
+    fn from(value: SolanaCreatorConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for SolanaCreatorConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolanaFailedConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<SolanaFailedConfig> for PostgresConfig {
+    fn from(value: SolanaFailedConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for SolanaFailedConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolanaRecalculateConfig {
+    pub active: ConfigValue,
+    pub mode: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<SolanaRecalculateConfig> for PostgresConfig {
+    fn from(value: SolanaRecalculateConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for SolanaRecalculateConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            mode: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SolanaSolConfig {
     pub active: ConfigValue,
@@ -13,6 +13,8 @@ use tokio::time::sleep;
 pub mod config;
 pub mod jupiter;
 pub mod leaderboard;
+pub mod meteora;
+pub mod orca;
 pub mod pumpfun;
 pub mod pumpswap;
 pub mod pumpup;
@@ -50,10 +52,21 @@ pub(crate) async fn send_every(senders: Vec<Sender<Partition>>, every: Duration)
     loop {
         let tx = &senders[i];
         tx.send(Partition(i + 1)).await.unwrap();
-        i = (i + 1) % 8;
+        i = (i + 1) % senders.len();
 
         if i == 0 {
             sleep(every).await;
         }
     }
 }
+
+/// Number of partitions the token pairs are split into, one worker per partition
+pub(crate) fn partition_count() -> i64 {
+    Partition::enumerate().into_iter().count() as i64
+}
+
+/// A partition processes the token pairs with token_pair_id % partition_count() = partition_remainder(),
+/// partitions are numbered from 1 by send_every
+pub(crate) fn partition_remainder(partition: Partition) -> i64 {
+    partition.0 as i64 - 1
+}
@@ -6,16 +6,19 @@
 use aggregator::config::Config;
 use aggregator::jupiter::{jupiter_candle, jupiter_mcap, jupiter_twap, jupiter_usd};
 use aggregator::leaderboard::leaderboard_refresh;
+use aggregator::meteora::{meteora_candle, meteora_summary, meteora_twap};
+use aggregator::orca::{orca_candle, orca_mcap, orca_usd};
 use aggregator::pumpfun::{
     pumpfun_candle, pumpfun_mcap, pumpfun_progress, pumpfun_summary, pumpfun_twap, pumpfun_usd,
 };
 use aggregator::pumpswap::{
-    pumpswap_candle, pumpswap_mcap, pumpswap_summary, pumpswap_twap, pumpswap_usd,
+    pumpswap_candle, pumpswap_liquidity, pumpswap_mcap, pumpswap_summary, pumpswap_twap,
+    pumpswap_usd,
 };
 use aggregator::pumpup::{
     pumpup_candle, pumpup_mcap, pumpup_progress, pumpup_summary, pumpup_twap, pumpup_usd,
 };
-use aggregator::solana::solana_sol;
+use aggregator::solana::{solana_creator, solana_failed, solana_recalculate, solana_sol};
 use common::{ResolveOr, Signal};
 use futures::future::join_all;
 use log::{error, info};
@@ -54,6 +57,12 @@ fn main() {
             jupiter_mcap(cfg.jupiter_mcap.unwrap_or_default(), signal.clone()),
             jupiter_twap(cfg.jupiter_twap.unwrap_or_default(), signal.clone()),
             jupiter_usd(cfg.jupiter_usd.unwrap_or_default(), signal.clone()),
+            meteora_candle(cfg.meteora_candle.unwrap_or_default(), signal.clone()),
+            meteora_summary(cfg.meteora_summary.unwrap_or_default(), signal.clone()),
+            meteora_twap(cfg.meteora_twap.unwrap_or_default(), signal.clone()),
+            orca_candle(cfg.orca_candle.unwrap_or_default(), signal.clone()),
+            orca_mcap(cfg.orca_mcap.unwrap_or_default(), signal.clone()),
+            orca_usd(cfg.orca_usd.unwrap_or_default(), signal.clone()),
             pumpfun_candle(cfg.pumpfun_candle.unwrap_or_default(), signal.clone()),
             pumpfun_mcap(cfg.pumpfun_mcap.unwrap_or_default(), signal.clone()),
             pumpfun_progress(cfg.pumpfun_progress.unwrap_or_default(), signal.clone()),
@@ -61,6 +70,7 @@ fn main() {
             pumpfun_twap(cfg.pumpfun_twap.unwrap_or_default(), signal.clone()),
             pumpfun_usd(cfg.pumpfun_usd.unwrap_or_default(), signal.clone()),
             pumpswap_candle(cfg.pumpswap_candle.unwrap_or_default(), signal.clone()),
+            pumpswap_liquidity(cfg.pumpswap_liquidity.unwrap_or_default(), signal.clone()),
             pumpswap_mcap(cfg.pumpswap_mcap.unwrap_or_default(), signal.clone()),
             pumpswap_summary(cfg.pumpswap_summary.unwrap_or_default(), signal.clone()),
             pumpswap_twap(cfg.pumpswap_twap.unwrap_or_default(), signal.clone()),
@@ -72,6 +82,9 @@ fn main() {
             pumpup_twap(cfg.pumpup_twap.unwrap_or_default(), signal.clone()),
             pumpup_usd(cfg.pumpup_usd.unwrap_or_default(), signal.clone()),
             solana_sol(cfg.solana_sol.unwrap_or_default()),
+            solana_creator(cfg.solana_creator.unwrap_or_default(), signal.clone()),
+            solana_failed(cfg.solana_failed.unwrap_or_default(), signal.clone()),
+            solana_recalculate(cfg.solana_recalculate.unwrap_or_default(), signal.clone()),
             leaderboard_refresh(cfg.leaderboard.unwrap_or_default(), signal.clone()),
         ];
 
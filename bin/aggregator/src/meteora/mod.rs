@@ -0,0 +1,11 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use candle::meteora_candle;
+pub use summary::meteora_summary;
+pub use twap::meteora_twap;
+
+mod candle;
+pub(crate) mod repo;
+mod summary;
+mod twap;
@@ -0,0 +1,476 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::{partition_count, partition_remainder};
+use common::model::{Partition, Timestamp};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Postgres, Transaction};
+
+pub(crate) struct CandleRepo {}
+
+impl CandleRepo {
+    pub(crate) async fn calculate_1s(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::from_swaps(executor, partition, "1s", "1 second", "10 seconds").await
+    }
+
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::from_swaps(executor, partition, "1m", "1 minute", "2 minutes").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1m", "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "5m", "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "15m", "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1h", "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1h", "1d", "1 day").await
+    }
+
+    /// Recalculates every candle of the buckets overlapping [from, to] across all partitions,
+    /// e.g. after swaps of the range got backfilled
+    pub(crate) async fn recalculate(
+        tx: &mut Transaction<'_, Postgres>,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        Self::from_swaps_between(&mut *tx, "1s", "1 second", from.clone(), to.clone()).await?;
+        Self::from_swaps_between(&mut *tx, "1m", "1 minute", from.clone(), to.clone()).await?;
+        for (source, table, bucket) in [
+            ("1m", "5m", "5 minutes"),
+            ("5m", "15m", "15 minutes"),
+            ("15m", "1h", "1 hour"),
+            ("1h", "6h", "6 hours"),
+            ("1h", "1d", "1 day"),
+        ] {
+            Self::roll_up_between(&mut *tx, source, table, bucket, from.clone(), to.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Recalculates the candles swaps happened in since the lookback
+    async fn from_swaps(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        bucket: &str,
+        lookback: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into meteora.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($2::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price order by id))[1],
+    max(price),
+    min(price),
+    (array_agg(price order by id desc))[1],
+    sum(amount_quote),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    now()
+from meteora.swap
+where timestamp >= date_bin($2::interval, now() - $3::interval, to_timestamp(0))
+  and token_pair_id % $4 = $1
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(lookback)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Recalculates the current and the previous candle from the candles of a smaller timeframe
+    async fn roll_up(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        source: &str,
+        table: &str,
+        bucket: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into meteora.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($2::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price_open order by timestamp))[1],
+    max(price_high),
+    min(price_low),
+    (array_agg(price_close order by timestamp desc))[1],
+    sum(volume),
+    sum(swap),
+    sum(swap_buy),
+    sum(swap_sell),
+    now()
+from meteora.candle_{source}
+where timestamp >= date_bin($2::interval, now(), to_timestamp(0)) - $2::interval
+  and token_pair_id % $3 = $1
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the candles of the buckets overlapping [from, to] by the ones of their swaps,
+    /// buckets without swaps left get removed
+    async fn from_swaps_between(
+        mut executor: impl AsSqlExecutor,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from meteora.candle_{table}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into meteora.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price order by id))[1],
+    max(price),
+    min(price),
+    (array_agg(price order by id desc))[1],
+    sum(amount_quote),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    now()
+from meteora.swap
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket;
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the candles of the buckets overlapping [from, to] by the ones rolled up from the
+    /// candles of a smaller timeframe
+    async fn roll_up_between(
+        mut executor: impl AsSqlExecutor,
+        source: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from meteora.candle_{table}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into meteora.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price_open order by timestamp))[1],
+    max(price_high),
+    min(price_low),
+    (array_agg(price_close order by timestamp desc))[1],
+    sum(volume),
+    sum(swap),
+    sum(swap_buy),
+    sum(swap_sell),
+    now()
+from meteora.candle_{source}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket;
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub(crate) struct SummaryRepo {}
+
+impl SummaryRepo {
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1d", "1 day").await
+    }
+
+    /// Summarizes the swaps within the trailing window, token pairs without swaps get removed
+    async fn calculate(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        window: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into meteora.summary_{table} (token_pair_id, amount_base, amount_quote, volume_buy, volume_sell, swap, swap_buy, swap_sell, trader, updated_at)
+select
+    token_pair_id,
+    sum(amount_base),
+    sum(amount_quote),
+    coalesce(sum(amount_quote) filter (where is_buy), 0),
+    coalesce(sum(amount_quote) filter (where not is_buy), 0),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    count(distinct address_id),
+    now()
+from meteora.swap
+where timestamp > now() - $2::interval
+  and token_pair_id % $3 = $1
+group by token_pair_id
+on conflict (token_pair_id) do update set
+    amount_base = excluded.amount_base,
+    amount_quote = excluded.amount_quote,
+    volume_buy = excluded.volume_buy,
+    volume_sell = excluded.volume_sell,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    trader = excluded.trader,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(window)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        // now() is the start of the transaction, everything updated above has exactly that time
+        query(&format!(
+            r#"
+delete from meteora.summary_{table}
+where token_pair_id % $2 = $1
+  and updated_at < now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub(crate) struct TwapRepo {}
+
+impl TwapRepo {
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1s", "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1s", "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "5m", "1d", "1 day").await
+    }
+
+    /// Time weighted average price of the trailing window, each close holds until the next candle
+    async fn calculate(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        source: &str,
+        table: &str,
+        window: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+with candle as (
+    select
+        token_pair_id,
+        price_close,
+        extract(epoch from coalesce(lead(timestamp) over (partition by token_pair_id order by timestamp), now()) - timestamp) as seconds
+    from meteora.candle_{source}
+    where timestamp > now() - $2::interval
+      and token_pair_id % $3 = $1
+)
+insert into meteora.twap_{table} (token_pair_id, twap, updated_at)
+select
+    token_pair_id,
+    coalesce(sum(price_close * seconds) / nullif(sum(seconds), 0), avg(price_close)),
+    now()
+from candle
+group by token_pair_id
+on conflict (token_pair_id) do update set
+    twap = excluded.twap,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(window)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use candle::orca_candle;
+pub use mcap::orca_mcap;
+pub use usd::orca_usd;
+
+mod candle;
+mod mcap;
+pub(crate) mod repo;
+mod usd;
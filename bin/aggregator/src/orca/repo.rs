@@ -0,0 +1,517 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::{partition_count, partition_remainder};
+use common::model::{Partition, Timestamp};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Postgres, Transaction};
+
+pub(crate) struct CandleRepo {}
+
+impl CandleRepo {
+    pub(crate) async fn calculate_1s(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::from_swaps(executor, partition, "1s", "1 second", "10 seconds").await
+    }
+
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::from_swaps(executor, partition, "1m", "1 minute", "2 minutes").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1m", "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "5m", "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "15m", "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1h", "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::roll_up(executor, partition, "1h", "1d", "1 day").await
+    }
+
+    pub(crate) async fn calculate_mcap_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_mcap_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_mcap_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_mcap_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_mcap_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_mcap_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::mcap(executor, partition, "1d", "1 day").await
+    }
+
+    pub(crate) async fn calculate_usd_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_usd_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_usd_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_usd_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_usd_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_usd_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::usd(executor, partition, "1d", "1 day").await
+    }
+
+    /// Recalculates every candle of the buckets overlapping [from, to] across all partitions,
+    /// e.g. after swaps of the range got backfilled
+    pub(crate) async fn recalculate(
+        tx: &mut Transaction<'_, Postgres>,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        Self::from_swaps_between(&mut *tx, "1s", "1 second", from.clone(), to.clone()).await?;
+        Self::from_swaps_between(&mut *tx, "1m", "1 minute", from.clone(), to.clone()).await?;
+        for (source, table, bucket) in [
+            ("1m", "5m", "5 minutes"),
+            ("5m", "15m", "15 minutes"),
+            ("15m", "1h", "1 hour"),
+            ("1h", "6h", "6 hours"),
+            ("1h", "1d", "1 day"),
+        ] {
+            Self::roll_up_between(&mut *tx, source, table, bucket, from.clone(), to.clone())
+                .await?;
+        }
+        for (table, bucket) in [
+            ("1m", "1 minute"),
+            ("5m", "5 minutes"),
+            ("15m", "15 minutes"),
+            ("1h", "1 hour"),
+            ("6h", "6 hours"),
+            ("1d", "1 day"),
+        ] {
+            Self::mcap_between(&mut *tx, table, bucket, from.clone(), to.clone()).await?;
+            Self::usd_between(&mut *tx, table, bucket, from.clone(), to.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Recalculates the candles swaps happened in since the lookback
+    async fn from_swaps(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        bucket: &str,
+        lookback: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into orca.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($2::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price order by id))[1],
+    max(price),
+    min(price),
+    (array_agg(price order by id desc))[1],
+    sum(amount_quote),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    now()
+from orca.swap
+where timestamp >= date_bin($2::interval, now() - $3::interval, to_timestamp(0))
+  and token_pair_id % $4 = $1
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(lookback)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Recalculates the current and the previous candle from the candles of a smaller timeframe
+    async fn roll_up(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        source: &str,
+        table: &str,
+        bucket: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into orca.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($2::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price_open order by timestamp))[1],
+    max(price_high),
+    min(price_low),
+    (array_agg(price_close order by timestamp desc))[1],
+    sum(volume),
+    sum(swap),
+    sum(swap_buy),
+    sum(swap_sell),
+    now()
+from orca.candle_{source}
+where timestamp >= date_bin($2::interval, now(), to_timestamp(0)) - $2::interval
+  and token_pair_id % $3 = $1
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Market cap of the current and the previous candle, priced by the supply of the base token
+    async fn mcap(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        bucket: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update orca.candle_{table} c set
+    market_cap_open = c.price_open * t.supply,
+    market_cap_high = c.price_high * t.supply,
+    market_cap_low = c.price_low * t.supply,
+    market_cap_close = c.price_close * t.supply,
+    updated_at = now()
+from solana.token_pair tp
+join solana.token t on t.id = tp.base_id
+where tp.id = c.token_pair_id
+  and t.supply is not null
+  and c.timestamp >= date_bin($2::interval, now(), to_timestamp(0)) - $2::interval
+  and c.token_pair_id % $3 = $1;
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Usd values of the current and the previous candle, by the sol price of the same bucket kept by
+    /// solana_sol. Candles without a sol price yet are skipped.
+    async fn usd(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        bucket: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update orca.candle_{table} c set
+    price_open_usd = c.price_open * sol.usd,
+    price_high_usd = c.price_high * sol.usd,
+    price_low_usd = c.price_low * sol.usd,
+    price_close_usd = c.price_close * sol.usd,
+    market_cap_open_usd = c.market_cap_open * sol.usd,
+    market_cap_high_usd = c.market_cap_high * sol.usd,
+    market_cap_low_usd = c.market_cap_low * sol.usd,
+    market_cap_close_usd = c.market_cap_close * sol.usd,
+    volume_usd = c.volume * sol.usd,
+    updated_at = now()
+from solana.sol_price_{table} sol
+where c.timestamp = sol.timestamp
+  and c.timestamp >= date_bin($2::interval, now(), to_timestamp(0)) - $2::interval
+  and c.token_pair_id % $3 = $1;
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(bucket)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the candles of the buckets overlapping [from, to] by the ones of their swaps,
+    /// buckets without swaps left get removed
+    async fn from_swaps_between(
+        mut executor: impl AsSqlExecutor,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from orca.candle_{table}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into orca.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price order by id))[1],
+    max(price),
+    min(price),
+    (array_agg(price order by id desc))[1],
+    sum(amount_quote),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    now()
+from orca.swap
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket;
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the candles of the buckets overlapping [from, to] by the ones rolled up from the
+    /// candles of a smaller timeframe
+    async fn roll_up_between(
+        mut executor: impl AsSqlExecutor,
+        source: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from orca.candle_{table}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into orca.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price_open order by timestamp))[1],
+    max(price_high),
+    min(price_low),
+    (array_agg(price_close order by timestamp desc))[1],
+    sum(volume),
+    sum(swap),
+    sum(swap_buy),
+    sum(swap_sell),
+    now()
+from orca.candle_{source}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket;
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Market cap of the candles of the buckets overlapping [from, to]
+    async fn mcap_between(
+        mut executor: impl AsSqlExecutor,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update orca.candle_{table} c set
+    market_cap_open = c.price_open * t.supply,
+    market_cap_high = c.price_high * t.supply,
+    market_cap_low = c.price_low * t.supply,
+    market_cap_close = c.price_close * t.supply,
+    updated_at = now()
+from solana.token_pair tp
+join solana.token t on t.id = tp.base_id
+where tp.id = c.token_pair_id
+  and t.supply is not null
+  and c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Usd values of the candles of the buckets overlapping [from, to], priced like [Self::usd]
+    async fn usd_between(
+        mut executor: impl AsSqlExecutor,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update orca.candle_{table} c set
+    price_open_usd = c.price_open * sol.usd,
+    price_high_usd = c.price_high * sol.usd,
+    price_low_usd = c.price_low * sol.usd,
+    price_close_usd = c.price_close * sol.usd,
+    market_cap_open_usd = c.market_cap_open * sol.usd,
+    market_cap_high_usd = c.market_cap_high * sol.usd,
+    market_cap_low_usd = c.market_cap_low * sol.usd,
+    market_cap_close_usd = c.market_cap_close * sol.usd,
+    volume_usd = c.volume * sol.usd,
+    updated_at = now()
+from solana.sol_price_{table} sol
+where c.timestamp = sol.timestamp
+  and c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -2,13 +2,16 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 pub use candle::pumpswap_candle;
+pub use liquidity::pumpswap_liquidity;
 pub use mcap::pumpswap_mcap;
 pub use summary::pumpswap_summary;
 pub use twap::pumpswap_twap;
 pub use usd::pumpswap_usd;
 
 mod candle;
+mod liquidity;
 mod mcap;
+mod repo;
 mod summary;
 mod twap;
 mod usd;
@@ -0,0 +1,133 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::{partition_count, partition_remainder};
+use common::model::Partition;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::query;
+
+pub(crate) struct LiquidityRepo {}
+
+impl LiquidityRepo {
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1d", "1 day").await
+    }
+
+    /// Summarizes the liquidity events within the trailing window, token pairs without events get
+    /// removed. The burned lp tokens are relative to the largest supply a pool had within the
+    /// window - the supply before a withdrawal includes the tokens it burned.
+    async fn calculate(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        window: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+with pool as (
+    select
+        token_pair_id,
+        pool_id,
+        max(case when kind = 'withdraw' then lp_supply + lp_amount else lp_supply end) as lp_supply
+    from pumpswap.liquidity
+    where timestamp > now() - $2::interval
+      and token_pair_id % $3 = $1
+    group by token_pair_id, pool_id
+),
+supply as (
+    select token_pair_id, sum(lp_supply) as lp_supply
+    from pool
+    group by token_pair_id
+)
+insert into pumpswap.liquidity_{table} (token_pair_id, deposit, withdraw, provider, quote_added, quote_removed, quote_change, lp_minted, lp_burned, lp_burned_percent, updated_at)
+select
+    l.token_pair_id,
+    count(*) filter (where l.kind <> 'withdraw'),
+    count(*) filter (where l.kind = 'withdraw'),
+    count(distinct l.address_id),
+    coalesce(sum(l.amount_quote) filter (where l.kind <> 'withdraw'), 0),
+    coalesce(sum(l.amount_quote) filter (where l.kind = 'withdraw'), 0),
+    coalesce(sum(case when l.kind = 'withdraw' then -l.amount_quote else l.amount_quote end), 0),
+    coalesce(sum(l.lp_amount) filter (where l.kind <> 'withdraw'), 0),
+    coalesce(sum(l.lp_amount) filter (where l.kind = 'withdraw'), 0),
+    coalesce(round(sum(l.lp_amount) filter (where l.kind = 'withdraw') / nullif(max(s.lp_supply), 0) * 100, 4), 0),
+    now()
+from pumpswap.liquidity l
+join supply s on s.token_pair_id = l.token_pair_id
+where l.timestamp > now() - $2::interval
+  and l.token_pair_id % $3 = $1
+group by l.token_pair_id
+on conflict (token_pair_id) do update set
+    deposit = excluded.deposit,
+    withdraw = excluded.withdraw,
+    provider = excluded.provider,
+    quote_added = excluded.quote_added,
+    quote_removed = excluded.quote_removed,
+    quote_change = excluded.quote_change,
+    lp_minted = excluded.lp_minted,
+    lp_burned = excluded.lp_burned,
+    lp_burned_percent = excluded.lp_burned_percent,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(window)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        // now() is the start of the transaction, everything updated above has exactly that time
+        query(&format!(
+            r#"
+delete from pumpswap.liquidity_{table}
+where token_pair_id % $2 = $1
+  and updated_at < now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
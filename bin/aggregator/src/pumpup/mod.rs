@@ -11,6 +11,7 @@ pub use usd::pumpup_usd;
 mod candle;
 mod mcap;
 mod progress;
+mod repo;
 mod summary;
 mod twap;
 mod usd;
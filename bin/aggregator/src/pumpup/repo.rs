@@ -0,0 +1,99 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::{partition_count, partition_remainder};
+use common::model::Partition;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::query;
+
+pub(crate) struct AiVolumeRepo {}
+
+impl AiVolumeRepo {
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1d", "1 day").await
+    }
+
+    /// Replaces the ai token volume of the partition with the swaps within the window,
+    /// token pairs without swaps in the window drop out of the table
+    async fn calculate(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        window: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+with volume as (
+    select
+        token_pair_id,
+        sum(amount_ai) as volume,
+        coalesce(sum(amount_ai) filter (where is_buy), 0) as volume_buy,
+        coalesce(sum(amount_ai) filter (where not is_buy), 0) as volume_sell
+    from pumpup.swap
+    where timestamp > now() - $2::interval
+      and token_pair_id % $3 = $1
+    group by token_pair_id
+),
+removed as (
+    delete from pumpup.summary_ai_{table} s
+    where s.token_pair_id % $3 = $1
+      and not exists (select 1 from volume v where v.token_pair_id = s.token_pair_id)
+)
+insert into pumpup.summary_ai_{table} (token_pair_id, volume, volume_buy, volume_sell, updated_at)
+select token_pair_id, volume, volume_buy, volume_sell, now()
+from volume
+on conflict (token_pair_id) do update set
+    volume = excluded.volume,
+    volume_buy = excluded.volume_buy,
+    volume_sell = excluded.volume_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(window)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
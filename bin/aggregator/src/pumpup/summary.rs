@@ -2,6 +2,7 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 use crate::config::PumpupSummaryConfig;
+use crate::pumpup::repo::AiVolumeRepo;
 use crate::{log_ms, partitioned, send_every, Worker};
 use async_trait::async_trait;
 use common::model::Partition;
@@ -29,28 +30,54 @@ impl Worker<Partition> for SummaryWorker {
                     SummaryRepo::calculate_1m(&mut tx, partition).await.unwrap()
                 });
 
+                log_ms!("ai 1m", partition, async {
+                    AiVolumeRepo::calculate_1m(&mut tx, partition).await.unwrap()
+                });
+
                 log_ms!("5m", partition, async {
                     SummaryRepo::calculate_5m(&mut tx, partition).await.unwrap()
                 });
 
+                log_ms!("ai 5m", partition, async {
+                    AiVolumeRepo::calculate_5m(&mut tx, partition).await.unwrap()
+                });
+
                 log_ms!("15m", partition, async {
                     SummaryRepo::calculate_15m(&mut tx, partition)
                         .await
                         .unwrap()
                 });
 
+                log_ms!("ai 15m", partition, async {
+                    AiVolumeRepo::calculate_15m(&mut tx, partition)
+                        .await
+                        .unwrap()
+                });
+
                 log_ms!("1h", partition, async {
                     SummaryRepo::calculate_1h(&mut tx, partition).await.unwrap()
                 });
 
+                log_ms!("ai 1h", partition, async {
+                    AiVolumeRepo::calculate_1h(&mut tx, partition).await.unwrap()
+                });
+
                 log_ms!("6h", partition, async {
                     SummaryRepo::calculate_6h(&mut tx, partition).await.unwrap()
                 });
 
+                log_ms!("ai 6h", partition, async {
+                    AiVolumeRepo::calculate_6h(&mut tx, partition).await.unwrap()
+                });
+
                 log_ms!("1d", partition, async {
                     SummaryRepo::calculate_1d(&mut tx, partition).await.unwrap()
                 });
 
+                log_ms!("ai 1d", partition, async {
+                    AiVolumeRepo::calculate_1d(&mut tx, partition).await.unwrap()
+                });
+
                 let _ = tx.commit().await;
                 return;
             } else {
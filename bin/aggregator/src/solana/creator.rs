@@ -0,0 +1,45 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::config::SolanaCreatorConfig;
+use common::repo::pool::setup_pool;
+use common::{ResolveOr, Signal};
+use log::{error, info};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+pub fn solana_creator(cfg: SolanaCreatorConfig, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        if cfg.active.resolve_or(false) != true {
+            info!("not active");
+            return;
+        }
+
+        info!("active");
+        let pool = setup_pool(cfg).await;
+
+        let mut interval = interval(Duration::from_secs(60));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    if let Err(err) = refresh_creator_summary(&pool).await {
+                        error!("error refreshing creator summary: {:?}", err);
+                    }
+                }
+                _ = signal.recv() => {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+async fn refresh_creator_summary(pool: &PgPool) -> Result<(), sqlx::Error> {
+    sqlx::query("refresh materialized view concurrently solana.creator_summary")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -1,6 +1,13 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+pub use creator::solana_creator;
+pub use failed::solana_failed;
+pub use recalculate::solana_recalculate;
 pub use sol::solana_sol;
 
-mod sol;
\ No newline at end of file
+mod creator;
+mod failed;
+mod recalculate;
+mod repo;
+mod sol;
@@ -0,0 +1,71 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::config::SolanaRecalculateConfig;
+use crate::solana::repo::{AggregateRangeRepo, VenueCandleRepo, CANDLE_VENUES};
+use crate::{log_ms, meteora, orca};
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::{ResolveOr, Signal};
+use log::{error, info};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+/// Pending ranges merged into a single recalculation, the ranges of a backfill are adjacent
+const BATCH: i64 = 100;
+
+/// Recalculates the candles of block times whose swaps got indexed after the candle workers
+/// passed them. Summaries and twaps cover a trailing window and get recalculated continuously,
+/// so they pick up the swaps on their own.
+pub fn solana_recalculate(cfg: SolanaRecalculateConfig, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        if cfg.active.resolve_or(false) != true {
+            info!("not active");
+            return;
+        }
+
+        info!("active");
+        let pool = setup_pool(cfg).await;
+
+        let mut interval = interval(Duration::from_secs(1));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    if let Err(err) = recalculate(&pool).await {
+                        error!("error recalculating candles: {:?}", err);
+                    }
+                }
+                _ = signal.recv() => {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+async fn recalculate(pool: &PgPool) -> RepoResult<()> {
+    let mut tx = pool.begin().await?;
+
+    // the claimed ranges stay pending unless every venue got recalculated
+    if let Some((from, to)) = AggregateRangeRepo::claim(&mut tx, BATCH).await? {
+        for venue in CANDLE_VENUES {
+            log_ms!(venue, async {
+                VenueCandleRepo::recalculate(&mut tx, venue, from.clone(), to.clone()).await
+            })?;
+        }
+
+        log_ms!("meteora", async {
+            meteora::repo::CandleRepo::recalculate(&mut tx, from.clone(), to.clone()).await
+        })?;
+
+        log_ms!("orca", async {
+            orca::repo::CandleRepo::recalculate(&mut tx, from, to).await
+        })?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
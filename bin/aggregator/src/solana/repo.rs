@@ -0,0 +1,431 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::{partition_count, partition_remainder};
+use common::model::{Partition, Timestamp};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Postgres, Row, Transaction};
+
+pub(crate) struct FailedRepo {}
+
+impl FailedRepo {
+    pub(crate) async fn calculate_1m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1m", "1 minute").await
+    }
+
+    pub(crate) async fn calculate_5m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "5m", "5 minutes").await
+    }
+
+    pub(crate) async fn calculate_15m(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "15m", "15 minutes").await
+    }
+
+    pub(crate) async fn calculate_1h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1h", "1 hour").await
+    }
+
+    pub(crate) async fn calculate_6h(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "6h", "6 hours").await
+    }
+
+    pub(crate) async fn calculate_1d(
+        executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        Self::calculate(executor, partition, "1d", "1 day").await
+    }
+
+    /// Deletes the failed transactions which fell out of the largest window, those without a
+    /// token pair are handled by the first partition
+    pub(crate) async fn prune(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+delete from solana.failed_transaction
+where timestamp < now() - interval '1 day'
+  and coalesce(token_pair_id, 0) % $2 = $1;
+"#,
+        )
+        .bind(partition_remainder(partition))
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Summarizes the failed transactions within the trailing window, token pairs without failed
+    /// transactions get removed
+    async fn calculate(
+        mut executor: impl AsSqlExecutor,
+        partition: Partition,
+        table: &str,
+        window: &str,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+insert into solana.failed_transaction_{table} (token_pair_id, failed, failed_buy, failed_sell, wallet, wallet_buy, updated_at)
+select
+    token_pair_id,
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    count(distinct address_id),
+    count(distinct address_id) filter (where is_buy),
+    now()
+from solana.failed_transaction
+where timestamp > now() - $2::interval
+  and token_pair_id is not null
+  and token_pair_id % $3 = $1
+group by token_pair_id
+on conflict (token_pair_id) do update set
+    failed = excluded.failed,
+    failed_buy = excluded.failed_buy,
+    failed_sell = excluded.failed_sell,
+    wallet = excluded.wallet,
+    wallet_buy = excluded.wallet_buy,
+    updated_at = now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(window)
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        // now() is the start of the transaction, everything updated above has exactly that time
+        query(&format!(
+            r#"
+delete from solana.failed_transaction_{table}
+where token_pair_id % $2 = $1
+  and updated_at < now();
+"#
+        ))
+        .bind(partition_remainder(partition))
+        .bind(partition_count())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub(crate) struct AggregateRangeRepo {}
+
+impl AggregateRangeRepo {
+    /// Marks the oldest pending ranges as recalculated and returns the range covering all of them.
+    /// The ranges stay pending if the transaction gets rolled back.
+    pub(crate) async fn claim(
+        mut executor: impl AsSqlExecutor,
+        limit: i64,
+    ) -> RepoResult<Option<(Timestamp, Timestamp)>> {
+        let row = query(
+            r#"
+with claimed as (
+    select id, from_timestamp, to_timestamp
+    from solana.aggregate_range
+    where recalculated_at is null
+    order by id
+    limit $1
+    for update skip locked
+),
+recalculated as (
+    update solana.aggregate_range r set recalculated_at = now()
+    from claimed c
+    where r.id = c.id
+)
+select min(from_timestamp) as from_timestamp, max(to_timestamp) as to_timestamp from claimed;
+"#,
+        )
+        .bind(limit)
+        .fetch_one(executor.as_executor())
+        .await?;
+
+        Ok(row
+            .get::<Option<Timestamp>, _>("from_timestamp")
+            .zip(row.get::<Option<Timestamp>, _>("to_timestamp")))
+    }
+}
+
+/// Venues whose candles get calculated by the solana crate, recalculated here over a time range
+pub(crate) const CANDLE_VENUES: [&str; 4] = ["pumpfun", "pumpswap", "pumpup", "jupiter"];
+
+pub(crate) struct VenueCandleRepo {}
+
+impl VenueCandleRepo {
+    /// Recalculates every candle of the buckets overlapping [from, to] of the venue across all
+    /// partitions. Columns maintained by other workers, e.g. the twap, are kept.
+    pub(crate) async fn recalculate(
+        tx: &mut Transaction<'_, Postgres>,
+        venue: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        for (table, bucket) in [("1s", "1 second"), ("1m", "1 minute")] {
+            Self::from_swaps_between(&mut *tx, venue, table, bucket, from.clone(), to.clone())
+                .await?;
+        }
+        for (source, table, bucket) in [
+            ("1m", "5m", "5 minutes"),
+            ("5m", "15m", "15 minutes"),
+            ("15m", "1h", "1 hour"),
+            ("1h", "6h", "6 hours"),
+            ("1h", "1d", "1 day"),
+        ] {
+            Self::roll_up_between(
+                &mut *tx,
+                venue,
+                source,
+                table,
+                bucket,
+                from.clone(),
+                to.clone(),
+            )
+            .await?;
+        }
+        for (table, bucket) in [
+            ("1m", "1 minute"),
+            ("5m", "5 minutes"),
+            ("15m", "15 minutes"),
+            ("1h", "1 hour"),
+            ("6h", "6 hours"),
+            ("1d", "1 day"),
+        ] {
+            Self::mcap_between(&mut *tx, venue, table, bucket, from.clone(), to.clone()).await?;
+            Self::usd_between(&mut *tx, venue, table, bucket, from.clone(), to.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Updates the candles of the buckets overlapping [from, to] from their swaps,
+    /// buckets without swaps left get removed
+    async fn from_swaps_between(
+        mut executor: impl AsSqlExecutor,
+        venue: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from {venue}.candle_{table} c
+where c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0))
+  and not exists (
+    select 1 from {venue}.swap s
+    where s.token_pair_id = c.token_pair_id
+      and s.timestamp >= c.timestamp
+      and s.timestamp < c.timestamp + $1::interval
+  );
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into {venue}.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price order by id))[1],
+    max(price),
+    min(price),
+    (array_agg(price order by id desc))[1],
+    sum(amount_quote),
+    count(*),
+    count(*) filter (where is_buy),
+    count(*) filter (where not is_buy),
+    now()
+from {venue}.swap
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Updates the candles of the buckets overlapping [from, to] from the candles of a smaller
+    /// timeframe, buckets without candles left get removed
+    async fn roll_up_between(
+        mut executor: impl AsSqlExecutor,
+        venue: &str,
+        source: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+delete from {venue}.candle_{table} c
+where c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0))
+  and not exists (
+    select 1 from {venue}.candle_{source} s
+    where s.token_pair_id = c.token_pair_id
+      and s.timestamp >= c.timestamp
+      and s.timestamp < c.timestamp + $1::interval
+  );
+"#
+        ))
+        .bind(bucket)
+        .bind(from.clone())
+        .bind(to.clone())
+        .execute(executor.as_executor())
+        .await?;
+
+        query(&format!(
+            r#"
+insert into {venue}.candle_{table} (token_pair_id, timestamp, price_open, price_high, price_low, price_close, volume, swap, swap_buy, swap_sell, updated_at)
+select
+    token_pair_id,
+    date_bin($1::interval, timestamp, to_timestamp(0)) as bucket,
+    (array_agg(price_open order by timestamp))[1],
+    max(price_high),
+    min(price_low),
+    (array_agg(price_close order by timestamp desc))[1],
+    sum(volume),
+    sum(swap),
+    sum(swap_buy),
+    sum(swap_sell),
+    now()
+from {venue}.candle_{source}
+where timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and timestamp < date_bin($1::interval, $3, to_timestamp(0)) + $1::interval
+group by token_pair_id, bucket
+on conflict (token_pair_id, timestamp) do update set
+    price_open = excluded.price_open,
+    price_high = excluded.price_high,
+    price_low = excluded.price_low,
+    price_close = excluded.price_close,
+    volume = excluded.volume,
+    swap = excluded.swap,
+    swap_buy = excluded.swap_buy,
+    swap_sell = excluded.swap_sell,
+    updated_at = now();
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mcap_between(
+        mut executor: impl AsSqlExecutor,
+        venue: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update {venue}.candle_{table} c set
+    market_cap_open = c.price_open * t.supply,
+    market_cap_high = c.price_high * t.supply,
+    market_cap_low = c.price_low * t.supply,
+    market_cap_close = c.price_close * t.supply,
+    updated_at = now()
+from solana.token_pair tp
+join solana.token t on t.id = tp.base_id
+where tp.id = c.token_pair_id
+  and t.supply is not null
+  and c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Usd values by the sol price of the same bucket, token pairs not quoted in sol (e.g. the
+    /// ones of pumpup) keep theirs until the usd worker of the venue passes them again
+    async fn usd_between(
+        mut executor: impl AsSqlExecutor,
+        venue: &str,
+        table: &str,
+        bucket: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RepoResult<()> {
+        query(&format!(
+            r#"
+update {venue}.candle_{table} c set
+    price_open_usd = c.price_open * sol.usd,
+    price_high_usd = c.price_high * sol.usd,
+    price_low_usd = c.price_low * sol.usd,
+    price_close_usd = c.price_close * sol.usd,
+    market_cap_open_usd = c.market_cap_open * sol.usd,
+    market_cap_high_usd = c.market_cap_high * sol.usd,
+    market_cap_low_usd = c.market_cap_low * sol.usd,
+    market_cap_close_usd = c.market_cap_close * sol.usd,
+    volume_usd = c.volume * sol.usd,
+    updated_at = now()
+from solana.sol_price_{table} sol, solana.token_pair tp, solana.token q
+where c.timestamp = sol.timestamp
+  and tp.id = c.token_pair_id
+  and q.id = tp.quote_id
+  and q.mint = 'So11111111111111111111111111111111111111112'
+  and c.timestamp >= date_bin($1::interval, $2, to_timestamp(0))
+  and c.timestamp <= date_bin($1::interval, $3, to_timestamp(0));
+"#
+        ))
+        .bind(bucket)
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
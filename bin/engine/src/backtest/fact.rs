@@ -0,0 +1,156 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::{CandleSeries, TIMEFRAMES};
+use base::model::Fact::{
+    AgeBaseDuration, MarketCapQuote, MarketCapQuoteAggregate, MarketCapSolAggregate, PriceQuote,
+    SwapAllChangeAggregate, SwapAllCountAggregate, SwapAllPercentAggregate, SwapBuyCountAggregate,
+    SwapBuyPercentAggregate, SwapSellCountAggregate, SwapSellPercentAggregate, VenuePumpfun,
+    VenuePumpup,
+};
+use base::model::{Facts, TokenPairId, Value, Venue};
+use bigdecimal::BigDecimal;
+use common::model::TimeUnit;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PairInfo {
+    pub(crate) created: Option<i64>,
+    pub(crate) supply: Option<BigDecimal>,
+    /// whether the quote token is wrapped sol, otherwise quote values are no sol values
+    pub(crate) quote_sol: bool,
+}
+
+pub(crate) async fn list_pair_info(
+    mut executor: impl AsSqlExecutor,
+) -> RepoResult<HashMap<TokenPairId, PairInfo>> {
+    Ok(query(
+        r#"
+select
+    tp.id,
+    extract(epoch from t.block_time)::int8 as created,
+    t.supply,
+    q.mint = 'So11111111111111111111111111111111111111112' as quote_sol
+from solana.token_pair tp
+join solana.token t on t.id = tp.base_id
+join solana.token q on q.id = tp.quote_id;
+"#,
+    )
+    .fetch_all(executor.as_executor())
+    .await?
+    .into_iter()
+    .map(|r| {
+        (
+            r.get::<TokenPairId, _>("id"),
+            PairInfo {
+                created: r.get::<Option<i64>, _>("created"),
+                supply: r.get::<Option<BigDecimal>, _>("supply"),
+                quote_sol: r.get::<bool, _>("quote_sol"),
+            },
+        )
+    })
+    .collect())
+}
+
+/// Rebuilds the facts of a token pair as they would have been at the given point in time.
+/// Only the price, market cap and swap aggregates of the summary can be derived from candles.
+/// Usd values, curve progress, holder, creator, metadata, lifecycle and indicator facts are not
+/// supported and not set, so conditions on them never match.
+pub(crate) fn facts_at(
+    venue: Venue,
+    series: &CandleSeries,
+    at: i64,
+    info: Option<&PairInfo>,
+) -> Facts {
+    let mut facts = Facts::new();
+
+    match venue {
+        Venue::PumpFun => facts.set_value(VenuePumpfun, Value::boolean(true)),
+        Venue::PumpUp => facts.set_value(VenuePumpup, Value::boolean(true)),
+        _ => {}
+    }
+
+    let supply = info.and_then(|i| i.supply.clone());
+    let quote_sol = info.is_some_and(|i| i.quote_sol);
+
+    if let Some(created) = info.and_then(|i| i.created) {
+        facts.set_value(
+            AgeBaseDuration,
+            Value::duration(at - created, TimeUnit::Second),
+        );
+    }
+
+    if let Some(price) = series.close_before(at - 59) {
+        facts.set_value(PriceQuote, Value::quote(price.clone()));
+        if let Some(supply) = &supply {
+            facts.set_value(MarketCapQuote, Value::quote(price * supply));
+        }
+    }
+
+    for (timeframe, _, seconds) in TIMEFRAMES {
+        let Some(current) = series.window(at - seconds, at) else {
+            continue;
+        };
+
+        if let Some(supply) = &supply {
+            let cap = &current.close * supply;
+            facts.set_timeframe_value(
+                MarketCapQuoteAggregate,
+                Value::quote(cap.clone()),
+                timeframe,
+            );
+            if quote_sol {
+                facts.set_timeframe_value(MarketCapSolAggregate, Value::sol(cap), timeframe);
+            }
+        }
+
+        facts.set_timeframe_value(SwapAllCountAggregate, Value::count(current.swap), timeframe);
+        facts.set_timeframe_value(
+            SwapBuyCountAggregate,
+            Value::count(current.swap_buy),
+            timeframe,
+        );
+        facts.set_timeframe_value(
+            SwapSellCountAggregate,
+            Value::count(current.swap_sell),
+            timeframe,
+        );
+
+        let Some(previous) = series.window(at - 2 * seconds, at - seconds) else {
+            continue;
+        };
+
+        facts.set_timeframe_value(
+            SwapAllChangeAggregate,
+            Value::count(current.swap - previous.swap),
+            timeframe,
+        );
+
+        if let Some(percent) = percent(current.swap, previous.swap) {
+            facts.set_timeframe_value(SwapAllPercentAggregate, Value::percent(percent), timeframe);
+        }
+
+        if let Some(percent) = percent(current.swap_buy, previous.swap_buy) {
+            facts.set_timeframe_value(SwapBuyPercentAggregate, Value::percent(percent), timeframe);
+        }
+
+        if let Some(percent) = percent(current.swap_sell, previous.swap_sell) {
+            facts.set_timeframe_value(SwapSellPercentAggregate, Value::percent(percent), timeframe);
+        }
+    }
+
+    facts
+}
+
+fn percent(current: i64, previous: i64) -> Option<BigDecimal> {
+    if previous == 0 {
+        return None;
+    }
+    Some(
+        (BigDecimal::from(current - previous) * BigDecimal::from(100) / BigDecimal::from(previous))
+            .round(2),
+    )
+}
@@ -0,0 +1,220 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+mod fact;
+mod report;
+
+pub use crate::backtest::report::{BacktestMatch, BacktestOutcome, BacktestReport};
+
+use crate::backtest::fact::{facts_at, list_pair_info, PairInfo};
+use crate::candle::{list_candles, CandleQuery, CandleSeries};
+use crate::command::BacktestCommand;
+use crate::config::RuleConfig;
+use crate::rule::trigger::{TriggerPolicy, TriggerRepo, TriggerState};
+use base::model::{Condition, RuleId, TokenPairId, Venue};
+use bigdecimal::BigDecimal;
+use common::model::{Limit, Timeframe, Timestamp};
+use common::repo::pool::setup_pool;
+use common::repo::{RepoResult, Tx};
+use common::sql::AsSqlExecutor;
+use log::info;
+use sqlx::{query, PgPool, Row};
+use std::collections::HashMap;
+
+const DAY: i64 = 86_400;
+
+/// Condition and trigger policy of the rule to backtest, no matter whether the rule is active
+#[derive(Debug, Clone)]
+pub struct BacktestRule {
+    pub id: RuleId,
+    pub condition: Condition,
+    pub trigger: TriggerPolicy,
+}
+
+impl BacktestRule {
+    pub async fn load(pool: &PgPool, rule: RuleId) -> RepoResult<Option<Self>> {
+        let mut tx = pool.begin().await?;
+
+        let condition = query(
+            r#"
+select (sequence::jsonb -> 'condition')::text as condition
+from solana.rule
+where id = $1;
+"#,
+        )
+        .bind(rule)
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|r| r.get::<String, _>("condition"));
+
+        let Some(condition) = condition else {
+            return Ok(None);
+        };
+
+        let trigger = TriggerRepo::get_policy(&mut tx, rule).await?;
+        tx.commit().await?;
+
+        Ok(Some(Self {
+            id: rule,
+            condition: serde_json::from_str(&condition).expect("condition is deserializable"),
+            trigger,
+        }))
+    }
+}
+
+pub async fn run_backtest(cfg: RuleConfig, cmd: BacktestCommand) {
+    let pool = setup_pool(cfg).await;
+
+    let rule = BacktestRule::load(&pool, RuleId::from(cmd.rule))
+        .await
+        .unwrap()
+        .expect("rule not found");
+
+    info!("usd, curve, holder, creator, metadata, lifecycle and indicator facts are not supported");
+
+    let report = backtest(&pool, &rule, &cmd).await;
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+}
+
+/// Replays the rule condition against point in time facts, rebuilt from the stored 1m candles
+/// within the requested range. Token pairs get replayed one after another, so that only the
+/// candles of a single token pair are kept in memory. A match is only reported if the trigger
+/// policy of the rule would have allowed the invocation at that time.
+/// Facts which can not be derived from candles are not supported, see [`facts_at`].
+pub async fn backtest(pool: &PgPool, rule: &BacktestRule, cmd: &BacktestCommand) -> BacktestReport {
+    let mut tx = pool.begin().await.unwrap();
+
+    let from = to_epoch(&mut tx, &cmd.from).await.unwrap();
+    let to = to_epoch(&mut tx, &cmd.to).await.unwrap();
+
+    let pairs: HashMap<TokenPairId, PairInfo> = list_pair_info(&mut tx).await.unwrap();
+    let token_pairs = list_venue_pairs(&mut tx, cmd.venue).await;
+    info!("replaying {} token pairs", token_pairs.len());
+
+    let mut matches = Vec::new();
+
+    for token_pair in token_pairs {
+        let info = pairs.get(&token_pair);
+        if info
+            .and_then(|i| i.created)
+            .is_some_and(|created| created > to)
+        {
+            continue;
+        }
+
+        // two days before the range, the change of the largest timeframe compares the day before
+        // with the one before that, and one day after to evaluate outcomes
+        let candles = list_candles(
+            &mut tx,
+            CandleQuery {
+                venue: cmd.venue,
+                token_pair: Some(token_pair),
+                timeframe: Timeframe::M1,
+                from: Timestamp::from_epoch_second(from - 2 * DAY).unwrap(),
+                to: Timestamp::from_epoch_second(to + DAY).unwrap(),
+            },
+        )
+        .await
+        .unwrap();
+
+        if candles.is_empty() {
+            continue;
+        }
+
+        let series = CandleSeries::new(candles);
+        let mut state: Option<TriggerState> = None;
+
+        for candle in series.candles() {
+            // facts are evaluated when the candle closes
+            let at = candle.epoch + 60;
+            if at < from || at > to {
+                continue;
+            }
+
+            let facts = facts_at(cmd.venue, &series, at, info);
+            let matched = rule.condition.test(&facts);
+
+            if matched && rule.trigger.should_trigger(state.as_ref(), at) {
+                matches.push(BacktestMatch {
+                    token_pair,
+                    timestamp: at,
+                    price: candle.close.clone(),
+                    outcome: BacktestOutcome {
+                        m5: change(&series, &candle.close, at + 300),
+                        h1: change(&series, &candle.close, at + 3_600),
+                        d1: change(&series, &candle.close, at + DAY),
+                    },
+                });
+
+                state = Some(TriggerState {
+                    count: state.as_ref().map(|s| s.count).unwrap_or_default() + 1,
+                    matched: true,
+                    triggered_at: at,
+                });
+            } else if let Some(state) = state.as_mut() {
+                state.matched = matched;
+            }
+        }
+    }
+
+    tx.commit().await.unwrap();
+
+    matches.sort_by_key(|m| m.timestamp);
+
+    BacktestReport {
+        rule: rule.id,
+        venue: cmd.venue,
+        from,
+        to,
+        matches,
+    }
+}
+
+/// Token pairs which got traded on the venue
+async fn list_venue_pairs<'a>(tx: &mut Tx<'a>, venue: Venue) -> Vec<TokenPairId> {
+    match venue {
+        Venue::PumpFun => solana::pumpfun::repo::CurrentRepo::list(
+            &mut *tx,
+            solana::pumpfun::repo::CurrentQuery {
+                limit: Limit::unlimited(),
+            },
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect(),
+        Venue::PumpUp => solana::pumpup::repo::CurrentRepo::list(
+            &mut *tx,
+            solana::pumpup::repo::CurrentQuery {
+                limit: Limit::unlimited(),
+            },
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.id)
+        .collect(),
+        _ => vec![],
+    }
+}
+
+async fn to_epoch(mut executor: impl AsSqlExecutor, timestamp: &str) -> RepoResult<i64> {
+    Ok(
+        query("select extract(epoch from $1::timestamptz)::int8 as epoch")
+            .bind(timestamp)
+            .fetch_one(executor.as_executor())
+            .await?
+            .get::<i64, _>("epoch"),
+    )
+}
+
+/// Percent change between the price and the close of the last candle closed by the given time
+fn change(series: &CandleSeries, price: &BigDecimal, at: i64) -> Option<BigDecimal> {
+    if price == &BigDecimal::from(0) {
+        return None;
+    }
+
+    let close = series.close_before(at - 59)?;
+    Some(((close - price) * BigDecimal::from(100) / price).round(2))
+}
@@ -0,0 +1,32 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::{RuleId, TokenPairId, Venue};
+use bigdecimal::BigDecimal;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct BacktestReport {
+    pub rule: RuleId,
+    pub venue: Venue,
+    pub from: i64,
+    pub to: i64,
+    pub matches: Vec<BacktestMatch>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BacktestMatch {
+    pub token_pair: TokenPairId,
+    /// point in time the rule would have fired, in seconds since epoch
+    pub timestamp: i64,
+    pub price: BigDecimal,
+    pub outcome: BacktestOutcome,
+}
+
+/// Price change in percent after the rule would have fired
+#[derive(Debug, Serialize)]
+pub struct BacktestOutcome {
+    pub m5: Option<BigDecimal>,
+    pub h1: Option<BigDecimal>,
+    pub d1: Option<BigDecimal>,
+}
@@ -0,0 +1,321 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+//! Technical indicators over candles ordered by time, the last candle being the most recent one.
+//! All functions return none if there are not enough candles for the requested period.
+
+use crate::candle::Candle;
+use bigdecimal::{BigDecimal, ToPrimitive};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cross {
+    /// the fast average moved above the slow average with the last candle
+    Up,
+    /// the fast average moved below the slow average with the last candle
+    Down,
+}
+
+pub(crate) fn closes(candles: &[Candle]) -> Vec<f64> {
+    candles
+        .iter()
+        .map(|c| c.close.to_f64().unwrap_or_default())
+        .collect()
+}
+
+/// Fills the gaps between candles of a token pair, and after the last candle up to the candle
+/// starting at until, with flat candles at the previous close and without swaps
+pub(crate) fn fill_gaps(candles: &[Candle], seconds: i64, until: i64) -> Vec<Candle> {
+    let mut result: Vec<Candle> = Vec::with_capacity(candles.len());
+
+    for candle in candles.iter().map(Some).chain([None]) {
+        let end = candle.map(|c| c.epoch).unwrap_or(until + seconds);
+
+        if let Some(previous) = result.last().cloned() {
+            let mut epoch = previous.epoch + seconds;
+            while epoch < end {
+                result.push(Candle {
+                    epoch,
+                    open: previous.close.clone(),
+                    high: previous.close.clone(),
+                    low: previous.close.clone(),
+                    volume: BigDecimal::from(0),
+                    swap: 0,
+                    swap_buy: 0,
+                    swap_sell: 0,
+                    ..previous.clone()
+                });
+                epoch += seconds;
+            }
+        }
+
+        if let Some(candle) = candle {
+            result.push(candle.clone());
+        }
+    }
+
+    result
+}
+
+/// Last n values, or all of them if there are fewer
+pub(crate) fn last<T>(values: &[T], n: usize) -> &[T] {
+    &values[values.len().saturating_sub(n)..]
+}
+
+/// Simple moving average of the last period values
+pub fn sma(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() < period {
+        return None;
+    }
+    Some(values[values.len() - period..].iter().sum::<f64>() / period as f64)
+}
+
+/// Exponential moving average, seeded with the simple moving average of the first period values
+pub fn ema(values: &[f64], period: usize) -> Option<f64> {
+    let seed = sma(&values[..values.len().min(period)], period)?;
+    let k = 2.0 / (period as f64 + 1.0);
+    Some(
+        values[period..]
+            .iter()
+            .fold(seed, |ema, value| value * k + ema * (1.0 - k)),
+    )
+}
+
+/// Relative strength index with Wilder's smoothing, between 0 and 100
+pub fn rsi(values: &[f64], period: usize) -> Option<f64> {
+    if period == 0 || values.len() < period + 1 {
+        return None;
+    }
+
+    let changes: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+
+    let mut gain = changes[..period].iter().filter(|c| **c > 0.0).sum::<f64>() / period as f64;
+    let mut loss = -changes[..period].iter().filter(|c| **c < 0.0).sum::<f64>() / period as f64;
+
+    for change in &changes[period..] {
+        gain = (gain * (period - 1) as f64 + change.max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change).max(0.0)) / period as f64;
+    }
+
+    if loss == 0.0 {
+        return Some(if gain == 0.0 { 50.0 } else { 100.0 });
+    }
+
+    Some(100.0 - 100.0 / (1.0 + gain / loss))
+}
+
+/// Whether the fast average crossed the slow average with the last value
+pub fn cross(
+    values: &[f64],
+    fast: usize,
+    slow: usize,
+    average: fn(&[f64], usize) -> Option<f64>,
+) -> Option<Cross> {
+    if values.len() < 2 {
+        return None;
+    }
+    let previous = &values[..values.len() - 1];
+
+    let before = average(previous, fast)? - average(previous, slow)?;
+    let now = average(values, fast)? - average(values, slow)?;
+
+    if before <= 0.0 && now > 0.0 {
+        Some(Cross::Up)
+    } else if before >= 0.0 && now < 0.0 {
+        Some(Cross::Down)
+    } else {
+        None
+    }
+}
+
+/// Deviation of the last close from the volume weighted average price in percent
+pub fn vwap_deviation(candles: &[Candle], period: usize) -> Option<f64> {
+    if period == 0 || candles.len() < period {
+        return None;
+    }
+
+    let (weighted, volume) =
+        candles[candles.len() - period..]
+            .iter()
+            .fold((0.0, 0.0), |(weighted, volume), c| {
+                let typical = (c.high.to_f64().unwrap_or_default()
+                    + c.low.to_f64().unwrap_or_default()
+                    + c.close.to_f64().unwrap_or_default())
+                    / 3.0;
+                let v = c.volume.to_f64().unwrap_or_default();
+                (weighted + typical * v, volume + v)
+            });
+
+    if volume == 0.0 {
+        return None;
+    }
+
+    let vwap = weighted / volume;
+    let close = candles.last()?.close.to_f64()?;
+    Some((close - vwap) / vwap * 100.0)
+}
+
+/// Position of the last close within the bollinger bands in percent,
+/// 0 being the lower and 100 the upper band
+pub fn bollinger_position(values: &[f64], period: usize, width: f64) -> Option<f64> {
+    let mean = sma(values, period)?;
+    let window = &values[values.len() - period..];
+    let deviation = (window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / period as f64).sqrt();
+
+    if deviation == 0.0 {
+        return None;
+    }
+
+    let lower = mean - width * deviation;
+    let upper = mean + width * deviation;
+    Some((values.last()? - lower) / (upper - lower) * 100.0)
+}
+
+/// Average true range with Wilder's smoothing
+pub fn atr(candles: &[Candle], period: usize) -> Option<f64> {
+    if period == 0 || candles.len() < period + 1 {
+        return None;
+    }
+
+    let ranges: Vec<f64> = candles
+        .windows(2)
+        .map(|w| {
+            let previous = w[0].close.to_f64().unwrap_or_default();
+            let high = w[1].high.to_f64().unwrap_or_default();
+            let low = w[1].low.to_f64().unwrap_or_default();
+            (high - low)
+                .max((high - previous).abs())
+                .max((low - previous).abs())
+        })
+        .collect();
+
+    let seed = ranges[..period].iter().sum::<f64>() / period as f64;
+    Some(ranges[period..].iter().fold(seed, |atr, range| {
+        (atr * (period - 1) as f64 + range) / period as f64
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candle::indicator::{
+        atr, bollinger_position, cross, ema, fill_gaps, last, rsi, sma, vwap_deviation, Cross,
+    };
+    use crate::candle::Candle;
+    use bigdecimal::{BigDecimal, FromPrimitive};
+
+    fn candle(high: f64, low: f64, close: f64, volume: f64) -> Candle {
+        Candle {
+            token_pair: 1.into(),
+            epoch: 0,
+            open: BigDecimal::from_f64(close).unwrap(),
+            high: BigDecimal::from_f64(high).unwrap(),
+            low: BigDecimal::from_f64(low).unwrap(),
+            close: BigDecimal::from_f64(close).unwrap(),
+            volume: BigDecimal::from_f64(volume).unwrap(),
+            swap: 1,
+            swap_buy: 1,
+            swap_sell: 0,
+        }
+    }
+
+    fn assert_close(value: Option<f64>, expected: f64) {
+        let value = value.unwrap();
+        assert!((value - expected).abs() < 1e-6, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_fill_gaps() {
+        let candles = vec![
+            Candle {
+                epoch: 0,
+                ..candle(3.0, 1.0, 2.0, 5.0)
+            },
+            Candle {
+                epoch: 180,
+                ..candle(6.0, 4.0, 5.0, 1.0)
+            },
+        ];
+
+        let filled = fill_gaps(&candles, 60, 300);
+        assert_eq!(
+            filled.iter().map(|c| c.epoch).collect::<Vec<_>>(),
+            vec![0, 60, 120, 180, 240, 300]
+        );
+
+        let gap = &filled[1];
+        assert_eq!(gap.open, BigDecimal::from(2));
+        assert_eq!(gap.high, BigDecimal::from(2));
+        assert_eq!(gap.low, BigDecimal::from(2));
+        assert_eq!(gap.close, BigDecimal::from(2));
+        assert_eq!(gap.volume, BigDecimal::from(0));
+        assert_eq!(gap.swap, 0);
+
+        assert_eq!(filled[3].close, BigDecimal::from(5));
+        assert_eq!(filled[5].close, BigDecimal::from(5));
+
+        assert_eq!(fill_gaps(&candles, 60, 180).len(), 4);
+        assert!(fill_gaps(&[], 60, 300).is_empty());
+    }
+
+    #[test]
+    fn test_last() {
+        assert_eq!(last(&[1, 2, 3], 2), &[2, 3]);
+        assert_eq!(last(&[1, 2, 3], 5), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sma() {
+        assert_eq!(sma(&[1.0, 2.0], 3), None);
+        assert_close(sma(&[1.0, 2.0, 3.0, 4.0], 2), 3.5);
+    }
+
+    #[test]
+    fn test_ema() {
+        assert_eq!(ema(&[1.0, 2.0], 3), None);
+        assert_close(ema(&[2.0, 4.0, 6.0], 3), 4.0);
+        // k = 0.5, seed 4 -> 8 * 0.5 + 4 * 0.5
+        assert_close(ema(&[2.0, 4.0, 6.0, 8.0], 3), 6.0);
+    }
+
+    #[test]
+    fn test_rsi() {
+        assert_eq!(rsi(&[1.0, 2.0], 2), None);
+        assert_close(rsi(&[1.0, 2.0, 3.0, 4.0], 3), 100.0);
+        assert_close(rsi(&[4.0, 3.0, 2.0, 1.0], 3), 0.0);
+        assert_close(rsi(&[1.0, 1.0, 1.0], 2), 50.0);
+        // gains 2, losses 1 -> rs 2
+        assert_close(rsi(&[1.0, 3.0, 2.0], 2), 100.0 - 100.0 / 3.0);
+    }
+
+    #[test]
+    fn test_cross() {
+        assert_eq!(cross(&[3.0, 2.0, 1.0, 4.0], 1, 3, sma), Some(Cross::Up));
+        assert_eq!(cross(&[1.0, 2.0, 3.0, 0.0], 1, 3, sma), Some(Cross::Down));
+        assert_eq!(cross(&[1.0, 2.0, 3.0, 4.0], 1, 3, sma), None);
+    }
+
+    #[test]
+    fn test_vwap_deviation() {
+        let candles = vec![candle(10.0, 10.0, 10.0, 1.0), candle(20.0, 20.0, 20.0, 1.0)];
+        assert_close(vwap_deviation(&candles, 2), (20.0 - 15.0) / 15.0 * 100.0);
+        assert_eq!(vwap_deviation(&candles, 3), None);
+    }
+
+    #[test]
+    fn test_bollinger_position() {
+        assert_close(bollinger_position(&[1.0, 3.0, 2.0], 3, 2.0), 50.0);
+        assert_eq!(bollinger_position(&[2.0, 2.0], 2, 2.0), None);
+        assert!(bollinger_position(&[1.0, 1.0, 1.0, 9.0], 4, 1.0).unwrap() > 100.0);
+    }
+
+    #[test]
+    fn test_atr() {
+        let candles = vec![
+            candle(10.0, 8.0, 9.0, 1.0),
+            candle(12.0, 9.0, 11.0, 1.0),
+            candle(11.0, 10.0, 10.0, 1.0),
+        ];
+        // true ranges 3 and 1
+        assert_close(atr(&candles, 2), 2.0);
+        assert_eq!(atr(&candles, 3), None);
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub mod indicator;
+
+use base::model::{TokenPairId, Venue};
+use bigdecimal::BigDecimal;
+use common::model::{Timeframe, Timestamp};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+
+pub(crate) const TIMEFRAMES: [(Timeframe, &str, i64); 6] = [
+    (Timeframe::M1, "1m", 60),
+    (Timeframe::M5, "5m", 300),
+    (Timeframe::M15, "15m", 900),
+    (Timeframe::H1, "1h", 3_600),
+    (Timeframe::H6, "6h", 21_600),
+    (Timeframe::D1, "1d", 86_400),
+];
+
+#[derive(Debug, Clone)]
+pub struct Candle {
+    pub token_pair: TokenPairId,
+    /// start of the candle in seconds since epoch
+    pub epoch: i64,
+    pub open: BigDecimal,
+    pub high: BigDecimal,
+    pub low: BigDecimal,
+    pub close: BigDecimal,
+    pub volume: BigDecimal,
+    pub swap: i64,
+    pub swap_buy: i64,
+    pub swap_sell: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CandleQuery {
+    pub venue: Venue,
+    /// candles of all token pairs of the venue if not set
+    pub token_pair: Option<TokenPairId>,
+    pub timeframe: Timeframe,
+    pub from: Timestamp,
+    pub to: Timestamp,
+}
+
+/// Reads the candles the aggregator maintains through the candle repo of the venue,
+/// ordered by token pair and time
+pub async fn list_candles(
+    executor: impl AsSqlExecutor,
+    candle_query: CandleQuery,
+) -> RepoResult<Vec<Candle>> {
+    macro_rules! list {
+        ($venue:ident) => {
+            solana::$venue::repo::CandleRepo::new()
+                .list(
+                    executor,
+                    solana::$venue::repo::CandleQuery {
+                        token_pair: candle_query.token_pair,
+                        timeframe: candle_query.timeframe,
+                        from: candle_query.from,
+                        to: candle_query.to,
+                    },
+                )
+                .await?
+                .into_iter()
+                .map(|c| Candle {
+                    token_pair: c.token_pair,
+                    epoch: c.timestamp.to_epoch_second(),
+                    open: c.open.0,
+                    high: c.high.0,
+                    low: c.low.0,
+                    close: c.close.0,
+                    volume: c.volume.0,
+                    swap: c.swap.0,
+                    swap_buy: c.swap_buy.0,
+                    swap_sell: c.swap_sell.0,
+                })
+                .collect()
+        };
+    }
+
+    Ok(match candle_query.venue {
+        Venue::PumpFun => list!(pumpfun),
+        Venue::PumpSwap => list!(pumpswap),
+        Venue::PumpUp => list!(pumpup),
+        _ => vec![],
+    })
+}
+
+pub(crate) fn table(timeframe: Timeframe) -> &'static str {
+    TIMEFRAMES
+        .iter()
+        .find(|(tf, _, _)| *tf == timeframe)
+        .map(|(_, table, _)| *table)
+        .expect("unsupported timeframe")
+}
+
+/// Candles of a single token pair, sorted by time, which can be rolled up into
+/// windows ending at an arbitrary point in time.
+pub struct CandleSeries {
+    candles: Vec<Candle>,
+    swap: Vec<i64>,
+    swap_buy: Vec<i64>,
+    swap_sell: Vec<i64>,
+    volume: Vec<BigDecimal>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub open: BigDecimal,
+    pub high: BigDecimal,
+    pub low: BigDecimal,
+    pub close: BigDecimal,
+    pub volume: BigDecimal,
+    pub swap: i64,
+    pub swap_buy: i64,
+    pub swap_sell: i64,
+}
+
+impl CandleSeries {
+    pub fn new(candles: Vec<Candle>) -> Self {
+        let mut swap = vec![0];
+        let mut swap_buy = vec![0];
+        let mut swap_sell = vec![0];
+        let mut volume = vec![BigDecimal::from(0)];
+
+        for candle in &candles {
+            swap.push(swap.last().unwrap() + candle.swap);
+            swap_buy.push(swap_buy.last().unwrap() + candle.swap_buy);
+            swap_sell.push(swap_sell.last().unwrap() + candle.swap_sell);
+            volume.push(volume.last().unwrap() + &candle.volume);
+        }
+
+        Self {
+            candles,
+            swap,
+            swap_buy,
+            swap_sell,
+            volume,
+        }
+    }
+
+    pub fn candles(&self) -> &[Candle] {
+        &self.candles
+    }
+
+    /// Index of the first candle starting at or after epoch
+    fn index(&self, epoch: i64) -> usize {
+        self.candles.partition_point(|c| c.epoch < epoch)
+    }
+
+    /// Close of the last candle which started before epoch
+    pub fn close_before(&self, epoch: i64) -> Option<&BigDecimal> {
+        let idx = self.index(epoch);
+        if idx == 0 {
+            None
+        } else {
+            Some(&self.candles[idx - 1].close)
+        }
+    }
+
+    /// Rolls up all candles starting within [from, to)
+    pub fn window(&self, from: i64, to: i64) -> Option<Window> {
+        let start = self.index(from);
+        let end = self.index(to);
+        if start >= end {
+            return None;
+        }
+
+        let candles = &self.candles[start..end];
+        Some(Window {
+            open: candles.first().unwrap().open.clone(),
+            high: candles.iter().map(|c| &c.high).max().unwrap().clone(),
+            low: candles.iter().map(|c| &c.low).min().unwrap().clone(),
+            close: candles.last().unwrap().close.clone(),
+            volume: &self.volume[end] - &self.volume[start],
+            swap: self.swap[end] - self.swap[start],
+            swap_buy: self.swap_buy[end] - self.swap_buy[start],
+            swap_sell: self.swap_sell[end] - self.swap_sell[start],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candle::{Candle, CandleSeries};
+    use bigdecimal::BigDecimal;
+
+    fn candle(epoch: i64, close: i64, swap: i64) -> Candle {
+        Candle {
+            token_pair: 1.into(),
+            epoch,
+            open: BigDecimal::from(close),
+            high: BigDecimal::from(close),
+            low: BigDecimal::from(close),
+            close: BigDecimal::from(close),
+            volume: BigDecimal::from(swap),
+            swap,
+            swap_buy: swap,
+            swap_sell: 0,
+        }
+    }
+
+    #[test]
+    fn test_window() {
+        let series = CandleSeries::new(vec![candle(0, 1, 1), candle(60, 3, 2), candle(180, 2, 4)]);
+
+        let window = series.window(0, 120).unwrap();
+        assert_eq!(window.open, BigDecimal::from(1));
+        assert_eq!(window.high, BigDecimal::from(3));
+        assert_eq!(window.close, BigDecimal::from(3));
+        assert_eq!(window.swap, 3);
+
+        let window = series.window(60, 240).unwrap();
+        assert_eq!(window.low, BigDecimal::from(2));
+        assert_eq!(window.close, BigDecimal::from(2));
+        assert_eq!(window.volume, BigDecimal::from(6));
+
+        assert_eq!(series.window(120, 180), None);
+    }
+
+    #[test]
+    fn test_close_before() {
+        let series = CandleSeries::new(vec![candle(0, 1, 1), candle(60, 3, 2)]);
+        assert_eq!(series.close_before(0), None);
+        assert_eq!(series.close_before(1), Some(&BigDecimal::from(1)));
+        assert_eq!(series.close_before(600), Some(&BigDecimal::from(3)));
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::trigger::TriggerPolicy;
+use base::model::Venue;
+use std::env::args;
+
+const BACKTEST_USAGE: &str = "usage: engine backtest <pumpfun|pumpup> <rule id> <from> <to>";
+const EXPLAIN_USAGE: &str = "usage: engine explain <pumpfun|pumpup> <rule id> <token pair id>";
+const TRIGGER_USAGE: &str =
+    "usage: engine trigger <rule id> [once|edge|cooldown <seconds>|max <count>]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run,
+    Backtest(BacktestCommand),
+    Explain(ExplainCommand),
+    Trigger(TriggerCommand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestCommand {
+    pub venue: Venue,
+    pub rule: i64,
+    /// start of the range, as postgres timestamptz literal e.g. 2025-04-01T00:00:00Z
+    pub from: String,
+    /// end of the range, as postgres timestamptz literal e.g. 2025-04-02T00:00:00Z
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainCommand {
+    pub venue: Venue,
+    pub rule: i64,
+    pub token_pair: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerCommand {
+    pub rule: i64,
+    /// policy to set, the current policy is shown if none is given
+    pub policy: Option<TriggerPolicy>,
+}
+
+impl Command {
+    pub fn load() -> Self {
+        Self::parse(args().skip(1).collect())
+    }
+
+    pub fn parse(args: Vec<String>) -> Self {
+        match args.first().map(String::as_str) {
+            Some("backtest") => Command::Backtest(BacktestCommand {
+                venue: parse_venue(args.get(1)).expect(BACKTEST_USAGE),
+                rule: args
+                    .get(2)
+                    .and_then(|r| r.parse().ok())
+                    .expect(BACKTEST_USAGE),
+                from: args.get(3).cloned().expect(BACKTEST_USAGE),
+                to: args.get(4).cloned().expect(BACKTEST_USAGE),
+            }),
+            Some("explain") => Command::Explain(ExplainCommand {
+                venue: parse_venue(args.get(1)).expect(EXPLAIN_USAGE),
+                rule: args
+                    .get(2)
+                    .and_then(|r| r.parse().ok())
+                    .expect(EXPLAIN_USAGE),
+                token_pair: args
+                    .get(3)
+                    .and_then(|r| r.parse().ok())
+                    .expect(EXPLAIN_USAGE),
+            }),
+            Some("trigger") => Command::Trigger(TriggerCommand {
+                rule: args
+                    .get(1)
+                    .and_then(|r| r.parse().ok())
+                    .expect(TRIGGER_USAGE),
+                policy: (args.len() > 2).then(|| parse_policy(&args[2..]).expect(TRIGGER_USAGE)),
+            }),
+            _ => Command::Run,
+        }
+    }
+}
+
+fn parse_policy(args: &[String]) -> Option<TriggerPolicy> {
+    let value = || {
+        args.get(1)
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|v| *v > 0)
+    };
+    match args.first().map(String::as_str) {
+        Some("once") => Some(TriggerPolicy::Once),
+        Some("edge") => Some(TriggerPolicy::Edge),
+        Some("cooldown") => Some(TriggerPolicy::Cooldown { seconds: value()? }),
+        Some("max") => Some(TriggerPolicy::Max { count: value()? }),
+        _ => None,
+    }
+}
+
+fn parse_venue(value: Option<&String>) -> Option<Venue> {
+    match value.map(String::as_str) {
+        Some("pumpfun") => Some(Venue::PumpFun),
+        Some("pumpup") => Some(Venue::PumpUp),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::{BacktestCommand, Command, ExplainCommand, TriggerCommand};
+    use crate::rule::trigger::TriggerPolicy;
+    use base::model::Venue;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(Command::parse(args(&[])), Command::Run);
+        assert_eq!(Command::parse(args(&["config.toml"])), Command::Run);
+    }
+
+    #[test]
+    fn test_backtest() {
+        assert_eq!(
+            Command::parse(args(&[
+                "backtest",
+                "pumpfun",
+                "14",
+                "2025-04-01T00:00:00Z",
+                "2025-04-02T00:00:00Z"
+            ])),
+            Command::Backtest(BacktestCommand {
+                venue: Venue::PumpFun,
+                rule: 14,
+                from: "2025-04-01T00:00:00Z".to_string(),
+                to: "2025-04-02T00:00:00Z".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            Command::parse(args(&["explain", "pumpup", "14", "23073"])),
+            Command::Explain(ExplainCommand {
+                venue: Venue::PumpUp,
+                rule: 14,
+                token_pair: 23073,
+            })
+        );
+    }
+
+    #[test]
+    fn test_trigger() {
+        assert_eq!(
+            Command::parse(args(&["trigger", "14"])),
+            Command::Trigger(TriggerCommand {
+                rule: 14,
+                policy: None,
+            })
+        );
+        assert_eq!(
+            Command::parse(args(&["trigger", "14", "cooldown", "300"])),
+            Command::Trigger(TriggerCommand {
+                rule: 14,
+                policy: Some(TriggerPolicy::Cooldown { seconds: 300 }),
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_trigger_invalid_policy() {
+        Command::parse(args(&["trigger", "14", "max", "0"]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_backtest_invalid_venue() {
+        Command::parse(args(&["backtest", "raydium", "14", "a", "b"]));
+    }
+}
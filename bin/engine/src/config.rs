@@ -17,6 +17,7 @@ pub struct Config {
     pub rule_pumpfun: Option<RulePumpfunConfig>,
     pub rule_pumpup: Option<RulePumpupConfig>,
     pub tokio: TokioConfig,
+    pub webhook: Option<WebhookConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -120,6 +121,48 @@ impl Default for RuleConfig {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub active: ConfigValue,
+    /// attempts before a delivery is considered dead
+    pub max_attempts: ConfigValue,
+    pub timeout_ms: ConfigValue,
+    /// hex encoded 32 byte key, the secrets of webhook deliveries are encrypted with.
+    /// Required once active, the rule loops encrypt with it as well
+    pub secret_key: ConfigValue,
+
+    pub connection_string: ConfigValue,
+    pub pool_min: ConfigValue,
+    pub pool_max: ConfigValue,
+    pub timeout_acquire_ms: ConfigValue,
+}
+
+impl From<WebhookConfig> for PostgresConfig {
+    fn from(value: WebhookConfig) -> Self {
+        Self {
+            connection_string: value.connection_string,
+            pool_min: value.pool_min,
+            pool_max: value.pool_max,
+            timeout_acquire_ms: value.timeout_acquire_ms,
+        }
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            active: ConfigValue::value(false),
+            max_attempts: ConfigValue::default(),
+            timeout_ms: ConfigValue::default(),
+            secret_key: ConfigValue::default(),
+            connection_string: ConfigValue::default(),
+            pool_min: ConfigValue::default(),
+            pool_max: ConfigValue::default(),
+            timeout_acquire_ms: ConfigValue::default(),
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Self {
         let args: Vec<String> = args().collect();
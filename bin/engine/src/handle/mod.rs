@@ -8,6 +8,7 @@ mod mock;
 
 use crate::config::HandleConfig;
 use crate::handle::mock::MockHandler;
+use crate::notify::{Wakeup, CHANNEL_REQUEST_SUBMITTED};
 use async_trait::async_trait;
 use base::model::RequestToProcess;
 use base::repo::RequestRepo;
@@ -18,7 +19,6 @@ use common::{ConfigValue, ResolveOr};
 use log::info;
 use std::time::Duration;
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Mode {
@@ -66,13 +66,15 @@ pub fn start_handle(cfg: HandleConfig) -> JoinHandle<()> {
             )),
         };
 
+        let mut wakeup = Wakeup::listen(&pool, CHANNEL_REQUEST_SUBMITTED).await;
+
         loop {
             let mut tx = pool.begin().await.unwrap();
             if let Some((_attempt, request)) = RequestRepo::attempt(&mut *tx).await.unwrap() {
                 handler.handle(&mut tx, request).await;
                 tx.commit().await.unwrap();
             }
-            sleep(Duration::from_millis(1000)).await;
+            wakeup.wait(Duration::from_millis(1000)).await;
         }
     })
 }
@@ -1,6 +1,11 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+pub mod backtest;
+pub mod candle;
+pub mod command;
 pub mod config;
 pub mod handle;
-pub mod rule;
\ No newline at end of file
+pub mod notify;
+pub mod rule;
+pub mod webhook;
\ No newline at end of file
@@ -4,9 +4,14 @@
 #![cfg_attr(not(debug_assertions), deny(warnings))]
 
 use common::ResolveOr;
+use engine::backtest::run_backtest;
+use engine::command::Command;
+use engine::rule::explain::run_explain;
 use engine::rule::start_automate;
+use engine::rule::trigger::run_trigger;
 use engine::config::Config;
 use engine::handle::start_handle;
+use engine::webhook::start_webhook;
 use futures::future::join_all;
 use log::{error, info};
 use tokio::runtime::Builder;
@@ -26,24 +31,43 @@ fn main() {
         .init();
 
     let config = Config::load();
+    let command = Command::load();
     info!("Start");
     let tokio_threads = config.tokio.threads.resolve_or(1);
     info!("tokio threads: {}", tokio_threads);
 
-    let runtime = Builder::new_current_thread()
+    let runtime = Builder::new_multi_thread()
         .worker_threads(tokio_threads)
         .enable_all()
         .build()
         .unwrap();
 
+    match command {
+        Command::Run => {}
+        Command::Backtest(cmd) => {
+            runtime.block_on(run_backtest(config.rule.unwrap_or_default(), cmd));
+            return;
+        }
+        Command::Explain(cmd) => {
+            runtime.block_on(run_explain(config.rule.unwrap_or_default(), cmd));
+            return;
+        }
+        Command::Trigger(cmd) => {
+            runtime.block_on(run_trigger(config.rule.unwrap_or_default(), cmd));
+            return;
+        }
+    }
+
     runtime.block_on(async {
         let handles: Vec<JoinHandle<()>> = vec![
             start_automate(
                 config.rule.unwrap_or_default(),
+                config.webhook.clone().unwrap_or_default(),
                 config.rule_pumpfun.unwrap_or_default(),
                 config.rule_pumpup.unwrap_or_default(),
             ),
             start_handle(config.handle.unwrap_or_default()),
+            start_webhook(config.webhook.unwrap_or_default()),
         ];
 
         for result in join_all(handles).await {
@@ -0,0 +1,147 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::TokenPairId;
+use log::warn;
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::{sleep, timeout, Instant};
+
+/// Notified by the indexer after a block got committed, same name as in the indexer
+pub const CHANNEL_BLOCK_INDEXED: &str = "block_indexed";
+/// Notified by the database whenever a token completed its pumpfun bonding curve,
+/// the payload has the same shape as a block notification
+pub const CHANNEL_TOKEN_GRADUATED: &str = "token_graduated";
+/// Notified by the database whenever a request gets inserted
+pub const CHANNEL_REQUEST_SUBMITTED: &str = "request_submitted";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BlockIndexed {
+    pub slot: i64,
+    /// token pairs swapped within the block, none if too many to be sent
+    pub token_pairs: Option<Vec<i64>>,
+}
+
+/// Wakes up a polling loop as soon as something got notified on a channel.
+/// Falls back to plain polling if listening is not possible.
+pub struct Wakeup {
+    listener: Option<PgListener>,
+}
+
+impl Wakeup {
+    pub async fn listen(pool: &PgPool, channel: &str) -> Self {
+        Self::listen_all(pool, &[channel]).await
+    }
+
+    pub async fn listen_all(pool: &PgPool, channels: &[&str]) -> Self {
+        let listener = match PgListener::connect_with(pool).await {
+            Ok(mut listener) => match listener.listen_all(channels.iter().copied()).await {
+                Ok(_) => Some(listener),
+                Err(err) => {
+                    warn!("failed to listen on {channels:?}, fall back to polling: {err:?}");
+                    None
+                }
+            },
+            Err(err) => {
+                warn!("failed to connect listener, fall back to polling: {err:?}");
+                None
+            }
+        };
+
+        Self { listener }
+    }
+
+    /// Waits for the next notification and returns its payload,
+    /// none if nothing got notified within the timeout
+    pub async fn wait(&mut self, duration: Duration) -> Option<String> {
+        let Some(listener) = &mut self.listener else {
+            sleep(duration).await;
+            return None;
+        };
+
+        match timeout(duration, listener.recv()).await {
+            Ok(Ok(notification)) => Some(notification.payload().to_string()),
+            Ok(Err(err)) => {
+                // the listener reconnects with the next recv
+                warn!("failed to receive notification: {err:?}");
+                sleep(duration).await;
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Waits until the deadline for a block, which touched a known set of token pairs,
+    /// or for a graduated token.
+    /// Returns none once the deadline passed or if the block touched too many token pairs,
+    /// in both cases all token pairs should be evaluated.
+    pub async fn block_indexed_until(&mut self, deadline: Instant) -> Option<Vec<TokenPairId>> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+
+            let Some(payload) = self.wait(remaining).await else {
+                continue;
+            };
+
+            match serde_json::from_str::<BlockIndexed>(&payload) {
+                Ok(BlockIndexed {
+                    token_pairs: Some(token_pairs),
+                    ..
+                }) => {
+                    if token_pairs.is_empty() {
+                        continue;
+                    }
+                    return Some(token_pairs.into_iter().map(TokenPairId::from).collect());
+                }
+                Ok(_) => return None,
+                Err(err) => {
+                    warn!("invalid block notification {payload}: {err:?}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::notify::BlockIndexed;
+
+    #[test]
+    fn test_block_indexed() {
+        assert_eq!(
+            serde_json::from_str::<BlockIndexed>(r#"{"slot": 42, "token_pairs": [1, 2]}"#).unwrap(),
+            BlockIndexed {
+                slot: 42,
+                token_pairs: Some(vec![1, 2]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_block_indexed_too_many_token_pairs() {
+        assert_eq!(
+            serde_json::from_str::<BlockIndexed>(r#"{"slot": 42, "token_pairs": null}"#).unwrap(),
+            BlockIndexed {
+                slot: 42,
+                token_pairs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_token_graduated() {
+        // as sent by the token_graduated trigger
+        assert_eq!(
+            serde_json::from_str::<BlockIndexed>(r#"{"slot" : 42, "token_pairs" : [7]}"#).unwrap(),
+            BlockIndexed {
+                slot: 42,
+                token_pairs: Some(vec![7]),
+            }
+        );
+    }
+}
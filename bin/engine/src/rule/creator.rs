@@ -0,0 +1,113 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::scope_ids;
+use base::model::Fact::{
+    CreatorGraduatedCount, CreatorGraduationRate, CreatorPeakMarketCapQuote, CreatorSellDuration,
+    CreatorTokenCount,
+};
+use base::model::{Facts, TokenPairId, Value};
+use bigdecimal::BigDecimal;
+use common::model::TimeUnit;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Track record of the creator of the base token of a token pair,
+/// refreshed periodically by the aggregator
+#[derive(Debug, Clone)]
+pub struct CreatorSummary {
+    pub token_pair: TokenPairId,
+    /// tokens launched by the creator, including this one
+    pub token_count: i64,
+    /// tokens of the creator which completed their bonding curve
+    pub graduated_count: i64,
+    /// median of the highest market cap each token of the creator reached
+    pub median_peak_market_cap: Option<BigDecimal>,
+    /// median time between launching a token and the creator's first sell of it,
+    /// tokens the creator never sold are not taken into account
+    pub median_sell_seconds: Option<i64>,
+}
+
+pub struct CreatorRepo {}
+
+impl CreatorRepo {
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<CreatorSummary>> {
+        Ok(query(
+            r#"
+select
+    tp.id as token_pair_id,
+    cs.token_count,
+    cs.graduated_count,
+    round(cs.median_peak_market_cap::numeric, 12) as median_peak_market_cap,
+    round(cs.median_sell_seconds)::int8 as median_sell_seconds
+from solana.creator_summary cs
+join solana.token t on t.creator_id = cs.creator_id
+join solana.token_pair tp on tp.base_id = t.id
+where ($1::int8[] is null or tp.id = any($1));
+"#,
+        )
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| CreatorSummary {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            token_count: r.get::<i64, _>("token_count"),
+            graduated_count: r.get::<i64, _>("graduated_count"),
+            median_peak_market_cap: r.get::<Option<BigDecimal>, _>("median_peak_market_cap"),
+            median_sell_seconds: r.get::<Option<i64>, _>("median_sell_seconds"),
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_creator_to_facts(facts: &mut Facts, creator: CreatorSummary) {
+    facts.set_value(CreatorTokenCount, Value::count(creator.token_count));
+    facts.set_value(CreatorGraduatedCount, Value::count(creator.graduated_count));
+
+    if let Some(rate) = graduation_rate(creator.token_count, creator.graduated_count) {
+        facts.set_value(CreatorGraduationRate, Value::percent(rate));
+    }
+
+    if let Some(market_cap) = creator.median_peak_market_cap {
+        facts.set_value(CreatorPeakMarketCapQuote, Value::quote(market_cap));
+    }
+
+    if let Some(seconds) = creator.median_sell_seconds {
+        facts.set_value(
+            CreatorSellDuration,
+            Value::duration(seconds, TimeUnit::Second),
+        );
+    }
+}
+
+fn graduation_rate(token_count: i64, graduated_count: i64) -> Option<BigDecimal> {
+    if token_count == 0 {
+        return None;
+    }
+    Some(
+        (BigDecimal::from(graduated_count) * BigDecimal::from(100) / BigDecimal::from(token_count))
+            .round(2),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::creator::graduation_rate;
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_graduation_rate() {
+        assert_eq!(graduation_rate(0, 0), None);
+        assert_eq!(graduation_rate(4, 1), Some(BigDecimal::from(25)));
+        assert_eq!(
+            graduation_rate(3, 1),
+            Some(BigDecimal::from_str("33.33").unwrap())
+        );
+    }
+}
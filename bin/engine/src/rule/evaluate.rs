@@ -0,0 +1,244 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::position::{add_position_to_facts, Position, PositionRepo};
+use crate::rule::trigger::{now, TriggerPolicy, TriggerRepo, Triggers};
+use crate::webhook::{webhook_payload, SecretCipher, WebhookEnqueueCmd, WebhookRepo};
+use base::model::{Action, Facts, Rule, RuleId, TokenPairId, UserId, Venue};
+use base::repo::{InvocationCreateCmd, InvocationRepo};
+use base::service::{NotificationRuleMatched, NotificationService};
+use common::repo::RepoResult;
+use rayon::prelude::*;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::task::spawn_blocking;
+use tokio::time::Instant;
+use tracing::{debug, error, info, warn};
+
+#[derive(Debug, Clone, Default)]
+pub struct RuleEvaluation {
+    /// token pairs which meet the condition of the rule
+    pub matched: Vec<TokenPairId>,
+    /// token pairs which meet the condition and are allowed to trigger by the rule's trigger policy
+    pub triggered: Vec<TokenPairId>,
+}
+
+/// Tests the condition of every rule against the facts of every token pair.
+/// The conditions are evaluated on the rayon thread pool, so that the runtime does not get blocked.
+/// If the owner of a rule holds a position in a token pair, the position facts are added before testing.
+pub async fn evaluate(
+    rules: Arc<Vec<Rule>>,
+    facts: Arc<HashMap<TokenPairId, Facts>>,
+    positions: Arc<HashMap<(UserId, TokenPairId), Position>>,
+    triggers: Arc<Triggers>,
+) -> Vec<RuleEvaluation> {
+    spawn_blocking(move || {
+        rules
+            .par_iter()
+            .map(|rule| {
+                let matched: Vec<TokenPairId> = facts
+                    .par_iter()
+                    .filter(|(token_pair, facts)| {
+                        match positions.get(&(rule.user, **token_pair)) {
+                            Some(position) => {
+                                let mut facts = (*facts).clone();
+                                add_position_to_facts(&mut facts, position);
+                                rule.sequence.condition.test(&facts)
+                            }
+                            None => rule.sequence.condition.test(facts),
+                        }
+                    })
+                    .map(|(token_pair, _)| *token_pair)
+                    .collect();
+
+                let triggered = matched
+                    .iter()
+                    .filter(|token_pair| triggers.should_trigger(rule.id, **token_pair))
+                    .copied()
+                    .collect();
+
+                RuleEvaluation { matched, triggered }
+            })
+            .collect()
+    })
+    .await
+    .unwrap()
+}
+
+/// Evaluates the rules and persists the resulting invocations.
+/// If scope is given, only the facts of those token pairs are evaluated.
+pub async fn run_evaluation(
+    pool: &PgPool,
+    notification: &NotificationService,
+    webhook: Option<&SecretCipher>,
+    venue: Venue,
+    rules: Vec<Rule>,
+    mut facts: HashMap<TokenPairId, Facts>,
+    scope: Option<&[TokenPairId]>,
+) {
+    if let Some(scope) = scope {
+        let scope: HashSet<&TokenPairId> = scope.iter().collect();
+        facts.retain(|token_pair, _| scope.contains(token_pair));
+    }
+
+    let rules = Arc::new(
+        rules
+            .into_iter()
+            .filter(|r| r.applicable())
+            .collect::<Vec<_>>(),
+    );
+
+    // trigger states and positions are loaded for the evaluated rules and token pairs only
+    let rule_ids: Vec<RuleId> = rules.iter().map(|r| r.id).collect();
+    let users: Vec<UserId> = rules
+        .iter()
+        .map(|r| r.user)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let evaluated: Vec<TokenPairId> = facts.keys().copied().collect();
+
+    let triggers = Arc::new(Triggers::load(pool, &rule_ids, &evaluated).await.unwrap());
+    let positions = Arc::new(PositionRepo::list(pool, &users, &evaluated).await.unwrap());
+
+    let start = Instant::now();
+    let facts = Arc::new(facts);
+    let evaluations = evaluate(
+        rules.clone(),
+        facts.clone(),
+        positions,
+        triggers.clone(),
+    )
+    .await;
+    debug!(
+        "evaluated {} {:?} rules - took {} ms",
+        rules.len(),
+        venue,
+        start.elapsed().as_millis()
+    );
+
+    let start = Instant::now();
+    for (rule, evaluation) in rules.iter().zip(evaluations) {
+        // every invocation gets its own transaction, so that a failing token pair does not
+        // roll back the invocations of the other token pairs
+        for token_pair in &evaluation.triggered {
+            if let Err(err) = persist(
+                pool,
+                notification,
+                webhook,
+                venue,
+                rule,
+                triggers.policy(rule.id),
+                *token_pair,
+                &facts,
+            )
+            .await
+            {
+                error!(
+                    "failed to persist invocation of rule {} for token pair {}: {:?}",
+                    rule.id.0, token_pair.0, err
+                );
+            }
+        }
+
+        if triggers.policy(rule.id) == TriggerPolicy::Edge {
+            let mut tx = pool.begin().await.unwrap();
+            TriggerRepo::unmatched(&mut tx, rule.id, &evaluation.matched, &evaluated)
+                .await
+                .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        if !evaluation.triggered.is_empty() {
+            info!(
+                "rule {} triggered for {} token pairs",
+                rule.id.0,
+                evaluation.triggered.len()
+            );
+        }
+    }
+    debug!(
+        "persisted {:?} invocations - took {} ms",
+        venue,
+        start.elapsed().as_millis()
+    );
+}
+
+async fn persist(
+    pool: &PgPool,
+    notification: &NotificationService,
+    webhook: Option<&SecretCipher>,
+    venue: Venue,
+    rule: &Rule,
+    policy: TriggerPolicy,
+    token_pair: TokenPairId,
+    facts: &HashMap<TokenPairId, Facts>,
+) -> RepoResult<()> {
+    let mut tx = pool.begin().await?;
+
+    // the snapshot the evaluation decided on might be outdated by now, e.g. another engine instance
+    // invoked the rule in the meantime
+    let state = TriggerRepo::lock(&mut tx, rule.id, token_pair).await?;
+    if !policy.should_trigger(Some(&state), now()) {
+        return Ok(());
+    }
+
+    InvocationRepo::new()
+        .create(
+            &mut tx,
+            InvocationCreateCmd {
+                user: rule.user,
+                rule: rule.id,
+                token_pair,
+                next: None,
+            },
+        )
+        .await?;
+
+    match &rule.sequence.action {
+        Action::AndThen { .. } => {}
+        Action::Buy => {}
+        Action::NotifyTelegram { buttons } => {
+            let _ = notification
+                .create_rule_matched_tx(
+                    &mut tx,
+                    NotificationRuleMatched::Telegram {
+                        user: rule.user,
+                        rule: rule.id,
+                        venue,
+                        token_pair,
+                        buttons: buttons.clone(),
+                    },
+                )
+                .await;
+        }
+        Action::NotifyWebhook { url, secret } => match webhook {
+            Some(webhook) => {
+                WebhookRepo::enqueue(
+                    &mut tx,
+                    WebhookEnqueueCmd {
+                        rule: rule.id,
+                        token_pair,
+                        url: url.clone(),
+                        secret_encrypted: webhook.encrypt(secret),
+                        payload: webhook_payload(
+                            rule.id,
+                            venue,
+                            token_pair,
+                            facts.get(&token_pair),
+                        ),
+                    },
+                )
+                .await?;
+            }
+            None => warn!("webhooks are not active, rule {} does not notify", rule.id),
+        },
+        Action::Sell => {}
+    }
+
+    TriggerRepo::triggered(&mut tx, rule.id, token_pair).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
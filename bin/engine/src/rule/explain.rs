@@ -0,0 +1,190 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::command::ExplainCommand;
+use crate::config::RuleConfig;
+use base::model::{Condition, Fact, Facts, TokenPairId, Value, Venue};
+use base::repo::{TokenPairRepo, TokenRepo};
+use base::service::RuleService;
+use common::model::Timeframe;
+use common::repo::pool::setup_pool;
+use serde::Serialize;
+use serde_json::Map;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Outcome {
+    Pass,
+    Fail,
+    /// the condition failed because the fact it refers to is not known for this token pair
+    MissingFact,
+}
+
+/// Evaluation of a single node of a rule's condition tree
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fact: Option<Fact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<Timeframe>,
+    /// value of the fact the condition was tested against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// everything else the condition compares with, e.g. operator and threshold
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub threshold: Map<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Explanation>,
+}
+
+impl Explanation {
+    pub fn passed(&self) -> bool {
+        self.outcome == Outcome::Pass
+    }
+
+    /// All facts a condition of this tree needed but which were not available
+    pub fn missing_facts(&self) -> Vec<(Fact, Option<Timeframe>)> {
+        let mut result = Vec::new();
+        self.collect_missing(&mut result);
+        result
+    }
+
+    fn collect_missing(&self, result: &mut Vec<(Fact, Option<Timeframe>)>) {
+        if self.outcome == Outcome::MissingFact {
+            if let Some(fact) = &self.fact {
+                result.push((fact.clone(), self.timeframe));
+            }
+        }
+        for child in &self.children {
+            child.collect_missing(result);
+        }
+    }
+}
+
+pub async fn run_explain(cfg: RuleConfig, cmd: ExplainCommand) {
+    let pool = setup_pool(cfg).await;
+
+    let rule = RuleService::new(pool.clone())
+        .list_active()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.id.0 == cmd.rule)
+        .expect("rule not found or not active");
+
+    let token_pair = TokenPairId::from(cmd.token_pair);
+    let pair_repo = TokenPairRepo::new(TokenRepo::new_read_only());
+    let mut facts = match cmd.venue {
+        Venue::PumpUp => {
+            crate::rule::pumpup::FactService::new(
+                pool.clone(),
+                pair_repo,
+                solana::pumpup::repo::SummaryRepo::new(),
+            )
+            .pumpup_facts_for(&[token_pair])
+            .await
+        }
+        _ => {
+            crate::rule::pumpfun::FactService::new(
+                pool.clone(),
+                pair_repo,
+                solana::pumpfun::repo::SummaryRepo::new(),
+            )
+            .pumpfun_facts_for(&[token_pair])
+            .await
+        }
+    };
+
+    let facts = facts.remove(&token_pair).unwrap_or_default();
+
+    let explanation = explain(&rule.sequence.condition, &facts);
+    println!("{}", serde_json::to_string_pretty(&explanation).unwrap());
+}
+
+/// Evaluates a condition tree against the facts of a token pair and explains for each
+/// sub-condition why it passed or failed.
+pub fn explain(condition: &Condition, facts: &Facts) -> Explanation {
+    let node = serde_json::to_value(condition).expect("condition is serializable");
+    explain_node(&node, facts)
+}
+
+fn explain_node(node: &serde_json::Value, facts: &Facts) -> Explanation {
+    let object = node.as_object().cloned().unwrap_or_default();
+
+    let kind = object
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let fact: Option<Fact> = object
+        .get("fact")
+        .and_then(|f| serde_json::from_value(f.clone()).ok());
+
+    let timeframe: Option<Timeframe> = object
+        .get("timeframe")
+        .and_then(|t| serde_json::from_value(t.clone()).ok());
+
+    let mut children = Vec::new();
+    let mut threshold = Map::new();
+
+    // a managed condition is predefined by its name, explain the condition tree it stands for
+    if let Ok(Condition::Managed { managed }) = serde_json::from_value::<Condition>(node.clone()) {
+        children.push(explain(&managed.condition(), facts));
+    }
+
+    for (key, value) in object {
+        if key == "type" || key == "fact" || key == "timeframe" {
+            continue;
+        }
+
+        if is_condition(&value) {
+            children.push(explain_node(&value, facts));
+        } else if let Some(conditions) = value
+            .as_array()
+            .filter(|a| !a.is_empty() && a.iter().all(is_condition))
+        {
+            children.extend(conditions.iter().map(|c| explain_node(c, facts)));
+        } else {
+            threshold.insert(key, value);
+        }
+    }
+
+    let value = fact.as_ref().and_then(|fact| match &timeframe {
+        Some(timeframe) => facts.get_timeframe_value(fact, timeframe).cloned(),
+        None => facts.get(fact).cloned(),
+    });
+
+    let passed = serde_json::from_value::<Condition>(node.clone())
+        .map(|c| c.test(facts))
+        .unwrap_or(false);
+
+    let outcome = if passed {
+        Outcome::Pass
+    } else if fact.is_some() && value.is_none() {
+        Outcome::MissingFact
+    } else {
+        Outcome::Fail
+    };
+
+    Explanation {
+        kind,
+        outcome,
+        fact,
+        timeframe,
+        value,
+        threshold,
+        children,
+    }
+}
+
+fn is_condition(value: &serde_json::Value) -> bool {
+    value
+        .as_object()
+        .filter(|o| o.contains_key("type"))
+        .map(|o| serde_json::from_value::<Condition>(serde_json::Value::Object(o.clone())).is_ok())
+        .unwrap_or(false)
+}
@@ -0,0 +1,92 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::table;
+use crate::rule::scope_ids;
+use base::model::Fact::{
+    FailedBuyAggregate, FailedBuyWalletAggregate, FailedTransactionAggregate, FailedWalletAggregate,
+};
+use base::model::{Facts, TokenPairId, Value};
+use common::model::Timeframe;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Failed transactions against a token pair within a timeframe, aggregated by the aggregator
+#[derive(Debug, Clone)]
+pub struct Failed {
+    pub token_pair: TokenPairId,
+    pub failed: i64,
+    /// failed buys on pumpfun and pumpswap, the other venues do not decode failed transactions
+    pub failed_buy: i64,
+    /// unique wallets with a failed transaction
+    pub wallet: i64,
+    /// unique wallets with a failed buy on pumpfun and pumpswap
+    pub wallet_buy: i64,
+}
+
+pub struct FailedRepo {}
+
+impl FailedRepo {
+    /// Lists the failed transactions of the timeframe, mapped onto every token pair with the same
+    /// base and quote token
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        timeframe: Timeframe,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<Failed>> {
+        let table = table(timeframe);
+
+        Ok(query(&format!(
+            r#"
+select
+    tp.id as token_pair_id,
+    f.failed,
+    f.failed_buy,
+    f.wallet,
+    f.wallet_buy
+from solana.failed_transaction_{table} f
+join solana.token_pair other on other.id = f.token_pair_id
+join solana.token_pair tp on tp.base_id = other.base_id and tp.quote_id = other.quote_id
+where ($1::int8[] is null or tp.id = any($1));
+"#
+        ))
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| Failed {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            failed: r.get::<i64, _>("failed"),
+            failed_buy: r.get::<i64, _>("failed_buy"),
+            wallet: r.get::<i64, _>("wallet"),
+            wallet_buy: r.get::<i64, _>("wallet_buy"),
+        })
+        .collect())
+    }
+}
+
+/// The buy facts cover pumpfun and pumpswap only, failed transactions of the other venues count
+/// towards the totals
+pub(crate) fn add_failed_to_facts(facts: &mut Facts, failed: Failed, timeframe: Timeframe) {
+    facts.set_timeframe_value(
+        FailedTransactionAggregate,
+        Value::count(failed.failed),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        FailedBuyAggregate,
+        Value::count(failed.failed_buy),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        FailedWalletAggregate,
+        Value::count(failed.wallet),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        FailedBuyWalletAggregate,
+        Value::count(failed.wallet_buy),
+        timeframe,
+    );
+}
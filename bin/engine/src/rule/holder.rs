@@ -0,0 +1,67 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::scope_ids;
+use base::model::Fact::{CreatorHoldingPercent, HolderCount, TopHoldersPercent};
+use base::model::{Facts, TokenPairId, Value};
+use bigdecimal::BigDecimal;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Holder snapshot of the base token of a token pair, maintained by the indexer
+#[derive(Debug, Clone)]
+pub struct HolderSummary {
+    pub token_pair: TokenPairId,
+    pub holder_count: i64,
+    /// share of the supply held by the 10 largest holders
+    pub top_holders_percent: Option<BigDecimal>,
+    /// share of the supply held by the creator of the token
+    pub creator_holding_percent: Option<BigDecimal>,
+}
+
+pub struct HolderRepo {}
+
+impl HolderRepo {
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<HolderSummary>> {
+        Ok(query(
+            r#"
+select
+    tp.id as token_pair_id,
+    s.holder_count,
+    case when t.supply > 0 then round(s.top_holders_balance / t.supply * 100, 2) end as top_holders_percent,
+    case when t.supply > 0 and t.creator_id is not null then round(s.creator_balance / t.supply * 100, 2) end as creator_holding_percent
+from solana.token_holder_summary s
+join solana.token t on t.id = s.token_id
+join solana.token_pair tp on tp.base_id = t.id
+where ($1::int8[] is null or tp.id = any($1));
+"#,
+        )
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| HolderSummary {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            holder_count: r.get::<i64, _>("holder_count"),
+            top_holders_percent: r.get::<Option<BigDecimal>, _>("top_holders_percent"),
+            creator_holding_percent: r.get::<Option<BigDecimal>, _>("creator_holding_percent"),
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_holder_to_facts(facts: &mut Facts, holder: HolderSummary) {
+    facts.set_value(HolderCount, Value::count(holder.holder_count));
+
+    if let Some(percent) = holder.top_holders_percent {
+        facts.set_value(TopHoldersPercent, Value::percent(percent));
+    }
+
+    if let Some(percent) = holder.creator_holding_percent {
+        facts.set_value(CreatorHoldingPercent, Value::percent(percent));
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::indicator::{
+    atr, bollinger_position, closes, cross, ema, fill_gaps, last, rsi, sma, vwap_deviation, Cross,
+};
+use crate::candle::{list_candles, Candle, CandleQuery, TIMEFRAMES};
+use base::model::Fact::{
+    AtrPercentAggregate, AtrQuoteAggregate, BollingerPositionPercentAggregate,
+    EmaCrossDownAggregate, EmaCrossUpAggregate, RsiAggregate, SmaCrossDownAggregate,
+    SmaCrossUpAggregate, VwapDeviationPercentAggregate,
+};
+use base::model::{Facts, TokenPairId, Value, Venue};
+use bigdecimal::{BigDecimal, FromPrimitive};
+use common::model::{Timeframe, Timestamp};
+use common::repo::{RepoResult, Tx};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const RSI_PERIOD: usize = 14;
+const FAST_PERIOD: usize = 9;
+const SLOW_PERIOD: usize = 21;
+const VWAP_PERIOD: usize = 20;
+const BOLLINGER_PERIOD: usize = 20;
+const BOLLINGER_WIDTH: f64 = 2.0;
+const ATR_PERIOD: usize = 14;
+
+/// Wilder's and exponential smoothing settle after about three times their period
+const WARM_UP: usize = 3;
+
+const RSI_LOOKBACK: usize = RSI_PERIOD * WARM_UP + 1;
+const EMA_LOOKBACK: usize = SLOW_PERIOD * WARM_UP + 1;
+/// one more candle to tell whether the averages crossed with the last one
+const SMA_LOOKBACK: usize = SLOW_PERIOD + 1;
+const VWAP_LOOKBACK: usize = VWAP_PERIOD;
+const BOLLINGER_LOOKBACK: usize = BOLLINGER_PERIOD;
+const ATR_LOOKBACK: usize = ATR_PERIOD * WARM_UP + 1;
+
+/// Candles loaded per token pair and timeframe, the longest lookback of all indicators
+const LOOKBACK: usize = max(&[
+    RSI_LOOKBACK,
+    EMA_LOOKBACK,
+    SMA_LOOKBACK,
+    VWAP_LOOKBACK,
+    BOLLINGER_LOOKBACK,
+    ATR_LOOKBACK,
+]);
+
+const fn max(values: &[usize]) -> usize {
+    let mut result = 0;
+    let mut idx = 0;
+    while idx < values.len() {
+        if values[idx] > result {
+            result = values[idx];
+        }
+        idx += 1;
+    }
+    result
+}
+
+/// Computes technical indicators from the candles of each timeframe,
+/// for all token pairs which already got facts
+pub(crate) async fn add_indicators_to_facts<'a>(
+    tx: &mut Tx<'a>,
+    venue: Venue,
+    result: &mut HashMap<TokenPairId, Facts>,
+    scope: Option<&[TokenPairId]>,
+) -> RepoResult<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    // a scoped evaluation loads the candles of its token pairs only
+    let token_pairs: Vec<Option<TokenPairId>> = match scope {
+        Some(scope) => scope.iter().map(|t| Some(*t)).collect(),
+        None => vec![None],
+    };
+
+    for (timeframe, _, seconds) in TIMEFRAMES {
+        // start of the candle currently in progress
+        let current = now - now % seconds;
+
+        for token_pair in &token_pairs {
+            let candles = list_candles(
+                &mut *tx,
+                CandleQuery {
+                    venue,
+                    token_pair: *token_pair,
+                    timeframe,
+                    from: Timestamp::from_epoch_second(current - (LOOKBACK as i64 - 1) * seconds)
+                        .unwrap(),
+                    to: Timestamp::from_epoch_second(current + seconds).unwrap(),
+                },
+            )
+            .await?;
+
+            for (token_pair, candles) in &candles.into_iter().chunk_by(|c| c.token_pair) {
+                if let Some(facts) = result.get_mut(&token_pair) {
+                    let candles = fill_gaps(&candles.collect::<Vec<_>>(), seconds, current);
+                    add_indicators(facts, &candles, timeframe);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn add_indicators(facts: &mut Facts, candles: &[Candle], timeframe: Timeframe) {
+    let closes = closes(candles);
+
+    if let Some(rsi) = rsi(last(&closes, RSI_LOOKBACK), RSI_PERIOD).and_then(decimal) {
+        facts.set_timeframe_value(RsiAggregate, Value::percent(rsi), timeframe);
+    }
+
+    for (average, lookback, up, down) in [
+        (
+            ema as fn(&[f64], usize) -> Option<f64>,
+            EMA_LOOKBACK,
+            EmaCrossUpAggregate,
+            EmaCrossDownAggregate,
+        ),
+        (
+            sma,
+            SMA_LOOKBACK,
+            SmaCrossUpAggregate,
+            SmaCrossDownAggregate,
+        ),
+    ] {
+        let closes = last(&closes, lookback);
+        let cross = cross(closes, FAST_PERIOD, SLOW_PERIOD, average);
+        if average(closes, SLOW_PERIOD).is_some() {
+            facts.set_timeframe_value(up, Value::boolean(cross == Some(Cross::Up)), timeframe);
+            facts.set_timeframe_value(down, Value::boolean(cross == Some(Cross::Down)), timeframe);
+        }
+    }
+
+    if let Some(deviation) =
+        vwap_deviation(last(candles, VWAP_LOOKBACK), VWAP_PERIOD).and_then(decimal)
+    {
+        facts.set_timeframe_value(
+            VwapDeviationPercentAggregate,
+            Value::percent(deviation),
+            timeframe,
+        );
+    }
+
+    if let Some(position) = bollinger_position(
+        last(&closes, BOLLINGER_LOOKBACK),
+        BOLLINGER_PERIOD,
+        BOLLINGER_WIDTH,
+    )
+    .and_then(decimal)
+    {
+        facts.set_timeframe_value(
+            BollingerPositionPercentAggregate,
+            Value::percent(position),
+            timeframe,
+        );
+    }
+
+    if let Some(atr) = atr(last(candles, ATR_LOOKBACK), ATR_PERIOD) {
+        if let Some(quote) = BigDecimal::from_f64(atr) {
+            facts.set_timeframe_value(AtrQuoteAggregate, Value::quote(quote), timeframe);
+        }
+
+        let close = closes.last().copied().unwrap_or_default();
+        if close > 0.0 {
+            if let Some(percent) = decimal(atr / close * 100.0) {
+                facts.set_timeframe_value(AtrPercentAggregate, Value::percent(percent), timeframe);
+            }
+        }
+    }
+}
+
+fn decimal(value: f64) -> Option<BigDecimal> {
+    BigDecimal::from_f64(value).map(|v| v.round(2))
+}
@@ -0,0 +1,113 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::TIMEFRAMES;
+use crate::rule::scope_ids;
+use base::model::Fact::{Graduated, TimeSinceGraduationDuration, VolumeAllVenuesAggregate};
+use base::model::{Facts, TokenPairId, Value};
+use bigdecimal::BigDecimal;
+use common::model::{TimeUnit, Timeframe};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Graduation of the base token of a token pair from the pumpfun bonding curve,
+/// recorded by the indexer when the curve completes
+#[derive(Debug, Clone)]
+pub struct Lifecycle {
+    pub token_pair: TokenPairId,
+    /// seconds since the curve got completed
+    pub graduation_age: i64,
+}
+
+pub struct LifecycleRepo {}
+
+impl LifecycleRepo {
+    /// Lists every token pair whose base token graduated, no matter the venue
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<Lifecycle>> {
+        Ok(query(
+            r#"
+select
+    tp.id as token_pair_id,
+    extract(epoch from now() - tl.graduated_at)::int8 as graduation_age
+from solana.token_lifecycle tl
+join solana.token_pair tp on tp.base_id = tl.token_id
+where ($1::int8[] is null or tp.id = any($1));
+"#,
+        )
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| Lifecycle {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            graduation_age: r.get::<i64, _>("graduation_age"),
+        })
+        .collect())
+    }
+
+    /// Volume traded within the timeframe on pumpfun, pumpswap and jupiter,
+    /// summed up over all token pairs with the same base and quote token
+    pub async fn list_volume(
+        mut executor: impl AsSqlExecutor,
+        timeframe: Timeframe,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<(TokenPairId, BigDecimal)>> {
+        let Some((_, _, seconds)) = TIMEFRAMES.iter().find(|(tf, _, _)| *tf == timeframe) else {
+            return Ok(vec![]);
+        };
+
+        // hourly candles are precise enough for the larger timeframes
+        let table = if *seconds <= 3_600 { "1m" } else { "1h" };
+
+        Ok(query(&format!(
+            r#"
+with volume as (
+    select token_pair_id, volume from pumpfun.candle_{table} where timestamp >= now() - make_interval(secs => $1)
+    union all
+    select token_pair_id, volume from pumpswap.candle_{table} where timestamp >= now() - make_interval(secs => $1)
+    union all
+    select token_pair_id, volume from jupiter.candle_{table} where timestamp >= now() - make_interval(secs => $1)
+)
+select tp.id as token_pair_id, sum(v.volume) as volume
+from volume v
+join solana.token_pair other on other.id = v.token_pair_id
+join solana.token_pair tp on tp.base_id = other.base_id and tp.quote_id = other.quote_id
+where ($2::int8[] is null or tp.id = any($2))
+group by tp.id;
+"#
+        ))
+        .bind(*seconds as f64)
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| {
+            (
+                r.get::<TokenPairId, _>("token_pair_id"),
+                r.get::<BigDecimal, _>("volume"),
+            )
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_lifecycle_to_facts(facts: &mut Facts, lifecycle: Option<&Lifecycle>) {
+    match lifecycle {
+        Some(lifecycle) => {
+            facts.set_value(Graduated, Value::boolean(true));
+            facts.set_value(
+                TimeSinceGraduationDuration,
+                Value::duration(lifecycle.graduation_age, TimeUnit::Second),
+            );
+        }
+        None => facts.set_value(Graduated, Value::boolean(false)),
+    }
+}
+
+pub(crate) fn add_volume_to_facts(facts: &mut Facts, volume: BigDecimal, timeframe: Timeframe) {
+    facts.set_timeframe_value(VolumeAllVenuesAggregate, Value::quote(volume), timeframe);
+}
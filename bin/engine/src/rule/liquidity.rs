@@ -0,0 +1,95 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::table;
+use crate::rule::scope_ids;
+use base::model::Fact::{
+    LiquidityAddedQuoteAggregate, LiquidityChangeQuoteAggregate, LiquidityRemovedPercentAggregate,
+    LiquidityRemovedQuoteAggregate,
+};
+use base::model::{Facts, TokenPairId, Value};
+use bigdecimal::BigDecimal;
+use common::model::Timeframe;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Liquidity added to and removed from the pumpswap pools of a token pair within a timeframe,
+/// aggregated by the aggregator
+#[derive(Debug, Clone)]
+pub struct Liquidity {
+    pub token_pair: TokenPairId,
+    pub quote_added: BigDecimal,
+    pub quote_removed: BigDecimal,
+    pub quote_change: BigDecimal,
+    /// lp tokens burned, relative to the largest lp supply within the timeframe
+    pub lp_burned_percent: BigDecimal,
+}
+
+pub struct LiquidityRepo {}
+
+impl LiquidityRepo {
+    /// Lists the liquidity changes of the timeframe, mapped onto every token pair with the same
+    /// base and quote token
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        timeframe: Timeframe,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<Liquidity>> {
+        let table = table(timeframe);
+
+        Ok(query(&format!(
+            r#"
+select
+    tp.id as token_pair_id,
+    l.quote_added,
+    l.quote_removed,
+    l.quote_change,
+    l.lp_burned_percent
+from pumpswap.liquidity_{table} l
+join solana.token_pair other on other.id = l.token_pair_id
+join solana.token_pair tp on tp.base_id = other.base_id and tp.quote_id = other.quote_id
+where ($1::int8[] is null or tp.id = any($1));
+"#
+        ))
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| Liquidity {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            quote_added: r.get::<BigDecimal, _>("quote_added"),
+            quote_removed: r.get::<BigDecimal, _>("quote_removed"),
+            quote_change: r.get::<BigDecimal, _>("quote_change"),
+            lp_burned_percent: r.get::<BigDecimal, _>("lp_burned_percent"),
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_liquidity_to_facts(
+    facts: &mut Facts,
+    liquidity: Liquidity,
+    timeframe: Timeframe,
+) {
+    facts.set_timeframe_value(
+        LiquidityAddedQuoteAggregate,
+        Value::quote(liquidity.quote_added),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        LiquidityRemovedQuoteAggregate,
+        Value::quote(liquidity.quote_removed),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        LiquidityChangeQuoteAggregate,
+        Value::quote(liquidity.quote_change),
+        timeframe,
+    );
+    facts.set_timeframe_value(
+        LiquidityRemovedPercentAggregate,
+        Value::percent(liquidity.lp_burned_percent),
+        timeframe,
+    );
+}
@@ -0,0 +1,61 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::scope_ids;
+use base::model::Fact::{HasTelegram, HasTwitter, HasWebsite};
+use base::model::{Facts, TokenPairId, Value};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+/// Links of the base token of a token pair, as found in its off chain metadata
+#[derive(Debug, Clone)]
+pub struct MetadataSummary {
+    pub token_pair: TokenPairId,
+    pub website: bool,
+    pub twitter: bool,
+    pub telegram: bool,
+}
+
+pub struct MetadataRepo {}
+
+impl MetadataRepo {
+    /// Lists token pairs whose base token metadata got fetched by the indexer,
+    /// for all others it is unknown whether they have links or not
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<MetadataSummary>> {
+        Ok(query(
+            r#"
+select
+    tp.id as token_pair_id,
+    t.website is not null as website,
+    t.twitter is not null as twitter,
+    t.telegram is not null as telegram
+from solana.token_metadata tm
+join solana.token t on t.id = tm.token_id
+join solana.token_pair tp on tp.base_id = t.id
+where tm.status = 'FETCHED'
+  and ($1::int8[] is null or tp.id = any($1));
+"#,
+        )
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| MetadataSummary {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            website: r.get::<bool, _>("website"),
+            twitter: r.get::<bool, _>("twitter"),
+            telegram: r.get::<bool, _>("telegram"),
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_metadata_to_facts(facts: &mut Facts, metadata: MetadataSummary) {
+    facts.set_value(HasWebsite, Value::boolean(metadata.website));
+    facts.set_value(HasTwitter, Value::boolean(metadata.twitter));
+    facts.set_value(HasTelegram, Value::boolean(metadata.telegram));
+}
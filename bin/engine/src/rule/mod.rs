@@ -1,29 +1,57 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
-use crate::config::{RuleConfig, RulePumpfunConfig, RulePumpupConfig};
+use crate::config::{RuleConfig, RulePumpfunConfig, RulePumpupConfig, WebhookConfig};
+use crate::webhook::SecretCipher;
+use base::model::TokenPairId;
 use common::ResolveOr;
 use futures_util::future::join_all;
 use log::error;
 use tokio::task::JoinHandle;
 
+pub mod creator;
+pub mod evaluate;
+pub mod explain;
+pub mod failed;
+pub mod holder;
+pub mod indicator;
+pub mod lifecycle;
+pub mod liquidity;
+pub mod metadata;
+pub mod position;
 pub mod pumpfun;
 pub mod pumpup;
+pub mod trigger;
+
+/// Ids of the token pairs a query gets restricted to, none to query all token pairs
+pub(crate) fn scope_ids(scope: Option<&[TokenPairId]>) -> Option<Vec<i64>> {
+    scope.map(|s| s.iter().map(|t| t.0).collect())
+}
 
 pub fn start_automate(
     cfg: RuleConfig,
+    webhook_config: WebhookConfig,
     pf_config: RulePumpfunConfig,
     pu_config: RulePumpupConfig,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
+        // webhook actions get queued only if the webhook worker delivers them
+        let webhook = match SecretCipher::load(&webhook_config) {
+            Ok(webhook) => webhook,
+            Err(err) => {
+                error!("invalid config: {err}");
+                return;
+            }
+        };
+
         let mut handles = Vec::new();
 
         if pf_config.active.resolve_or(false) {
-            handles.push(pumpfun::start_rules(cfg.clone()));
+            handles.push(pumpfun::start_rules(cfg.clone(), webhook.clone()));
         }
 
         if pu_config.active.resolve_or(false) {
-            handles.push(pumpup::start_rules(cfg.clone()));
+            handles.push(pumpup::start_rules(cfg.clone(), webhook.clone()));
         }
 
         for result in join_all(handles).await {
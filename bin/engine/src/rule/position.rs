@@ -0,0 +1,195 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::Fact::{
+    PositionAgeDuration, PositionAmount, PositionEntryPriceQuote, PositionPnlPercent,
+};
+use base::model::{Facts, TokenPairId, UserId, Value};
+use bigdecimal::BigDecimal;
+use common::model::TimeUnit;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+use std::collections::HashMap;
+
+/// Position of a user in a token pair, built from the swaps executed on behalf of the user
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub user: UserId,
+    pub token_pair: TokenPairId,
+    /// quote paid per base token, averaged over all buys
+    pub entry_price: Option<BigDecimal>,
+    /// change of the current price relative to the entry price,
+    /// the price being the last pumpswap swap once the token graduated
+    pub pnl_percent: Option<BigDecimal>,
+    /// base tokens held across all wallets of the user
+    pub amount: BigDecimal,
+    /// seconds since the first buy
+    pub age: i64,
+}
+
+pub struct PositionRepo {}
+
+impl PositionRepo {
+    /// Lists the positions of the given users in the given token pairs
+    pub async fn list(
+        mut executor: impl AsSqlExecutor,
+        users: &[UserId],
+        token_pairs: &[TokenPairId],
+    ) -> RepoResult<HashMap<(UserId, TokenPairId), Position>> {
+        Ok(query(
+            r#"
+with buy as (
+    select rs.user_id, s.token_pair_id, s.amount_base, s.amount_quote, s.timestamp
+    from solana.result_swap rs
+    join pumpfun.swap s on s.signature = rs.swap_hash and s.is_buy = true
+    union all
+    select rs.user_id, s.token_pair_id, s.amount_base, s.amount_quote, s.timestamp
+    from solana.result_swap rs
+    join pumpswap.swap s on s.signature = rs.swap_hash and s.is_buy = true
+    union all
+    select rs.user_id, s.token_pair_id, s.amount_base, s.amount_quote, s.timestamp
+    from solana.result_swap rs
+    join pumpup.swap s on s.signature = rs.swap_hash and s.is_buy = true
+),
+entry as (
+    select
+        user_id,
+        token_pair_id,
+        sum(amount_quote) / nullif(sum(amount_base), 0) as entry_price,
+        min(timestamp) as first_buy
+    from buy
+    where user_id = any($1)
+      and token_pair_id = any($2)
+    group by user_id, token_pair_id
+),
+balance as (
+    select distinct on (wbt.wallet_id, wbt.token_id) w.user_id, wbt.token_id, wbt.balance
+    from solana.wallet_balance_token wbt
+    join solana.wallet w on w.id = wbt.wallet_id
+    order by wbt.wallet_id, wbt.token_id, wbt.slot desc
+),
+-- a graduated token trades on pumpswap, whose last swap supersedes the final curve price
+price as (
+    select
+        e.token_pair_id,
+        coalesce(ps.price, pf.price, pu.price) as price
+    from entry e
+    left join pumpfun.current pf on pf.id = e.token_pair_id
+    left join pumpup.current pu on pu.id = e.token_pair_id
+    left join lateral (
+        select s.price
+        from pumpswap.swap s
+        where s.token_pair_id = e.token_pair_id
+        order by s.slot desc
+        limit 1
+    ) ps on true
+)
+select
+    e.user_id,
+    e.token_pair_id,
+    round(e.entry_price, 12) as entry_price,
+    round((p.price - e.entry_price) / nullif(e.entry_price, 0) * 100, 2) as pnl_percent,
+    coalesce((select sum(b.balance) from balance b where b.user_id = e.user_id and b.token_id = tp.base_id), 0) as amount,
+    extract(epoch from now() - e.first_buy)::int8 as age
+from entry e
+join solana.token_pair tp on tp.id = e.token_pair_id
+left join price p on p.token_pair_id = e.token_pair_id;
+"#,
+        )
+        .bind(users.iter().map(|u| u.0).collect::<Vec<i64>>())
+        .bind(token_pairs.iter().map(|t| t.0).collect::<Vec<i64>>())
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| {
+            let position = Position {
+                user: r.get::<UserId, _>("user_id"),
+                token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+                entry_price: r.get::<Option<BigDecimal>, _>("entry_price"),
+                pnl_percent: r.get::<Option<BigDecimal>, _>("pnl_percent"),
+                amount: r.get::<BigDecimal, _>("amount"),
+                age: r.get::<i64, _>("age"),
+            };
+            ((position.user, position.token_pair), position)
+        })
+        .collect())
+    }
+}
+
+pub(crate) fn add_position_to_facts(facts: &mut Facts, position: &Position) {
+    facts.set_value(PositionAmount, Value::amount(position.amount.clone()));
+    facts.set_value(
+        PositionAgeDuration,
+        Value::duration(position.age, TimeUnit::Second),
+    );
+
+    if let Some(price) = &position.entry_price {
+        facts.set_value(PositionEntryPriceQuote, Value::quote(price.clone()));
+    }
+
+    if let Some(percent) = &position.pnl_percent {
+        facts.set_value(PositionPnlPercent, Value::percent(percent.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::position::{add_position_to_facts, Position};
+    use base::model::Fact::{
+        PositionAgeDuration, PositionAmount, PositionEntryPriceQuote, PositionPnlPercent,
+    };
+    use base::model::{Facts, TokenPairId, UserId, Value};
+    use bigdecimal::BigDecimal;
+    use common::model::TimeUnit;
+
+    fn position(entry_price: Option<BigDecimal>, pnl_percent: Option<BigDecimal>) -> Position {
+        Position {
+            user: UserId::from(1),
+            token_pair: TokenPairId::from(2),
+            entry_price,
+            pnl_percent,
+            amount: BigDecimal::from(1_000),
+            age: 60,
+        }
+    }
+
+    #[test]
+    fn test_add_position() {
+        let mut facts = Facts::new();
+        add_position_to_facts(
+            &mut facts,
+            &position(Some(BigDecimal::from(2)), Some(BigDecimal::from(50))),
+        );
+
+        assert_eq!(
+            facts.get(&PositionAmount),
+            Some(&Value::amount(BigDecimal::from(1_000)))
+        );
+        assert_eq!(
+            facts.get(&PositionAgeDuration),
+            Some(&Value::duration(60, TimeUnit::Second))
+        );
+        assert_eq!(
+            facts.get(&PositionEntryPriceQuote),
+            Some(&Value::quote(BigDecimal::from(2)))
+        );
+        assert_eq!(
+            facts.get(&PositionPnlPercent),
+            Some(&Value::percent(BigDecimal::from(50)))
+        );
+    }
+
+    #[test]
+    fn test_add_position_without_price() {
+        let mut facts = Facts::new();
+        add_position_to_facts(&mut facts, &position(None, None));
+
+        assert_eq!(
+            facts.get(&PositionAmount),
+            Some(&Value::amount(BigDecimal::from(1_000)))
+        );
+        assert_eq!(facts.get(&PositionEntryPriceQuote), None);
+        assert_eq!(facts.get(&PositionPnlPercent), None);
+    }
+}
@@ -3,16 +3,26 @@
 
 mod summary;
 
+use crate::rule::creator::{add_creator_to_facts, CreatorRepo};
+use crate::rule::failed::{add_failed_to_facts, FailedRepo};
+use crate::rule::holder::{add_holder_to_facts, HolderRepo};
+use crate::rule::indicator::add_indicators_to_facts;
+use crate::rule::lifecycle::{
+    add_lifecycle_to_facts, add_volume_to_facts, Lifecycle, LifecycleRepo,
+};
+use crate::rule::liquidity::{add_liquidity_to_facts, LiquidityRepo};
+use crate::rule::metadata::{add_metadata_to_facts, MetadataRepo};
 use crate::rule::pumpfun::fact::summary::add_summary_to_facts;
 use base::model::Fact::{CurveProgressAgeDuration, MarketCapQuote, MarketCapUsd, VenuePumpfun};
-use base::model::{Fact, Facts, TokenPairId, Value};
+use base::model::{Fact, Facts, TokenPairId, Value, Venue};
 use base::repo::TokenPairRepo;
 use common::model::{Limit, TimeUnit, Timeframe};
 use solana::pumpfun::repo::{CurrentQuery, CurrentRepo, SummaryQuery, SummaryRepo};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use bigdecimal::{BigDecimal, FromPrimitive};
 use tokio::time::Instant;
+use tracing::debug;
 use Fact::CurveProgressPercent;
 
 #[derive(Clone)]
@@ -32,6 +42,25 @@ impl FactService {
     }
 
     pub async fn pumpfun_facts(&self) -> HashMap<TokenPairId, Facts> {
+        self.facts(None).await
+    }
+
+    /// Facts of the given token pairs only, e.g. the ones touched by a freshly indexed block
+    pub async fn pumpfun_facts_for(
+        &self,
+        token_pairs: &[TokenPairId],
+    ) -> HashMap<TokenPairId, Facts> {
+        self.facts(Some(token_pairs)).await
+    }
+
+    async fn facts(&self, scope: Option<&[TokenPairId]>) -> HashMap<TokenPairId, Facts> {
+        let scope_set: Option<HashSet<TokenPairId>> = scope.map(|s| s.iter().copied().collect());
+        let in_scope = |token_pair: &TokenPairId| {
+            scope_set
+                .as_ref()
+                .map_or(true, |scope| scope.contains(token_pair))
+        };
+
         let mut tx = self.pool.begin().await.unwrap();
 
         let start = Instant::now();
@@ -41,6 +70,7 @@ impl FactService {
             .await
             .unwrap()
             .into_iter()
+            .filter(|tp| in_scope(&tp.id))
             .map(|tp| {
                 let mut facts = Facts::new();
 
@@ -62,10 +92,7 @@ impl FactService {
             })
             .collect();
 
-        println!(
-            "token pairs took: {}",
-            Instant::now().duration_since(start).as_millis()
-        );
+        debug!("token pairs - took {} ms", start.elapsed().as_millis());
 
         for current in CurrentRepo::list(
             &mut *tx,
@@ -99,6 +126,40 @@ impl FactService {
             }
         }
 
+        let start = Instant::now();
+        add_indicators_to_facts(&mut tx, Venue::PumpFun, &mut result, scope)
+            .await
+            .unwrap();
+        debug!("indicators - took {} ms", start.elapsed().as_millis());
+
+        for creator in CreatorRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&creator.token_pair) {
+                add_creator_to_facts(facts, creator);
+            }
+        }
+
+        for holder in HolderRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&holder.token_pair) {
+                add_holder_to_facts(facts, holder);
+            }
+        }
+
+        for metadata in MetadataRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&metadata.token_pair) {
+                add_metadata_to_facts(facts, metadata);
+            }
+        }
+
+        let lifecycles: HashMap<TokenPairId, Lifecycle> = LifecycleRepo::list(&mut *tx, scope)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|l| (l.token_pair, l))
+            .collect();
+        for (token_pair, facts) in result.iter_mut() {
+            add_lifecycle_to_facts(facts, lifecycles.get(token_pair));
+        }
+
         for timeframe in [
             Timeframe::M1,
             Timeframe::M5,
@@ -122,16 +183,44 @@ impl FactService {
                 .unwrap();
 
             for (token_pair_id, summary) in summary {
+                if !in_scope(&token_pair_id) {
+                    continue;
+                }
+
                 let facts = result.entry(token_pair_id).or_insert(Facts::default());
                 add_summary_to_facts(facts, summary, timeframe);
 
                 facts.set_value(VenuePumpfun, Value::boolean(true));
             }
 
-            println!(
-                "Summary {:?} took: {}",
+            for (token_pair_id, volume) in LifecycleRepo::list_volume(&mut *tx, timeframe, scope)
+                .await
+                .unwrap()
+            {
+                if let Some(facts) = result.get_mut(&token_pair_id) {
+                    add_volume_to_facts(facts, volume, timeframe);
+                }
+            }
+
+            for liquidity in LiquidityRepo::list(&mut *tx, timeframe, scope)
+                .await
+                .unwrap()
+            {
+                if let Some(facts) = result.get_mut(&liquidity.token_pair) {
+                    add_liquidity_to_facts(facts, liquidity, timeframe);
+                }
+            }
+
+            for failed in FailedRepo::list(&mut *tx, timeframe, scope).await.unwrap() {
+                if let Some(facts) = result.get_mut(&failed.token_pair) {
+                    add_failed_to_facts(facts, failed, timeframe);
+                }
+            }
+
+            debug!(
+                "summary {:?} - took {} ms",
                 timeframe,
-                Instant::now().duration_since(start).as_millis()
+                start.elapsed().as_millis()
             );
         }
         tx.commit().await.unwrap();
@@ -7,19 +7,23 @@ pub mod state;
 pub use crate::rule::pumpfun::fact::FactService;
 use crate::rule::pumpfun::state::{Service, State, StateInner};
 use crate::config::RuleConfig;
-use base::model::Action;
+use crate::notify::{Wakeup, CHANNEL_BLOCK_INDEXED, CHANNEL_TOKEN_GRADUATED};
+use crate::rule::evaluate::run_evaluation;
+use crate::webhook::SecretCipher;
+use base::model::TokenPairId;
 use base::model::Venue::PumpFun;
-use base::repo::{InvocationCreateCmd, InvocationRepo, NotificationRepo, TokenPairRepo, TokenRepo};
-use base::service::{NotificationRuleMatched, NotificationService, RuleService};
+use base::repo::{NotificationRepo, TokenPairRepo, TokenRepo};
+use base::service::{NotificationService, RuleService};
 use common::repo::pool::setup_pool;
 use log::info;
 use solana::pumpfun::repo::SummaryRepo;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
-use tokio::time::{sleep, Instant};
+use tokio::time::Instant;
+use tracing::debug;
 
-pub fn start_rules(cfg: RuleConfig) -> JoinHandle<()> {
+pub fn start_rules(cfg: RuleConfig, webhook: Option<SecretCipher>) -> JoinHandle<()> {
     tokio::spawn(async move {
         info!("active");
 
@@ -37,82 +41,58 @@ pub fn start_rules(cfg: RuleConfig) -> JoinHandle<()> {
                 ),
                 notification: NotificationService::new(pool.clone(), NotificationRepo::new()),
                 rule: RuleService::new(pool.clone()),
+                webhook,
             },
         }));
 
+        // graduations get evaluated right away, so that rules can act on the event itself
+        let mut wakeup =
+            Wakeup::listen_all(&pool, &[CHANNEL_BLOCK_INDEXED, CHANNEL_TOKEN_GRADUATED]).await;
+
         loop {
             run_rules(state.clone()).await;
-            sleep(Duration::from_millis(1000)).await;
+
+            // re-evaluate pairs touched by freshly indexed blocks right away,
+            // everything else gets evaluated once per second
+            let deadline = Instant::now() + Duration::from_millis(1000);
+            while let Some(token_pairs) = wakeup.block_indexed_until(deadline).await {
+                run_rules_for(state.clone(), &token_pairs).await;
+            }
         }
     })
 }
 
 pub async fn run_rules(state: State) {
+    evaluate_rules(state, None).await
+}
+
+/// Evaluates the rules only for the given token pairs
+pub async fn run_rules_for(state: State, token_pairs: &[TokenPairId]) {
+    evaluate_rules(state, Some(token_pairs)).await
+}
+
+async fn evaluate_rules(state: State, scope: Option<&[TokenPairId]>) {
     let rules = state.service.rule.list_active().await.unwrap();
 
     let start = Instant::now();
-    let pumpfun_facts = state.service.fact.pumpfun_facts().await;
-    println!(
-        "{} pumpfun facts - took {}",
+    let pumpfun_facts = match scope {
+        Some(token_pairs) => state.service.fact.pumpfun_facts_for(token_pairs).await,
+        None => state.service.fact.pumpfun_facts().await,
+    };
+    debug!(
+        "{} pumpfun facts - took {} ms",
         pumpfun_facts.len(),
-        (Instant::now().duration_since(start)).as_millis()
+        start.elapsed().as_millis()
     );
 
-    for rule in &rules {
-        if !rule.applicable() {
-            // FIXME filter them out before hitting this loop
-            continue;
-        }
-        println!("test rule - {}", rule.id.0);
-        for (token_pair_id, facts) in &pumpfun_facts {
-            if rule.sequence.condition.test(facts) {
-                let mut tx = state.pool.begin().await.unwrap();
-
-                match InvocationRepo::new()
-                    .create(
-                        &mut tx,
-                        InvocationCreateCmd {
-                            user: rule.user,
-                            rule: rule.id,
-                            token_pair: *token_pair_id,
-                            next: None,
-                        },
-                    )
-                    .await
-                {
-                    Ok(_) => {
-                        println!("met - {token_pair_id}");
-
-                        match &rule.sequence.action {
-                            Action::AndThen { .. } => {}
-                            Action::Buy => {}
-                            Action::NotifyTelegram { buttons } => {
-                                let _ = state
-                                    .service
-                                    .notification
-                                    .create_rule_matched_tx(
-                                        &mut tx,
-                                        NotificationRuleMatched::Telegram {
-                                            user: rule.user,
-                                            rule: rule.id,
-                                            venue: PumpFun,
-                                            token_pair: *token_pair_id,
-                                            buttons: buttons.clone(),
-                                        },
-                                    )
-                                    .await;
-                            }
-                            Action::Sell => {}
-                        }
-
-                        tx.commit().await.unwrap();
-                    }
-                    Err(_) => {
-                        // FIXME cache already invoked strategies - otherwise this might be heavy on the database
-                        tx.rollback().await.unwrap();
-                    }
-                }
-            }
-        }
-    }
+    run_evaluation(
+        &state.pool,
+        &state.service.notification,
+        state.service.webhook.as_ref(),
+        PumpFun,
+        rules,
+        pumpfun_facts,
+        scope,
+    )
+    .await;
 }
@@ -2,6 +2,7 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 use crate::rule::pumpfun::FactService;
+use crate::webhook::SecretCipher;
 use base::service::{NotificationService, RuleService};
 use std::ops::Deref;
 use std::sync::Arc;
@@ -28,4 +29,6 @@ pub struct Service {
     pub fact: FactService,
     pub notification: NotificationService,
     pub rule: RuleService,
+    /// none if webhooks are not active
+    pub webhook: Option<SecretCipher>,
 }
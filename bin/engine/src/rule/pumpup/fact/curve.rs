@@ -0,0 +1,106 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::candle::table;
+use crate::rule::scope_ids;
+use base::model::TokenPairId;
+use bigdecimal::BigDecimal;
+use common::model::Timeframe;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone)]
+pub(crate) struct CurveProgress {
+    pub(crate) token_pair: TokenPairId,
+    /// latest close of the 1m progress aggregate
+    pub(crate) progress: BigDecimal,
+    /// seconds since the curve reached its latest progress
+    pub(crate) age: i64,
+}
+
+/// Traded amount of the AI token, not a quote amount
+#[derive(Debug, Clone)]
+pub(crate) struct AiVolume {
+    pub(crate) token_pair: TokenPairId,
+    pub(crate) volume: BigDecimal,
+    pub(crate) volume_buy: BigDecimal,
+    pub(crate) volume_sell: BigDecimal,
+}
+
+pub(crate) struct CurveRepo {}
+
+impl CurveRepo {
+    pub(crate) async fn list_progress(
+        mut executor: impl AsSqlExecutor,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<CurveProgress>> {
+        Ok(query(
+            r#"
+with latest as (
+    select distinct on (token_pair_id) token_pair_id, progress_close
+    from pumpup.candle_progress_1m
+    order by token_pair_id, timestamp desc
+),
+changed as (
+    select c.token_pair_id, max(c.timestamp) as timestamp
+    from pumpup.candle_progress_1m c
+    join latest l on l.token_pair_id = c.token_pair_id
+    where c.progress_close <> l.progress_close
+    group by c.token_pair_id
+),
+reached as (
+    select c.token_pair_id, min(c.timestamp) as timestamp
+    from pumpup.candle_progress_1m c
+    left join changed ch on ch.token_pair_id = c.token_pair_id
+    where ch.timestamp is null or c.timestamp > ch.timestamp
+    group by c.token_pair_id
+)
+select
+    l.token_pair_id,
+    round(l.progress_close::numeric, 2) as progress,
+    extract(epoch from now() - r.timestamp)::int8 as age
+from latest l
+join reached r on r.token_pair_id = l.token_pair_id
+where ($1::int8[] is null or l.token_pair_id = any($1));
+"#,
+        )
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| CurveProgress {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            progress: r.get::<BigDecimal, _>("progress"),
+            age: r.get::<i64, _>("age"),
+        })
+        .collect())
+    }
+
+    /// Traded amount of the AI token within the timeframe up until now, as summarized by the aggregator
+    pub(crate) async fn list_ai_volume(
+        mut executor: impl AsSqlExecutor,
+        timeframe: Timeframe,
+        scope: Option<&[TokenPairId]>,
+    ) -> RepoResult<Vec<AiVolume>> {
+        Ok(query(&format!(
+            r#"
+select token_pair_id, volume, volume_buy, volume_sell
+from pumpup.summary_ai_{table}
+where ($1::int8[] is null or token_pair_id = any($1));
+"#,
+            table = table(timeframe)
+        ))
+        .bind(scope_ids(scope))
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| AiVolume {
+            token_pair: r.get::<TokenPairId, _>("token_pair_id"),
+            volume: r.get::<BigDecimal, _>("volume"),
+            volume_buy: r.get::<BigDecimal, _>("volume_buy"),
+            volume_sell: r.get::<BigDecimal, _>("volume_sell"),
+        })
+        .collect())
+    }
+}
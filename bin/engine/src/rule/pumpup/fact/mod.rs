@@ -1,17 +1,27 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+mod curve;
 mod summary;
 
+use crate::rule::creator::{add_creator_to_facts, CreatorRepo};
+use crate::rule::holder::{add_holder_to_facts, HolderRepo};
+use crate::rule::indicator::add_indicators_to_facts;
+use crate::rule::metadata::{add_metadata_to_facts, MetadataRepo};
+use crate::rule::pumpup::fact::curve::CurveRepo;
 use crate::rule::pumpup::fact::summary::add_summary_to_facts;
-use base::model::Fact::{MarketCapQuote, MarketCapUsd, VenuePumpup};
-use base::model::{Fact, Facts, TokenPairId, Value};
+use base::model::Fact::{
+    CurveProgressAgeDuration, CurveProgressPercent, MarketCapQuote, MarketCapUsd, VenuePumpup,
+    VolumeAiAggregate, VolumeAiBuyAggregate, VolumeAiSellAggregate,
+};
+use base::model::{Fact, Facts, TokenPairId, Value, Venue};
 use base::repo::TokenPairRepo;
-use common::model::{Limit, Timeframe};
+use common::model::{Limit, TimeUnit, Timeframe};
 use solana::pumpup::repo::{CurrentQuery, CurrentRepo, SummaryQuery, SummaryRepo};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::time::Instant;
+use tracing::debug;
 
 #[derive(Clone)]
 pub struct FactService {
@@ -30,6 +40,25 @@ impl FactService {
     }
 
     pub async fn pumpup_facts(&self) -> HashMap<TokenPairId, Facts> {
+        self.facts(None).await
+    }
+
+    /// Facts of the given token pairs only, e.g. the ones touched by a freshly indexed block
+    pub async fn pumpup_facts_for(
+        &self,
+        token_pairs: &[TokenPairId],
+    ) -> HashMap<TokenPairId, Facts> {
+        self.facts(Some(token_pairs)).await
+    }
+
+    async fn facts(&self, scope: Option<&[TokenPairId]>) -> HashMap<TokenPairId, Facts> {
+        let scope_set: Option<HashSet<TokenPairId>> = scope.map(|s| s.iter().copied().collect());
+        let in_scope = |token_pair: &TokenPairId| {
+            scope_set
+                .as_ref()
+                .map_or(true, |scope| scope.contains(token_pair))
+        };
+
         let mut tx = self.pool.begin().await.unwrap();
 
         let start = Instant::now();
@@ -39,31 +68,29 @@ impl FactService {
             .await
             .unwrap()
             .into_iter()
+            .filter(|tp| in_scope(&tp.id))
             .map(|tp| {
-                let facts = Facts::new();
-
-                // if let Some(age) = tp.base.age() {
-                //     facts.set_value(
-                //         Fact::AgeBaseDuration,
-                //         Value::duration(age.0, TimeUnit::Second),
-                //     )
-                // }
-                //
-                // if let Some(age) = tp.quote.age() {
-                //     facts.set_value(
-                //         Fact::AgeQuoteDuration,
-                //         Value::duration(age.0, TimeUnit::Second),
-                //     )
-                // }
+                let mut facts = Facts::new();
+
+                if let Some(age) = tp.base.age() {
+                    facts.set_value(
+                        Fact::AgeBaseDuration,
+                        Value::duration(age.0, TimeUnit::Second),
+                    )
+                }
+
+                if let Some(age) = tp.quote.age() {
+                    facts.set_value(
+                        Fact::AgeQuoteDuration,
+                        Value::duration(age.0, TimeUnit::Second),
+                    )
+                }
 
                 (tp.id, facts)
             })
             .collect();
 
-        println!(
-            "token pairs took: {}",
-            Instant::now().duration_since(start).as_millis()
-        );
+        debug!("token pairs - took {} ms", start.elapsed().as_millis());
 
         for current in CurrentRepo::list(
             &mut *tx,
@@ -75,11 +102,6 @@ impl FactService {
         .unwrap()
         {
             if let Some(facts) = result.get_mut(&current.id) {
-                // facts.set_value(CurveProgressPercent, Value::percent(BigDecimal::from_f32(current.progress.0).unwrap()));
-                // facts.set_value(
-                //     CurveProgressAgeDuration,
-                //     Value::duration(current.age.0, TimeUnit::Second),
-                // );
                 facts.set_value(VenuePumpup, Value::boolean(true));
 
                 facts.set_value(Fact::PriceQuote, Value::quote(current.price.0));
@@ -97,6 +119,40 @@ impl FactService {
             }
         }
 
+        for progress in CurveRepo::list_progress(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&progress.token_pair) {
+                facts.set_value(CurveProgressPercent, Value::percent(progress.progress));
+                facts.set_value(
+                    CurveProgressAgeDuration,
+                    Value::duration(progress.age, TimeUnit::Second),
+                );
+            }
+        }
+
+        let start = Instant::now();
+        add_indicators_to_facts(&mut tx, Venue::PumpUp, &mut result, scope)
+            .await
+            .unwrap();
+        debug!("indicators - took {} ms", start.elapsed().as_millis());
+
+        for creator in CreatorRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&creator.token_pair) {
+                add_creator_to_facts(facts, creator);
+            }
+        }
+
+        for holder in HolderRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&holder.token_pair) {
+                add_holder_to_facts(facts, holder);
+            }
+        }
+
+        for metadata in MetadataRepo::list(&mut *tx, scope).await.unwrap() {
+            if let Some(facts) = result.get_mut(&metadata.token_pair) {
+                add_metadata_to_facts(facts, metadata);
+            }
+        }
+
         for timeframe in [
             Timeframe::M1,
             Timeframe::M5,
@@ -120,16 +176,43 @@ impl FactService {
                 .unwrap();
 
             for (token_pair_id, summary) in summary {
+                if !in_scope(&token_pair_id) {
+                    continue;
+                }
+
                 let facts = result.entry(token_pair_id).or_insert(Facts::default());
                 add_summary_to_facts(facts, summary, timeframe);
 
                 facts.set_value(VenuePumpup, Value::boolean(true));
             }
 
-            println!(
-                "Summary {:?} took: {}",
+            for volume in CurveRepo::list_ai_volume(&mut *tx, timeframe, scope)
+                .await
+                .unwrap()
+            {
+                if let Some(facts) = result.get_mut(&volume.token_pair) {
+                    facts.set_timeframe_value(
+                        VolumeAiAggregate,
+                        Value::amount(volume.volume),
+                        timeframe,
+                    );
+                    facts.set_timeframe_value(
+                        VolumeAiBuyAggregate,
+                        Value::amount(volume.volume_buy),
+                        timeframe,
+                    );
+                    facts.set_timeframe_value(
+                        VolumeAiSellAggregate,
+                        Value::amount(volume.volume_sell),
+                        timeframe,
+                    );
+                }
+            }
+
+            debug!(
+                "summary {:?} - took {} ms",
                 timeframe,
-                Instant::now().duration_since(start).as_millis()
+                start.elapsed().as_millis()
             );
         }
         tx.commit().await.unwrap();
@@ -0,0 +1,396 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::command::TriggerCommand;
+use crate::config::RuleConfig;
+use base::model::{RuleId, TokenPairId};
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{query, PgPool, Row};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Decides how often a rule gets invoked for the same token pair
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TriggerPolicy {
+    /// at most once per token pair
+    #[default]
+    Once,
+    /// again once the cooldown in seconds passed since the last invocation
+    Cooldown { seconds: i64 },
+    /// every time the condition changes from not met to met
+    Edge,
+    /// at most the given number of times per token pair
+    Max { count: i64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerState {
+    pub count: i64,
+    /// whether the condition was met during the previous evaluation
+    pub matched: bool,
+    /// last invocation in seconds since epoch
+    pub triggered_at: i64,
+}
+
+impl TriggerPolicy {
+    pub fn should_trigger(&self, state: Option<&TriggerState>, now: i64) -> bool {
+        let Some(state) = state else {
+            return true;
+        };
+
+        match self {
+            TriggerPolicy::Once => state.count == 0,
+            TriggerPolicy::Cooldown { seconds } => now - state.triggered_at >= *seconds,
+            TriggerPolicy::Edge => !state.matched,
+            TriggerPolicy::Max { count } => state.count < *count,
+        }
+    }
+}
+
+pub struct TriggerRepo {}
+
+impl TriggerRepo {
+    pub async fn list_policies(
+        mut executor: impl AsSqlExecutor,
+        rules: &[RuleId],
+    ) -> RepoResult<HashMap<RuleId, TriggerPolicy>> {
+        Ok(query(
+            r#"
+select id, trigger, trigger_cooldown, trigger_max
+from solana.rule
+where id = any($1);
+"#,
+        )
+        .bind(rules.iter().map(|r| r.0).collect::<Vec<i64>>())
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| (r.get::<RuleId, _>("id"), policy_from_row(&r)))
+        .collect())
+    }
+
+    pub async fn get_policy(
+        mut executor: impl AsSqlExecutor,
+        rule: RuleId,
+    ) -> RepoResult<TriggerPolicy> {
+        Ok(query(
+            r#"
+select id, trigger, trigger_cooldown, trigger_max
+from solana.rule
+where id = $1;
+"#,
+        )
+        .bind(rule)
+        .fetch_optional(executor.as_executor())
+        .await?
+        .map(|r| policy_from_row(&r))
+        .unwrap_or_default())
+    }
+
+    pub async fn set_policy(
+        mut executor: impl AsSqlExecutor,
+        rule: RuleId,
+        policy: TriggerPolicy,
+    ) -> RepoResult<()> {
+        let (trigger, cooldown, max) = match policy {
+            TriggerPolicy::Once => ("ONCE", None, None),
+            TriggerPolicy::Cooldown { seconds } => ("COOLDOWN", Some(seconds), None),
+            TriggerPolicy::Edge => ("EDGE", None, None),
+            TriggerPolicy::Max { count } => ("MAX", None, Some(count)),
+        };
+
+        query(
+            r#"
+update solana.rule set
+    trigger = $2,
+    trigger_cooldown = $3,
+    trigger_max = $4
+where id = $1;
+"#,
+        )
+        .bind(rule)
+        .bind(trigger)
+        .bind(cooldown)
+        .bind(max)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Lists the trigger states of the rules for the given token pairs
+    pub async fn list_states(
+        mut executor: impl AsSqlExecutor,
+        rules: &[RuleId],
+        token_pairs: &[TokenPairId],
+    ) -> RepoResult<HashMap<(RuleId, TokenPairId), TriggerState>> {
+        Ok(query(
+            r#"
+select
+    rule_id,
+    token_pair_id,
+    count,
+    matched,
+    extract(epoch from triggered_at)::int8 as triggered_at
+from solana.rule_trigger
+where rule_id = any($1)
+  and token_pair_id = any($2);
+"#,
+        )
+        .bind(rules.iter().map(|r| r.0).collect::<Vec<i64>>())
+        .bind(token_pairs.iter().map(|t| t.0).collect::<Vec<i64>>())
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| {
+            (
+                (
+                    r.get::<RuleId, _>("rule_id"),
+                    r.get::<TokenPairId, _>("token_pair_id"),
+                ),
+                TriggerState {
+                    count: r.get::<i64, _>("count"),
+                    matched: r.get::<bool, _>("matched"),
+                    triggered_at: r.get::<i64, _>("triggered_at"),
+                },
+            )
+        })
+        .collect())
+    }
+
+    /// Locks the trigger state of the rule for the token pair until the end of the transaction, so
+    /// that concurrent evaluations can not invoke the rule beyond its trigger policy. A token pair
+    /// which never triggered gets a state without invocations.
+    pub async fn lock(
+        mut executor: impl AsSqlExecutor,
+        rule: RuleId,
+        token_pair: TokenPairId,
+    ) -> RepoResult<TriggerState> {
+        query(
+            r#"
+insert into solana.rule_trigger (rule_id, token_pair_id, count, matched, triggered_at)
+values ($1, $2, 0, false, 'epoch')
+on conflict (rule_id, token_pair_id) do nothing;
+"#,
+        )
+        .bind(rule)
+        .bind(token_pair)
+        .execute(executor.as_executor())
+        .await?;
+
+        let row = query(
+            r#"
+select count, matched, extract(epoch from triggered_at)::int8 as triggered_at
+from solana.rule_trigger
+where rule_id = $1
+  and token_pair_id = $2
+for update;
+"#,
+        )
+        .bind(rule)
+        .bind(token_pair)
+        .fetch_one(executor.as_executor())
+        .await?;
+
+        Ok(TriggerState {
+            count: row.get::<i64, _>("count"),
+            matched: row.get::<bool, _>("matched"),
+            triggered_at: row.get::<i64, _>("triggered_at"),
+        })
+    }
+
+    pub async fn triggered(
+        mut executor: impl AsSqlExecutor,
+        rule: RuleId,
+        token_pair: TokenPairId,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.rule_trigger (rule_id, token_pair_id, count, matched, triggered_at)
+values ($1, $2, 1, true, now())
+on conflict (rule_id, token_pair_id) do update set
+    count = solana.rule_trigger.count + 1,
+    matched = true,
+    triggered_at = now(),
+    updated_at = now();
+"#,
+        )
+        .bind(rule)
+        .bind(token_pair)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Marks the evaluated token pairs of the rule, which are not part of matched, as no longer
+    /// matching. Token pairs evaluated by another venue are left as they are.
+    pub async fn unmatched(
+        mut executor: impl AsSqlExecutor,
+        rule: RuleId,
+        matched: &[TokenPairId],
+        evaluated: &[TokenPairId],
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update solana.rule_trigger set
+    matched = false,
+    updated_at = now()
+where rule_id = $1
+  and matched = true
+  and token_pair_id != all($2)
+  and token_pair_id = any($3);
+"#,
+        )
+        .bind(rule)
+        .bind(matched.iter().map(|t| t.0).collect::<Vec<i64>>())
+        .bind(evaluated.iter().map(|t| t.0).collect::<Vec<i64>>())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+}
+
+fn policy_from_row(row: &PgRow) -> TriggerPolicy {
+    match (
+        row.get::<String, _>("trigger").as_str(),
+        row.get::<Option<i64>, _>("trigger_cooldown"),
+        row.get::<Option<i64>, _>("trigger_max"),
+    ) {
+        ("COOLDOWN", Some(seconds), _) => TriggerPolicy::Cooldown { seconds },
+        ("EDGE", _, _) => TriggerPolicy::Edge,
+        ("MAX", _, Some(count)) => TriggerPolicy::Max { count },
+        _ => TriggerPolicy::Once,
+    }
+}
+
+/// Trigger policies and states of the evaluated rules and token pairs, loaded once per
+/// evaluation round
+pub struct Triggers {
+    policies: HashMap<RuleId, TriggerPolicy>,
+    states: HashMap<(RuleId, TokenPairId), TriggerState>,
+    now: i64,
+}
+
+impl Triggers {
+    pub async fn load(
+        pool: &PgPool,
+        rules: &[RuleId],
+        token_pairs: &[TokenPairId],
+    ) -> RepoResult<Self> {
+        let mut tx = pool.begin().await?;
+        let policies = TriggerRepo::list_policies(&mut tx, rules).await?;
+        let states = TriggerRepo::list_states(&mut tx, rules, token_pairs).await?;
+        tx.commit().await?;
+
+        Ok(Self {
+            policies,
+            states,
+            now: now(),
+        })
+    }
+
+    pub fn policy(&self, rule: RuleId) -> TriggerPolicy {
+        self.policies.get(&rule).copied().unwrap_or_default()
+    }
+
+    pub fn should_trigger(&self, rule: RuleId, token_pair: TokenPairId) -> bool {
+        self.policy(rule)
+            .should_trigger(self.states.get(&(rule, token_pair)), self.now)
+    }
+}
+
+/// Seconds since epoch
+pub(crate) fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Updates the trigger policy of the rule, if one was given, and prints the policy in effect
+pub async fn run_trigger(cfg: RuleConfig, cmd: TriggerCommand) {
+    let pool = setup_pool(cfg).await;
+    let rule = RuleId::from(cmd.rule);
+
+    let mut tx = pool.begin().await.unwrap();
+    if let Some(policy) = cmd.policy {
+        TriggerRepo::set_policy(&mut tx, rule, policy)
+            .await
+            .unwrap();
+    }
+
+    let policy = TriggerRepo::get_policy(&mut tx, rule).await.unwrap();
+    tx.commit().await.unwrap();
+    println!("{}", serde_json::to_string_pretty(&policy).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::trigger::{TriggerPolicy, TriggerState};
+    use serde_json::json;
+
+    fn state(count: i64, matched: bool, triggered_at: i64) -> TriggerState {
+        TriggerState {
+            count,
+            matched,
+            triggered_at,
+        }
+    }
+
+    #[test]
+    fn test_never_triggered() {
+        for policy in [
+            TriggerPolicy::Once,
+            TriggerPolicy::Cooldown { seconds: 60 },
+            TriggerPolicy::Edge,
+            TriggerPolicy::Max { count: 2 },
+        ] {
+            assert!(policy.should_trigger(None, 1_000));
+        }
+    }
+
+    #[test]
+    fn test_once() {
+        assert!(!TriggerPolicy::Once.should_trigger(Some(&state(1, false, 0)), 1_000));
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let policy = TriggerPolicy::Cooldown { seconds: 60 };
+        assert!(!policy.should_trigger(Some(&state(1, true, 1_000)), 1_059));
+        assert!(policy.should_trigger(Some(&state(1, true, 1_000)), 1_060));
+    }
+
+    #[test]
+    fn test_edge() {
+        assert!(!TriggerPolicy::Edge.should_trigger(Some(&state(1, true, 0)), 1_000));
+        assert!(TriggerPolicy::Edge.should_trigger(Some(&state(1, false, 0)), 1_000));
+    }
+
+    #[test]
+    fn test_max() {
+        let policy = TriggerPolicy::Max { count: 2 };
+        assert!(policy.should_trigger(Some(&state(1, true, 0)), 1_000));
+        assert!(!policy.should_trigger(Some(&state(2, true, 0)), 1_000));
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::to_value(TriggerPolicy::Cooldown { seconds: 60 }).unwrap(),
+            json!({"kind": "COOLDOWN", "seconds": 60})
+        );
+        assert_eq!(
+            serde_json::from_value::<TriggerPolicy>(json!({"kind": "MAX", "count": 3})).unwrap(),
+            TriggerPolicy::Max { count: 3 }
+        );
+        assert_eq!(
+            serde_json::from_value::<TriggerPolicy>(json!({"kind": "EDGE"})).unwrap(),
+            TriggerPolicy::Edge
+        );
+    }
+}
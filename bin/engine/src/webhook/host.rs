@@ -0,0 +1,154 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use reqwest::{Client, ClientBuilder};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use tokio::net::lookup_host;
+use url::{Host, Url};
+
+/// Host of a url which passed check_public, along with the addresses it got checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckedHost {
+    /// none if the url holds the address itself
+    pub domain: Option<String>,
+    pub addresses: Vec<SocketAddr>,
+}
+
+impl CheckedHost {
+    /// Client which connects to the checked addresses only. Resolving the domain again would
+    /// let its dns answer with another, non public address between check and request.
+    pub fn client(&self, builder: ClientBuilder) -> reqwest::Result<Client> {
+        match &self.domain {
+            Some(domain) => builder.resolve_to_addrs(domain, &self.addresses).build(),
+            None => builder.build(),
+        }
+    }
+}
+
+/// Rejects urls, which are not http(s) or whose host resolves to an address within a private
+/// network, e.g. loopback, private or link local addresses. Users must not be able to make us
+/// post into our own infrastructure. Requests must go through the client of the checked host.
+pub async fn check_public(url: &str) -> Result<CheckedHost, String> {
+    let url = Url::parse(url).map_err(|err| format!("invalid url: {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("unsupported scheme {}", url.scheme()));
+    }
+
+    let port = url.port_or_known_default().unwrap_or(443);
+    let (domain, addresses): (Option<String>, Vec<SocketAddr>) = match url.host() {
+        Some(Host::Ipv4(ip)) => (None, vec![SocketAddr::new(IpAddr::V4(ip), port)]),
+        Some(Host::Ipv6(ip)) => (None, vec![SocketAddr::new(IpAddr::V6(ip), port)]),
+        Some(Host::Domain(domain)) => (
+            Some(domain.to_string()),
+            lookup_host((domain, port))
+                .await
+                .map_err(|err| format!("failed to resolve {domain}: {err}"))?
+                .collect(),
+        ),
+        None => return Err("url without host".to_string()),
+    };
+
+    if addresses.is_empty() {
+        return Err("host does not resolve".to_string());
+    }
+
+    match addresses.iter().find(|address| !is_public(address.ip())) {
+        Some(address) => Err(format!(
+            "host resolves to non public address {}",
+            address.ip()
+        )),
+        None => Ok(CheckedHost { domain, addresses }),
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // this network
+        || a == 0
+        // carrier grade nat
+        || (a == 100 && (64..128).contains(&b)))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // unique local
+        || (first & 0xfe00) == 0xfc00
+        // link local
+        || (first & 0xffc0) == 0xfe80)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webhook::host::{check_public, is_public, CheckedHost};
+    use std::net::{IpAddr, SocketAddr};
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_is_public() {
+        assert!(is_public(ip("8.8.8.8")));
+        assert!(is_public(ip("2606:4700:4700::1111")));
+
+        assert!(!is_public(ip("127.0.0.1")));
+        assert!(!is_public(ip("10.1.2.3")));
+        assert!(!is_public(ip("172.16.0.1")));
+        assert!(!is_public(ip("192.168.1.1")));
+        assert!(!is_public(ip("169.254.169.254")));
+        assert!(!is_public(ip("100.64.0.1")));
+        assert!(!is_public(ip("0.0.0.0")));
+        assert!(!is_public(ip("::1")));
+        assert!(!is_public(ip("fd00::1")));
+        assert!(!is_public(ip("fe80::1")));
+        assert!(!is_public(ip("::ffff:127.0.0.1")));
+    }
+
+    #[tokio::test]
+    async fn test_check_public() {
+        assert!(check_public("http://8.8.8.8/hook").await.is_ok());
+        assert!(check_public("http://127.0.0.1:8080/hook").await.is_err());
+        assert!(check_public("http://[::1]/hook").await.is_err());
+        assert!(check_public("http://localhost/hook").await.is_err());
+        assert!(check_public("ftp://8.8.8.8/hook").await.is_err());
+        assert!(check_public("not a url").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checked_addresses() {
+        assert_eq!(
+            check_public("http://8.8.8.8:8080/hook").await.unwrap(),
+            CheckedHost {
+                domain: None,
+                addresses: vec![SocketAddr::new(ip("8.8.8.8"), 8080)],
+            }
+        );
+        assert_eq!(
+            check_public("https://[2606:4700:4700::1111]/hook")
+                .await
+                .unwrap()
+                .addresses,
+            vec![SocketAddr::new(ip("2606:4700:4700::1111"), 443)]
+        );
+    }
+}
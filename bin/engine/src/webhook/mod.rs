@@ -0,0 +1,257 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use host::{check_public, CheckedHost};
+pub use repo::{WebhookDelivery, WebhookEnqueueCmd, WebhookRepo};
+pub use secret::SecretCipher;
+pub use sign::{sign, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+mod host;
+mod repo;
+mod secret;
+mod sign;
+
+use crate::config::WebhookConfig;
+use base::model::Fact::{
+    AgeBaseDuration, CurveProgressPercent, HolderCount, MarketCapQuote, MarketCapUsd, PriceQuote,
+    PriceUsd,
+};
+use base::model::{Fact, Facts, RuleId, TokenPairId, Venue};
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::ResolveOr;
+use log::{error, info, warn};
+use reqwest::redirect::Policy;
+use reqwest::{Client, ClientBuilder};
+use serde_json::{json, Map};
+use sqlx::PgPool;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+/// Facts sent along with every webhook payload, if known for the token pair
+const KEY_FACTS: [Fact; 7] = [
+    PriceQuote,
+    PriceUsd,
+    MarketCapQuote,
+    MarketCapUsd,
+    AgeBaseDuration,
+    CurveProgressPercent,
+    HolderCount,
+];
+
+/// Number of deliveries attempted per round
+const BATCH_SIZE: usize = 100;
+
+/// Time on top of the request timeout, before a claimed delivery can be claimed again
+const LEASE_MARGIN_SECONDS: i64 = 30;
+
+/// Longest delay between two attempts of the same delivery
+const MAX_BACKOFF_SECONDS: i64 = 3_600;
+
+pub fn start_webhook(cfg: WebhookConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let cipher = match SecretCipher::load(&cfg) {
+            Ok(Some(cipher)) => cipher,
+            Ok(None) => {
+                info!("not active");
+                return;
+            }
+            Err(err) => {
+                error!("invalid config: {err}");
+                return;
+            }
+        };
+
+        info!("active");
+        let webhook = Webhook::new(
+            Duration::from_millis(cfg.timeout_ms.resolve_or(5_000usize) as u64),
+            cfg.max_attempts.resolve_or(8usize) as i32,
+            cipher,
+        );
+        let pool = setup_pool(cfg).await;
+
+        loop {
+            if let Err(err) = webhook.deliver_due(&pool).await {
+                warn!("failed to deliver webhooks: {err:?}");
+            }
+            sleep(Duration::from_millis(1000)).await;
+        }
+    })
+}
+
+/// Json payload posted to the webhook of a rule, once the rule matched a token pair
+pub fn webhook_payload(
+    rule: RuleId,
+    venue: Venue,
+    token_pair: TokenPairId,
+    facts: Option<&Facts>,
+) -> serde_json::Value {
+    let mut key_facts = Map::new();
+    if let Some(facts) = facts {
+        for fact in KEY_FACTS {
+            if let Some(value) = facts.get(&fact) {
+                let key = serde_json::to_value(&fact).unwrap();
+                key_facts.insert(
+                    key.as_str().unwrap_or_default().to_string(),
+                    serde_json::to_value(value).unwrap(),
+                );
+            }
+        }
+    }
+
+    json!({
+        "rule": rule,
+        "venue": venue,
+        "token_pair": token_pair,
+        "facts": key_facts,
+    })
+}
+
+/// Delivers queued webhook payloads, failed deliveries are retried with an exponential backoff
+/// until max attempts is reached, after which the delivery ends up dead.
+/// Every delivery gets claimed and marked within its own short transaction, so that no
+/// transaction is held open while posting.
+pub struct Webhook {
+    client: Client,
+    timeout: Duration,
+    max_attempts: i32,
+    cipher: SecretCipher,
+    allow_private_hosts: bool,
+}
+
+impl Webhook {
+    pub fn new(timeout: Duration, max_attempts: i32, cipher: SecretCipher) -> Self {
+        Self {
+            client: client_builder(timeout).build().unwrap(),
+            timeout,
+            max_attempts,
+            cipher,
+            allow_private_hosts: false,
+        }
+    }
+
+    /// Posts to loopback and private hosts as well, only meant for local development and tests
+    pub fn allow_private_hosts(mut self) -> Self {
+        self.allow_private_hosts = true;
+        self
+    }
+
+    /// Attempts all due deliveries once, returns the number of successful deliveries
+    pub async fn deliver_due(&self, pool: &PgPool) -> RepoResult<usize> {
+        let lease = self.timeout.as_secs() as i64 + LEASE_MARGIN_SECONDS;
+        let mut result = 0;
+
+        for _ in 0..BATCH_SIZE {
+            let mut tx = pool.begin().await?;
+            let delivery = WebhookRepo::claim(&mut tx, lease).await?;
+            tx.commit().await?;
+
+            let Some(delivery) = delivery else {
+                break;
+            };
+
+            let outcome = self.post(&delivery).await;
+
+            let mut tx = pool.begin().await?;
+            match outcome {
+                Ok(_) => {
+                    WebhookRepo::delivered(&mut tx, delivery.id).await?;
+                    result += 1;
+                }
+                Err(Failure::Retry(err)) => {
+                    warn!("webhook delivery {} failed: {err}", delivery.id);
+                    let retry_in = if delivery.attempts + 1 >= self.max_attempts {
+                        None
+                    } else {
+                        Some(backoff(delivery.attempts))
+                    };
+                    WebhookRepo::failed(&mut tx, delivery.id, &err, retry_in).await?;
+                }
+                Err(Failure::Reject(err)) => {
+                    warn!("webhook delivery {} rejected: {err}", delivery.id);
+                    WebhookRepo::failed(&mut tx, delivery.id, &err, None).await?;
+                }
+            }
+            tx.commit().await?;
+        }
+
+        Ok(result)
+    }
+
+    async fn post(&self, delivery: &WebhookDelivery) -> Result<(), Failure> {
+        // the request connects to the addresses which passed the check only
+        let client = if self.allow_private_hosts {
+            self.client.clone()
+        } else {
+            check_public(&delivery.url)
+                .await
+                .map_err(Failure::Reject)?
+                .client(client_builder(self.timeout))
+                .map_err(|err| Failure::Retry(err.to_string()))?
+        };
+
+        let secret = self
+            .cipher
+            .decrypt(&delivery.secret_encrypted)
+            .map_err(Failure::Reject)?;
+
+        let body = delivery.payload.to_string();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let response = client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(&secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| Failure::Retry(err.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(Failure::Retry(format!(
+                "unexpected status {}",
+                response.status()
+            )))
+        }
+    }
+}
+
+fn client_builder(timeout: Duration) -> ClientBuilder {
+    // redirects could point to hosts which did not pass the check
+    Client::builder().timeout(timeout).redirect(Policy::none())
+}
+
+enum Failure {
+    /// might succeed with a later attempt
+    Retry(String),
+    /// will never succeed, e.g. the host is not public
+    Reject(String),
+}
+
+/// Seconds to wait before the next attempt, given the number of attempts made so far
+fn backoff(attempts: i32) -> i64 {
+    10i64
+        .saturating_mul(2i64.saturating_pow(attempts.max(0) as u32))
+        .min(MAX_BACKOFF_SECONDS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webhook::backoff;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(0), 10);
+        assert_eq!(backoff(1), 20);
+        assert_eq!(backoff(3), 80);
+        assert_eq!(backoff(9), 3_600);
+        assert_eq!(backoff(100), 3_600);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::{RuleId, TokenPairId};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub url: String,
+    /// encrypted with the SecretCipher
+    pub secret_encrypted: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+}
+
+pub struct WebhookEnqueueCmd {
+    pub rule: RuleId,
+    pub token_pair: TokenPairId,
+    pub url: String,
+    /// encrypted with the SecretCipher
+    pub secret_encrypted: String,
+    pub payload: serde_json::Value,
+}
+
+pub struct WebhookRepo {}
+
+impl WebhookRepo {
+    pub async fn enqueue(
+        mut executor: impl AsSqlExecutor,
+        cmd: WebhookEnqueueCmd,
+    ) -> RepoResult<i64> {
+        Ok(query(
+            r#"
+insert into solana.webhook_delivery (rule_id, token_pair_id, url, secret_encrypted, payload)
+values ($1, $2, $3, $4, $5::jsonb)
+returning id;
+"#,
+        )
+        .bind(cmd.rule)
+        .bind(cmd.token_pair)
+        .bind(cmd.url)
+        .bind(cmd.secret_encrypted)
+        .bind(cmd.payload.to_string())
+        .fetch_one(executor.as_executor())
+        .await?
+        .get::<i64, _>("id"))
+    }
+
+    /// Claims the next due delivery by pushing its next attempt out by the lease,
+    /// so that no one else picks it up while it is being posted
+    pub async fn claim(
+        mut executor: impl AsSqlExecutor,
+        lease_seconds: i64,
+    ) -> RepoResult<Option<WebhookDelivery>> {
+        Ok(query(
+            r#"
+update solana.webhook_delivery set
+    next_attempt_at = now() + make_interval(secs => $1),
+    updated_at = now()
+where id = (
+    select id
+    from solana.webhook_delivery
+    where status = 'PENDING' and next_attempt_at <= now()
+    order by next_attempt_at
+    limit 1
+    for update skip locked
+)
+returning id, url, secret_encrypted, payload::text as payload, attempts;
+"#,
+        )
+        .bind(lease_seconds as f64)
+        .fetch_optional(executor.as_executor())
+        .await?
+        .map(|r| WebhookDelivery {
+            id: r.get::<i64, _>("id"),
+            url: r.get::<String, _>("url"),
+            secret_encrypted: r.get::<String, _>("secret_encrypted"),
+            payload: serde_json::from_str(&r.get::<String, _>("payload")).expect("payload is json"),
+            attempts: r.get::<i32, _>("attempts"),
+        }))
+    }
+
+    pub async fn delivered(mut executor: impl AsSqlExecutor, id: i64) -> RepoResult<()> {
+        query(
+            r#"
+update solana.webhook_delivery set
+    status = 'DELIVERED',
+    attempts = attempts + 1,
+    last_error = null,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(id)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed attempt, the delivery is retried after the backoff or marked as dead
+    pub async fn failed(
+        mut executor: impl AsSqlExecutor,
+        id: i64,
+        error: &str,
+        retry_in_seconds: Option<i64>,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update solana.webhook_delivery set
+    status = case when $3::int8 is null then 'DEAD' else 'PENDING' end,
+    attempts = attempts + 1,
+    next_attempt_at = now() + make_interval(secs => coalesce($3::int8, 0)),
+    last_error = $2,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_in_seconds)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+}
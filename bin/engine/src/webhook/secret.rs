@@ -0,0 +1,113 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::config::WebhookConfig;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use common::ResolveOr;
+
+const NONCE_LENGTH: usize = 12;
+
+/// Encrypts the webhook secrets of queued deliveries. Payloads are signed with the secret itself,
+/// so it can not be hashed. The secret of the rule action itself is kept by the rule service
+/// of the base crate as given.
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        }
+    }
+
+    /// Cipher keyed by webhook.secret_key, none if webhooks are not active.
+    /// Active webhooks without a valid key are a config error.
+    pub fn load(cfg: &WebhookConfig) -> Result<Option<Self>, String> {
+        if cfg.active.resolve_or(false) != true {
+            return Ok(None);
+        }
+
+        let key = cfg.secret_key.resolve_or(String::new());
+        if key.is_empty() {
+            return Err("webhook.secret_key is required for active webhooks".to_string());
+        }
+        Self::from_hex(&key).map(Some)
+    }
+
+    /// Key given as 64 hex characters
+    pub fn from_hex(key: &str) -> Result<Self, String> {
+        let key: [u8; 32] = hex::decode(key)
+            .map_err(|_| "webhook.secret_key is not hex encoded".to_string())?
+            .try_into()
+            .map_err(|_| "webhook.secret_key has not 32 bytes".to_string())?;
+        Ok(Self::new(key))
+    }
+
+    /// Base64 encoded random nonce followed by the cipher text
+    pub fn encrypt(&self, secret: &str) -> String {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let cipher_text = self
+            .cipher
+            .encrypt(&nonce, secret.as_bytes())
+            .expect("encryption does not fail");
+
+        let mut result = nonce.to_vec();
+        result.extend(cipher_text);
+        STANDARD.encode(result)
+    }
+
+    pub fn decrypt(&self, encrypted: &str) -> Result<String, String> {
+        let bytes = STANDARD
+            .decode(encrypted)
+            .map_err(|err| format!("invalid encrypted secret: {err}"))?;
+        if bytes.len() < NONCE_LENGTH {
+            return Err("invalid encrypted secret: too short".to_string());
+        }
+
+        let (nonce, cipher_text) = bytes.split_at(NONCE_LENGTH);
+        let secret = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), cipher_text)
+            .map_err(|_| "failed to decrypt secret".to_string())?;
+        String::from_utf8(secret).map_err(|err| format!("invalid secret: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webhook::secret::SecretCipher;
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = SecretCipher::new([7; 32]);
+        let encrypted = cipher.encrypt("s3cr3t");
+        assert!(!encrypted.contains("s3cr3t"));
+        assert_ne!(encrypted, cipher.encrypt("s3cr3t"));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "s3cr3t");
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let encrypted = SecretCipher::new([7; 32]).encrypt("s3cr3t");
+        assert!(SecretCipher::new([8; 32]).decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let cipher = SecretCipher::from_hex(&"07".repeat(32)).unwrap();
+        let encrypted = SecretCipher::new([7; 32]).encrypt("s3cr3t");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "s3cr3t");
+    }
+
+    #[test]
+    fn test_from_hex_invalid() {
+        assert!(SecretCipher::from_hex("").is_err());
+        assert!(SecretCipher::from_hex("zz").is_err());
+        assert!(SecretCipher::from_hex(&"07".repeat(16)).is_err());
+    }
+}
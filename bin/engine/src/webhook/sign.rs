@@ -0,0 +1,34 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying the signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Nyanbot-Signature";
+/// Header carrying the unix timestamp, which is part of the signed message
+pub const TIMESTAMP_HEADER: &str = "X-Nyanbot-Timestamp";
+
+/// Hex encoded HMAC-SHA256 of `{timestamp}.{body}`, keyed with the secret of the webhook.
+/// Receivers recompute it to verify the payload was sent by us and was not replayed.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webhook::sign::sign;
+
+    #[test]
+    fn test_sign() {
+        let signature = sign("secret", 1_700_000_000, r#"{"rule":1}"#);
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, sign("secret", 1_700_000_000, r#"{"rule":1}"#));
+        assert_ne!(signature, sign("other", 1_700_000_000, r#"{"rule":1}"#));
+        assert_ne!(signature, sign("secret", 1_700_000_001, r#"{"rule":1}"#));
+    }
+}
@@ -3,3 +3,4 @@
 
 mod rule;
 mod handle;
+mod webhook;
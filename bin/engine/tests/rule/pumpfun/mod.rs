@@ -5,10 +5,12 @@ use base::repo::{NotificationRepo, TokenPairRepo, TokenRepo};
 use base::service::{NotificationService, RuleService};
 use engine::rule::pumpfun::FactService;
 use engine::rule::pumpfun::state::{Service, State, StateInner};
+use engine::webhook::SecretCipher;
 use solana::pumpfun::repo::SummaryRepo;
 use sqlx::PgPool;
 use std::sync::Arc;
 
+mod explain;
 mod pf_001;
 
 pub(crate) fn setup(pool: PgPool) -> State {
@@ -22,6 +24,7 @@ pub(crate) fn setup(pool: PgPool) -> State {
             ),
             notification: NotificationService::new(pool.clone(), NotificationRepo::new()),
             rule: RuleService::new(pool.clone()),
+            webhook: Some(SecretCipher::new([7; 32])),
         },
     }))
 }
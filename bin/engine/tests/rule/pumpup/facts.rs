@@ -0,0 +1,55 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::pumpup::fact_service;
+use base::model::Fact::VolumeAiAggregate;
+use base::model::{TokenPairId, Value};
+use base::testing::run_test_with_pool_on_empty_db;
+use bigdecimal::BigDecimal;
+use common::model::Timeframe;
+use sqlx::Executor;
+
+#[test_log::test(sqlx::test)]
+async fn test_with_and_without_scope() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+    (1000, 0, 'AiTokenAiTokenAiTokenAiTokenAiTokenAiToken1', 'AI', 'AI', 6, 1000000000.000000000000, '2025-05-16 08:00:00'),
+    (1001, 0, 'FirstToken1FirstToken1FirstToken1FirstTok1', 'First', 'FIRST', 6, 1000000000.000000000000, '2025-05-16 08:00:00'),
+    (1002, 0, 'SecondToken2SecondToken2SecondToken2Second', 'Second', 'SECOND', 6, 1000000000.000000000000, '2025-05-16 08:00:00');
+
+insert into solana.token_pair (id, base_id, quote_id) values
+    (2001, 1001, 1000),
+    (2002, 1002, 1000);
+
+insert into pumpup.current (id, slot, virtual_base_reserves, virtual_quote_reserves, progress, complete, price, price_usd, market_cap, market_cap_usd) values
+    (2001, 336000000, 1000000000.000000000000, 30.000000000000, 10.0, false, 0.000000030000, null, 30.000000000000, null),
+    (2002, 336000000, 1000000000.000000000000, 40.000000000000, 20.0, false, 0.000000040000, null, 40.000000000000, null);
+
+insert into pumpup.summary_ai_1m (token_pair_id, volume, volume_buy, volume_sell) values
+    (2001, 100.000000000000, 60.000000000000, 40.000000000000),
+    (2002, 200.000000000000, 150.000000000000, 50.000000000000);
+"#,
+        )
+        .await
+        .unwrap();
+
+        let service = fact_service(pool.clone());
+
+        let facts = service.pumpup_facts().await;
+        assert_eq!(facts.len(), 2);
+        assert_eq!(
+            facts[&TokenPairId::from(2002)].get_timeframe_value(&VolumeAiAggregate, &Timeframe::M1),
+            Some(&Value::amount(BigDecimal::from(200)))
+        );
+
+        let facts = service.pumpup_facts_for(&[TokenPairId::from(2001)]).await;
+        assert_eq!(facts.len(), 1);
+        assert_eq!(
+            facts[&TokenPairId::from(2001)].get_timeframe_value(&VolumeAiAggregate, &Timeframe::M1),
+            Some(&Value::amount(BigDecimal::from(100)))
+        );
+    })
+    .await
+}
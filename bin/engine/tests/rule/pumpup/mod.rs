@@ -0,0 +1,34 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::repo::{NotificationRepo, TokenPairRepo, TokenRepo};
+use base::service::{NotificationService, RuleService};
+use engine::rule::pumpup::state::{Service, State, StateInner};
+use engine::rule::pumpup::FactService;
+use engine::webhook::SecretCipher;
+use solana::pumpup::repo::SummaryRepo;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+mod facts;
+mod trigger;
+
+pub(crate) fn fact_service(pool: PgPool) -> FactService {
+    FactService::new(
+        pool,
+        TokenPairRepo::new(TokenRepo::new_read_only()),
+        SummaryRepo::new(),
+    )
+}
+
+pub(crate) fn setup(pool: PgPool) -> State {
+    State(Arc::new(StateInner {
+        pool: pool.clone(),
+        service: Service {
+            fact: fact_service(pool.clone()),
+            notification: NotificationService::new(pool.clone(), NotificationRepo::new()),
+            rule: RuleService::new(pool.clone()),
+            webhook: Some(SecretCipher::new([7; 32])),
+        },
+    }))
+}
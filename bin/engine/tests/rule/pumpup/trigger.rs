@@ -0,0 +1,45 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::rule::pumpup::setup;
+use base::assert_sql;
+use base::testing::run_test_with_pool_on_empty_db;
+use base::testing::user::get_or_create_test_user;
+use engine::rule::pumpup::run_rules;
+use sqlx::Executor;
+
+#[test_log::test(sqlx::test)]
+async fn test_keeps_matched_pairs_of_other_venue() {
+    // an edge triggered rule matched a pumpfun pair, the pumpup loop does not evaluate that pair
+    // and must not reset it - otherwise the rule fires again on the next pumpfun evaluation
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let mut tx = pool.begin().await.unwrap();
+        get_or_create_test_user(&mut tx).await;
+        tx.commit().await.unwrap();
+
+        pool.execute(
+            r#"
+insert into solana.rule (id, status, version, name, user_id, sequence, created_at, updated_at, rule, trigger) values
+    (14, 1, 1, 'Kitty Paws 🐾', 1, '{"action": {"type": "NOTIFY_TELEGRAM", "buttons": []}, "condition": {"type": "MANAGED", "managed": "PUMP_FUN_MANAGED_KITTY_PAWS"}}', '2025-04-09 01:57:19.325275 +00:00', '2025-04-09 01:57:19.325275 +00:00', 2, 'EDGE');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+    (22675, 0, 'BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump', 'MAD WOLF', 'HOWL', 6, 997489335.785796000000, '2025-03-15 04:10:25');
+
+insert into solana.token_pair (id, base_id, quote_id) values
+    (23073, 22675, 1);
+
+insert into solana.rule_trigger (rule_id, token_pair_id, count, matched, triggered_at) values
+    (14, 23073, 1, true, '2025-04-09 02:00:00.000000 +00:00');
+"#,
+        )
+        .await
+        .unwrap();
+
+        let state = setup(pool.clone());
+        run_rules(state).await;
+
+        assert_sql!(&pool, "(select matched from solana.rule_trigger where rule_id = 14 and token_pair_id = 23073)");
+        assert_sql!(&pool, "(select count(*) from solana.invocation) = 0");
+    })
+    .await
+}
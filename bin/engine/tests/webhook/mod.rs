@@ -0,0 +1,216 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use base::assert_sql;
+use base::model::{RuleId, TokenPairId, Venue};
+use base::testing::run_test_with_pool_on_empty_db;
+use base::testing::user::get_or_create_test_user;
+use engine::webhook::{
+    sign, webhook_payload, SecretCipher, Webhook, WebhookEnqueueCmd, WebhookRepo,
+    SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use sqlx::{Executor, PgPool};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+#[derive(Clone, Default)]
+struct StandIn {
+    status: Arc<Mutex<Vec<StatusCode>>>,
+    received: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+}
+
+async fn receive(State(stand_in): State<StandIn>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    stand_in
+        .received
+        .lock()
+        .unwrap()
+        .push((headers, String::from_utf8(body.to_vec()).unwrap()));
+
+    let mut status = stand_in.status.lock().unwrap();
+    if status.is_empty() {
+        StatusCode::OK
+    } else {
+        status.remove(0)
+    }
+}
+
+/// Local http server standing in for the webhook of a user, responds with the given status codes
+/// in order and with 200 once they are used up
+async fn stand_in(status: Vec<StatusCode>) -> (String, StandIn) {
+    let stand_in = StandIn {
+        status: Arc::new(Mutex::new(status)),
+        received: Arc::default(),
+    };
+
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(stand_in.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (url, stand_in)
+}
+
+fn cipher() -> SecretCipher {
+    SecretCipher::new([7; 32])
+}
+
+/// The stand in listens on loopback, which is rejected by default
+fn local_webhook(max_attempts: i32) -> Webhook {
+    Webhook::new(Duration::from_secs(1), max_attempts, cipher()).allow_private_hosts()
+}
+
+async fn enqueue(pool: &PgPool, url: String) {
+    let mut tx = pool.begin().await.unwrap();
+    WebhookRepo::enqueue(
+        &mut tx,
+        WebhookEnqueueCmd {
+            rule: RuleId(14),
+            token_pair: TokenPairId::from(23073),
+            url,
+            secret_encrypted: cipher().encrypt("s3cr3t"),
+            payload: webhook_payload(RuleId(14), Venue::PumpFun, TokenPairId::from(23073), None),
+        },
+    )
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_delivered() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        prepare(&pool).await;
+        let (url, stand_in) = stand_in(vec![]).await;
+        enqueue(&pool, url).await;
+
+        let webhook = local_webhook(3);
+        assert_eq!(webhook.deliver_due(&pool).await.unwrap(), 1);
+
+        assert_sql!(&pool, "(select status from solana.webhook_delivery where id = 1) = 'DELIVERED'");
+        assert_sql!(&pool, "(select attempts from solana.webhook_delivery where id = 1) = 1");
+
+        let received = stand_in.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+
+        let (headers, body) = &received[0];
+        let timestamp: i64 = headers[TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("s3cr3t", timestamp, body)
+        );
+
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["rule"], 14);
+        assert_eq!(payload["token_pair"], 23073);
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_retried_with_backoff() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        prepare(&pool).await;
+        let (url, stand_in) = stand_in(vec![StatusCode::INTERNAL_SERVER_ERROR]).await;
+        enqueue(&pool, url).await;
+
+        let webhook = local_webhook(3);
+        assert_eq!(webhook.deliver_due(&pool).await.unwrap(), 0);
+
+        assert_sql!(&pool, "(select status from solana.webhook_delivery where id = 1) = 'PENDING'");
+        assert_sql!(&pool, "(select attempts from solana.webhook_delivery where id = 1) = 1");
+        assert_sql!(&pool, "(select next_attempt_at > now() from solana.webhook_delivery where id = 1)");
+        assert_sql!(&pool, "(select last_error from solana.webhook_delivery where id = 1) = 'unexpected status 500 Internal Server Error'");
+
+        // not due yet
+        assert_eq!(webhook.deliver_due(&pool).await.unwrap(), 0);
+        assert_eq!(stand_in.received.lock().unwrap().len(), 1);
+
+        pool.execute("update solana.webhook_delivery set next_attempt_at = now()")
+            .await
+            .unwrap();
+
+        assert_eq!(webhook.deliver_due(&pool).await.unwrap(), 1);
+        assert_sql!(&pool, "(select status from solana.webhook_delivery where id = 1) = 'DELIVERED'");
+        assert_sql!(&pool, "(select attempts from solana.webhook_delivery where id = 1) = 2");
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_dead_letter() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        prepare(&pool).await;
+        let (url, _stand_in) = stand_in(vec![StatusCode::BAD_GATEWAY, StatusCode::BAD_GATEWAY]).await;
+        enqueue(&pool, url).await;
+
+        let webhook = local_webhook(2);
+        webhook.deliver_due(&pool).await.unwrap();
+
+        pool.execute("update solana.webhook_delivery set next_attempt_at = now()")
+            .await
+            .unwrap();
+        webhook.deliver_due(&pool).await.unwrap();
+
+        assert_sql!(&pool, "(select status from solana.webhook_delivery where id = 1) = 'DEAD'");
+        assert_sql!(&pool, "(select attempts from solana.webhook_delivery where id = 1) = 2");
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_private_host_rejected() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        prepare(&pool).await;
+        let (url, stand_in) = stand_in(vec![]).await;
+        enqueue(&pool, url).await;
+
+        let webhook = Webhook::new(Duration::from_secs(1), 3, cipher());
+        assert_eq!(webhook.deliver_due(&pool).await.unwrap(), 0);
+
+        assert_sql!(&pool, "(select status from solana.webhook_delivery where id = 1) = 'DEAD'");
+        assert_sql!(&pool, "(select last_error from solana.webhook_delivery where id = 1) = 'host resolves to non public address 127.0.0.1'");
+        assert!(stand_in.received.lock().unwrap().is_empty());
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_secret_encrypted() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        prepare(&pool).await;
+        enqueue(&pool, "http://127.0.0.1/hook".to_string()).await;
+
+        assert_sql!(&pool, "(select position('s3cr3t' in secret_encrypted) = 0 from solana.webhook_delivery where id = 1)");
+    })
+    .await
+}
+
+async fn prepare(pool: &PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    get_or_create_test_user(&mut tx).await;
+    tx.commit().await.unwrap();
+
+    pool.execute(
+        r#"
+        insert into solana.rule (id, status, version, name, user_id, sequence, created_at, updated_at, rule) values
+            (14, 1, 1, 'Webhook', 1, '{"action": {"type": "NOTIFY_WEBHOOK", "url": "http://127.0.0.1/hook", "secret": "s3cr3t"}, "condition": {"type": "MANAGED", "managed": "PUMP_FUN_MANAGED_KITTY_PAWS"}}', '2025-04-09 01:57:19.325275 +00:00', '2025-04-09 01:57:19.325275 +00:00', 2);
+
+        insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+            (22675, 0, 'BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump', 'MAD WOLF', 'HOWL', 6, 997489335.785796000000, '2025-03-15 04:10:25');
+
+        insert into solana.token_pair (id, base_id, quote_id) values
+            (23073, 22675, 1);
+    "#,
+    )
+    .await
+    .unwrap();
+}
@@ -18,6 +18,20 @@ pub struct Config {
     pub rpc: RpcConfig,
     pub tokio: TokioConfig,
     pub rayon: RayonConfig,
+    #[serde(default)]
+    pub metadata: MetadataConfig,
+    #[serde(default)]
+    pub backfill: BackfillConfig,
+    #[serde(default)]
+    pub gap: GapConfig,
+    #[serde(default)]
+    pub geyser: GeyserConfig,
+    #[serde(default)]
+    pub replay: ReplayConfig,
+    #[serde(default)]
+    pub pipeline: PipelineConfig,
+    #[serde(default)]
+    pub venue: VenueConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -43,8 +57,85 @@ pub struct SlotStreamConfig {
 
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct BlockStreamConfig {
+    /// rpc, geyser or replay - defaults to rpc
+    #[serde(default)]
+    pub source: ConfigValue,
     pub url: ConfigValue,
     pub concurrency: ConfigValue,
+    /// processed, confirmed or finalized - defaults to confirmed
+    #[serde(default)]
+    pub commitment: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct MetadataConfig {
+    pub active: ConfigValue,
+    /// comma separated list of ipfs gateways, tried in order
+    pub gateways: ConfigValue,
+    pub timeout_ms: ConfigValue,
+    pub retries: ConfigValue,
+    pub max_attempts: ConfigValue,
+    pub batch: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct BackfillConfig {
+    pub active: ConfigValue,
+    /// first slot of the range, inclusive
+    pub from: ConfigValue,
+    /// last slot of the range, inclusive
+    pub to: ConfigValue,
+    /// rpc to load the blocks from, defaults to the rpc url
+    pub url: ConfigValue,
+    pub concurrency: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GapConfig {
+    pub active: ConfigValue,
+    /// rpc to load confirmed slots and missing blocks from, defaults to the rpc url
+    pub url: ConfigValue,
+    /// slots audited per round
+    pub window: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GeyserConfig {
+    /// yellowstone grpc endpoint, e.g. http://127.0.0.1:10000
+    pub url: ConfigValue,
+    /// x-token sent along with every request, if the endpoint requires one
+    pub token: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ReplayConfig {
+    /// captured block file or directory of block files to replay
+    pub path: ConfigValue,
+    /// none, realtime or the milliseconds to wait between two blocks - defaults to none
+    pub pace: ConfigValue,
+    /// directory fetched blocks get written to, capturing is disabled if not set
+    pub capture: ConfigValue,
+    /// whether captured blocks get gzipped - defaults to true
+    pub gzip: ConfigValue,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PipelineConfig {
+    /// blocks parsed ahead of the one being written - defaults to 4
+    pub depth: ConfigValue,
+}
+
+/// Program ids of the indexed venues, each defaults to its mainnet program
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct VenueConfig {
+    pub pumpfun: ConfigValue,
+    pub pumpswap: ConfigValue,
+    pub pumpup: ConfigValue,
+    pub jupiter: ConfigValue,
+    pub meteora_dlmm: ConfigValue,
+    pub meteora_damm: ConfigValue,
+    pub meteora_damm_v1: ConfigValue,
+    pub orca: ConfigValue,
 }
 
 impl Config {
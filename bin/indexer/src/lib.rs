@@ -2,4 +2,5 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 pub mod config;
+pub mod host;
 pub mod solana;
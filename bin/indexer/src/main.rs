@@ -27,7 +27,7 @@ pub fn main() {
 
     rayon::ThreadPoolBuilder::new()
         .num_threads(rayon_threads)
-        .build()
+        .build_global()
         .unwrap();
 
     let tokio_threads = config.tokio.threads.resolve_or(1);
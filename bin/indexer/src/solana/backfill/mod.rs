@@ -0,0 +1,284 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::backfill::repo::{Backfill, BackfillId, BackfillRepo};
+
+use crate::config::Config;
+use crate::solana::block::state::State;
+use crate::solana::block::{index_block_with_cursor, setup_state, Cursor};
+use crate::solana::indexer::IndexerRepo;
+use crate::solana::replay::BlockCapture;
+use crate::solana::token_holder::TokenHolderRepo;
+use async_trait::async_trait;
+use base::model::solana::{Block, Slot};
+use base::repo::WalletSwapRepo;
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::{ResolveOr, Signal};
+use futures::future::join_all;
+use log::{info, warn};
+use reqwest::Client;
+use serde::Deserialize;
+use solana::convert::convert_block;
+use solana_transaction_status::UiConfirmedBlock;
+use sqlx::{Postgres, Transaction};
+use std::process::exit;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio::{select, spawn};
+
+mod repo;
+
+/// Attempts to load a block before the backfill gets stopped
+const MAX_ATTEMPTS: usize = 3;
+
+/// Source of historical blocks
+#[async_trait]
+pub trait BlockSource: Send + Sync {
+    /// Loads the block of the slot, none if the slot got skipped by the cluster
+    async fn block(&self, slot: Slot) -> Result<Option<Block>, String>;
+}
+
+/// Loads confirmed blocks with the getBlock json rpc method
+pub struct RpcBlockSource {
+    client: Client,
+    url: String,
+    capture: Option<BlockCapture>,
+}
+
+impl RpcBlockSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap(),
+            url,
+            capture: None,
+        }
+    }
+
+    /// Writes every loaded block to disk
+    pub fn with_capture(self, capture: Option<BlockCapture>) -> Self {
+        Self { capture, ..self }
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<UiConfirmedBlock>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// The leader of the slot did not produce a block, or it got skipped by the cluster
+const SLOT_SKIPPED: [i64; 2] = [-32007, -32009];
+
+impl RpcBlockSource {
+    /// Loads the block once more only to write it to disk, for streams handing out converted
+    /// blocks. Fails if no capture is configured.
+    pub async fn capture(&self, slot: Slot) -> Result<(), String> {
+        if self.capture.is_none() {
+            return Err("no capture configured".to_string());
+        }
+        self.load(slot).await.map(|_| ())
+    }
+
+    /// Loads the block as returned by getBlock and captures it
+    async fn load(&self, slot: Slot) -> Result<Option<UiConfirmedBlock>, String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getBlock",
+                "params": [slot.0, {
+                    "commitment": "confirmed",
+                    "encoding": "base58",
+                    "maxSupportedTransactionVersion": 0,
+                    "transactionDetails": "full",
+                    "rewards": false
+                }]
+            }))
+            .send()
+            .await
+            .map_err(|err| err.to_string())?
+            .json::<RpcResponse>()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let block = match response {
+            RpcResponse {
+                result: Some(block),
+                ..
+            } => block,
+            RpcResponse {
+                error: Some(error), ..
+            } if SLOT_SKIPPED.contains(&error.code) => return Ok(None),
+            RpcResponse { error, .. } => return Err(format!("getBlock failed: {error:?}")),
+        };
+
+        if let Some(capture) = &self.capture {
+            capture.write_in_background(slot, block.clone());
+        }
+
+        Ok(Some(block))
+    }
+}
+
+#[async_trait]
+impl BlockSource for RpcBlockSource {
+    async fn block(&self, slot: Slot) -> Result<Option<Block>, String> {
+        let Some(block) = self.load(slot).await? else {
+            return Ok(None);
+        };
+
+        convert_block(slot, block)
+            .await
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+/// Indexes a historical range of slots with its own cursor, alongside the live indexer
+pub fn backfill_blocks(config: Config, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        let cfg = config.backfill.clone();
+        if !cfg.active.resolve_or(false) {
+            info!("not active");
+            return;
+        }
+
+        let from = cfg.from.resolve_or(Slot::from(0));
+        let to = cfg.to.resolve_or(Slot::from(0));
+        info!("active - backfill {from:?} to {to:?}");
+
+        let pool = setup_pool(config.postgres.clone()).await;
+        let backfiller = Backfiller {
+            state: setup_state(&config, pool),
+            source: RpcBlockSource::new(cfg.url.resolve_or(config.rpc.url.resolve()))
+                .with_capture(BlockCapture::load(&config)),
+            concurrency: cfg.concurrency.resolve_or(4usize),
+        };
+
+        select! {
+            result = backfiller.run(from, to) => {
+                match result {
+                    Ok(backfill) => info!("backfill {} done at {:?}", backfill.id.0, backfill.slot),
+                    Err(err) => warn!("backfill stopped: {err:?}"),
+                }
+            }
+            _ = signal.recv() => {
+                exit(-1);
+            }
+        }
+    })
+}
+
+#[derive(Debug)]
+pub enum BackfillError {
+    Repo(String),
+    /// the block of the slot could not be loaded, re-running the backfill continues with it
+    Source {
+        slot: Slot,
+        error: String,
+    },
+}
+
+pub struct Backfiller<S: BlockSource> {
+    pub state: State,
+    pub source: S,
+    /// blocks loaded concurrently, they get indexed in order
+    pub concurrency: usize,
+}
+
+impl<S: BlockSource> Backfiller<S> {
+    /// Indexes every slot of the range [from, to] that has not been indexed yet.
+    /// Blocks already stored, e.g. by the live indexer, are skipped, so re-running a range is a no-op.
+    /// Aggregates of every indexed block get refreshed along with it, see [refresh_behind].
+    pub async fn run(&self, from: Slot, to: Slot) -> Result<Backfill, BackfillError> {
+        let backfill = repo(BackfillRepo::get_or_create(&self.state.pool, from, to).await)?;
+        self.index(backfill.slot, to, Cursor::Backfill(backfill.id))
+            .await?;
+        repo(BackfillRepo::get_or_create(&self.state.pool, from, to).await)
+    }
+
+    /// Indexes every slot of the range (after, to] that has not been indexed yet and advances the
+    /// cursor with every slot, including the ones without a block
+    pub async fn index(&self, after: Slot, to: Slot, cursor: Cursor) -> Result<(), BackfillError> {
+        let mut slot = after.0;
+
+        while slot < to.0 {
+            let last = (slot + self.concurrency.max(1) as i64).min(to.0);
+            let slots: Vec<Slot> = ((slot + 1)..=last).map(Slot::from).collect();
+
+            let blocks = join_all(slots.iter().map(|slot| self.load(*slot))).await;
+
+            for (slot, block) in slots.iter().zip(blocks) {
+                let block = block.map_err(|error| BackfillError::Source { slot: *slot, error })?;
+                let indexed = repo(BackfillRepo::is_indexed(&self.state.pool, *slot).await)?;
+
+                match (block, cursor) {
+                    (Some(block), _) if !indexed => {
+                        index_block_with_cursor(self.state.clone(), block, cursor).await
+                    }
+                    (_, Cursor::Backfill(backfill)) => {
+                        repo(BackfillRepo::advance(&self.state.pool, backfill, *slot).await)?
+                    }
+                    _ => {}
+                }
+            }
+
+            info!("{cursor:?} at {last}");
+            slot = last;
+        }
+
+        Ok(())
+    }
+
+    async fn load(&self, slot: Slot) -> Result<Option<Block>, String> {
+        let mut attempt = 1;
+        loop {
+            match self.source.block(slot).await {
+                Ok(block) => return Ok(block),
+                Err(err) if attempt >= MAX_ATTEMPTS => return Err(err),
+                Err(err) => {
+                    warn!("failed to load block {slot:?} - attempt {attempt}: {err}");
+                    sleep(Duration::from_millis(500 * attempt as u64)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Brings everything derived from blocks up to date with a block indexed behind the live indexer,
+/// as their refreshers already passed its slot. Runs within the transaction writing the block, so
+/// that a stopped backfill never leaves an indexed block unaccounted.
+pub(crate) async fn refresh_behind(
+    tx: &mut Transaction<'_, Postgres>,
+    slot: Slot,
+) -> RepoResult<()> {
+    // wallet swaps get folded slot by slot, slots ahead of the cursor are left to the refresher
+    if let Some(cursor) = IndexerRepo::get_wallet_swap_slot(&mut *tx).await? {
+        if slot <= cursor {
+            WalletSwapRepo::refresh(&mut *tx, slot).await?;
+        }
+    }
+
+    // holder balances only get overwritten by newer slots, the summaries of all touched tokens get refreshed
+    TokenHolderRepo::refresh(&mut *tx, Slot::from(slot.0 - 1), slot).await?;
+
+    // candles are bucketed by block time, the aggregator recalculates the buckets of the block
+    BackfillRepo::enqueue_aggregate(&mut *tx, slot).await
+}
+
+fn repo<T>(result: RepoResult<T>) -> Result<T, BackfillError> {
+    result.map_err(|err| BackfillError::Repo(format!("{err:?}")))
+}
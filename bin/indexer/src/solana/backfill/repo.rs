@@ -0,0 +1,106 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BackfillId(pub i64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backfill {
+    pub id: BackfillId,
+    pub from: Slot,
+    pub to: Slot,
+    /// last slot of the range which got indexed or skipped
+    pub slot: Slot,
+    pub done: bool,
+}
+
+pub struct BackfillRepo {}
+
+impl BackfillRepo {
+    /// Returns the backfill of the range, re-running a range continues where it stopped
+    pub async fn get_or_create(
+        mut executor: impl AsSqlExecutor,
+        from: impl Into<Slot> + Send,
+        to: impl Into<Slot> + Send,
+    ) -> RepoResult<Backfill> {
+        let from = from.into();
+        let to = to.into();
+
+        Ok(query(
+            r#"
+insert into solana.backfill (from_slot, to_slot, slot) values ($1, $2, $1 - 1)
+on conflict (from_slot, to_slot) do update set updated_at = now()
+returning id, from_slot, to_slot, slot, status;
+"#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_one(executor.as_executor())
+        .await
+        .map(|r| Backfill {
+            id: BackfillId(r.get::<i64, _>("id")),
+            from: r.get::<Slot, _>("from_slot"),
+            to: r.get::<Slot, _>("to_slot"),
+            slot: r.get::<Slot, _>("slot"),
+            done: r.get::<String, _>("status") == "DONE",
+        })?)
+    }
+
+    /// Moves the cursor forward, never backwards
+    pub async fn advance(
+        mut executor: impl AsSqlExecutor,
+        backfill: BackfillId,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update solana.backfill set
+    slot = greatest(slot, $2),
+    status = case when greatest(slot, $2) >= to_slot then 'DONE' else status end,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(backfill.0)
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Whether the block of the slot got indexed already, either live or by another backfill
+    pub async fn is_indexed(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<bool> {
+        Ok(
+            query("select exists(select 1 from solana.block where slot = $1) as indexed;")
+                .bind(slot.into())
+                .fetch_one(executor.as_executor())
+                .await?
+                .get::<bool, _>("indexed"),
+        )
+    }
+
+    /// Queues the block time of the slot, so that the aggregator recalculates its candles
+    pub async fn enqueue_aggregate(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.aggregate_range (from_timestamp, to_timestamp)
+select timestamp, timestamp from solana.block where slot = $1;
+"#,
+        )
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::state::State;
+use crate::solana::token_holder::{HolderBalanceRepo, HolderBalanceToInsert};
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, PublicKey};
+use common::repo::Tx;
+use log::debug;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Records the latest balance of every holder of a tracked token within the block.
+/// Balances of quote mints and of mints which are not known yet are skipped, so that no token info
+/// has to be loaded.
+pub(crate) async fn index_holder_balance<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    slot: Slot,
+    balances: Vec<(PublicKey, Mint, String)>,
+) {
+    if balances.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+
+    let mut mints: Vec<Mint> = balances.iter().map(|(_, mint, _)| mint.clone()).collect();
+    mints.sort();
+    mints.dedup();
+
+    let tokens = HolderBalanceRepo::list_known_tokens(&mut *tx, mints)
+        .await
+        .unwrap();
+
+    let balances: Vec<(PublicKey, Mint, String)> = balances
+        .into_iter()
+        .filter(|(_, mint, _)| tokens.contains_key(mint))
+        .collect();
+
+    if balances.is_empty() {
+        return;
+    }
+
+    let mut keys: Vec<PublicKey> = balances.iter().map(|(key, _, _)| key.clone()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let addresses: HashMap<PublicKey, AddressId> = state
+        .address_repo
+        .list_or_populate(&mut *tx, keys)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.address, a.id))
+        .collect();
+
+    HolderBalanceRepo::insert(
+        &mut *tx,
+        slot,
+        balances
+            .into_iter()
+            .map(|(key, mint, balance)| HolderBalanceToInsert {
+                token: tokens[&mint].clone(),
+                address: addresses[&key],
+                balance,
+            })
+            .collect(),
+    )
+    .await
+    .unwrap();
+
+    debug!("took: {:?} ms", start.elapsed().as_millis());
+}
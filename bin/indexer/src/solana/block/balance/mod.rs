@@ -1,9 +1,11 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+pub(crate) use holder::index_holder_balance;
 pub(crate) use token::index_token_balance;
 pub(crate) use wallet::{index_wallet_balance_token, index_wallet_balance_sol}; 
 
+mod holder;
 mod token;
 mod wallet;
 
@@ -0,0 +1,98 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::failed::parse::FailedTransaction;
+use crate::solana::block::failed::repo::{FailedRepo, FailedToInsert, FailedsToInsert};
+use crate::solana::block::state::State;
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, PublicKey, TokenPairId};
+use common::model::Timestamp;
+use common::repo::Tx;
+use log::debug;
+use std::collections::HashMap;
+use std::time::Instant;
+
+pub mod parse;
+pub mod repo;
+
+pub(crate) async fn index_failed<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    slot: Slot,
+    timestamp: Timestamp,
+    transactions: Vec<FailedTransaction>,
+) {
+    if transactions.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+
+    let mut keys: Vec<PublicKey> = transactions.iter().map(|t| t.signer.clone()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let addresses: HashMap<PublicKey, AddressId> = state
+        .address_repo
+        .list_or_populate(&mut *tx, keys)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.address, a.id))
+        .collect();
+
+    // mints which are not known are not looked up, the mint might not even exist as its creation
+    // could have failed as well
+    let mut mints: Vec<Mint> = transactions.iter().filter_map(|t| t.mint.clone()).collect();
+    mints.sort();
+    mints.dedup();
+
+    let pairs: Vec<(Mint, Mint)> = FailedRepo::list_known_mints(&mut *tx, mints)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|mint| (mint, Mint::wsol()))
+        .collect();
+
+    let token_pairs: HashMap<Mint, TokenPairId> = if pairs.is_empty() {
+        HashMap::new()
+    } else {
+        state
+            .token_pair_repo
+            .list_or_populate(&mut *tx, pairs)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tp| (tp.base.mint, tp.id))
+            .collect()
+    };
+
+    let transactions = transactions
+        .into_iter()
+        .map(|transaction| FailedToInsert {
+            venue: transaction.venue,
+            signer: addresses[&transaction.signer],
+            token_pair: transaction
+                .mint
+                .and_then(|mint| token_pairs.get(&mint).cloned()),
+            is_buy: transaction.is_buy,
+            error: transaction.error,
+            signature: transaction.signature,
+        })
+        .collect();
+
+    FailedRepo::insert_transactions(
+        &mut *tx,
+        FailedsToInsert {
+            slot,
+            timestamp,
+            transactions,
+        },
+    )
+    .await
+    .unwrap();
+    debug!(
+        "failed transaction insert took: {:?} ms",
+        start.elapsed().as_millis()
+    );
+}
@@ -0,0 +1,142 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Transaction;
+use base::model::{Mint, PublicKey};
+
+/// Trade a failed transaction attempted, decoded from the instruction of the venue
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailedTrade {
+    pub mint: Mint,
+    pub is_buy: bool,
+}
+
+/// Transaction calling into the program of a venue which did not succeed
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailedTransaction {
+    pub venue: &'static str,
+    pub signer: PublicKey,
+    /// token the transaction was about to trade against sol
+    pub mint: Option<Mint>,
+    /// none if the venue does not decode its instructions for failed transactions
+    pub is_buy: Option<bool>,
+    pub error: String,
+    pub signature: String,
+}
+
+impl FailedTransaction {
+    /// Falls back to the only token besides wsol among the token balances of the transaction,
+    /// if the venue could not tell what got traded
+    pub fn new(
+        venue: &'static str,
+        transaction: &Transaction,
+        trade: Option<FailedTrade>,
+    ) -> Option<Self> {
+        let signer = transaction.keys.first()?.clone();
+
+        let (mint, is_buy) = match trade {
+            Some(trade) => (Some(trade.mint), Some(trade.is_buy)),
+            None => (traded_mint(transaction), None),
+        };
+
+        Some(Self {
+            venue,
+            signer,
+            mint,
+            is_buy,
+            error: parse_error(&transaction.log_messages),
+            signature: transaction.signature.to_string(),
+        })
+    }
+}
+
+fn traded_mint(transaction: &Transaction) -> Option<Mint> {
+    let wsol = Mint::wsol();
+    let mut mints: Vec<&Mint> = transaction
+        .balance
+        .token
+        .iter()
+        .map(|t| &t.mint)
+        .filter(|m| **m != wsol)
+        .collect();
+    mints.sort();
+    mints.dedup();
+
+    match mints.as_slice() {
+        [mint] => Some((*mint).clone()),
+        _ => None,
+    }
+}
+
+/// Error the transaction failed with, as reported in its logs - the anchor error code if there
+/// is one, otherwise the error message of the program which failed
+pub fn parse_error(log_messages: &[String]) -> String {
+    let anchor = log_messages.iter().rev().find_map(|line| {
+        let line = line.strip_prefix("Program log: AnchorError")?;
+        let (_, code) = line.split_once("Error Code: ")?;
+        code.split_once('.').map(|(code, _)| code.to_string())
+    });
+    if let Some(code) = anchor {
+        return code;
+    }
+
+    let message = log_messages
+        .iter()
+        .rev()
+        .find_map(|line| line.strip_prefix("Program log: Error: "));
+    if let Some(message) = message {
+        return message.to_string();
+    }
+
+    log_messages
+        .iter()
+        .rev()
+        .find_map(|line| {
+            line.split_once(" failed: ")
+                .map(|(_, error)| error.to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_error_anchor() {
+        let logs = logs(&[
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]",
+            "Program log: Instruction: Buy",
+            "Program log: AnchorError thrown in programs/pump/src/lib.rs:712. Error Code: TooMuchSolRequired. Error Number: 6002. Error Message: slippage: Too much SOL required to buy the given amount of tokens..",
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P failed: custom program error: 0x1772",
+        ]);
+        assert_eq!(parse_error(&logs), "TooMuchSolRequired");
+    }
+
+    #[test]
+    fn test_parse_error_message() {
+        let logs = logs(&[
+            "Program log: Error: exceeds desired slippage limit",
+            "Program 675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8 failed: custom program error: 0x1e",
+        ]);
+        assert_eq!(parse_error(&logs), "exceeds desired slippage limit");
+    }
+
+    #[test]
+    fn test_parse_error_failed() {
+        let logs = logs(&[
+            "Program 2FAaDn5qgjhhWsn9mw8CAFPNHvqB7M6TyL4CKVPegeTh invoke [1]",
+            "Program 2FAaDn5qgjhhWsn9mw8CAFPNHvqB7M6TyL4CKVPegeTh failed: custom program error: 0x1",
+        ]);
+        assert_eq!(parse_error(&logs), "custom program error: 0x1");
+    }
+
+    #[test]
+    fn test_parse_error_unknown() {
+        assert_eq!(parse_error(&[]), "unknown");
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, TokenPairId};
+use common::model::Timestamp;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+pub struct FailedToInsert {
+    pub venue: &'static str,
+    pub signer: AddressId,
+    /// token pair the transaction was about to trade, if its token is known
+    pub token_pair: Option<TokenPairId>,
+    pub is_buy: Option<bool>,
+    pub error: String,
+    pub signature: String,
+}
+
+pub struct FailedsToInsert {
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    pub transactions: Vec<FailedToInsert>,
+}
+
+pub struct FailedRepo {}
+
+impl FailedRepo {
+    /// Lists the mints which are known as tokens
+    pub async fn list_known_mints(
+        mut executor: impl AsSqlExecutor,
+        mints: Vec<Mint>,
+    ) -> RepoResult<Vec<Mint>> {
+        if mints.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mints: Vec<String> = mints.into_iter().map(|m| m.to_string()).collect();
+        Ok(query("select mint from solana.token where mint = any($1);")
+            .bind(&mints)
+            .fetch_all(executor.as_executor())
+            .await?
+            .into_iter()
+            .map(|r| Mint::from(r.get::<String, _>("mint")))
+            .collect())
+    }
+
+    /// Inserts the failed transactions
+    pub async fn insert_transactions(
+        mut executor: impl AsSqlExecutor,
+        to_insert: FailedsToInsert,
+    ) -> RepoResult<()> {
+        if to_insert.transactions.is_empty() {
+            return Ok(());
+        }
+
+        let mut venues = Vec::with_capacity(to_insert.transactions.len());
+        let mut signers = Vec::with_capacity(to_insert.transactions.len());
+        let mut token_pairs = Vec::with_capacity(to_insert.transactions.len());
+        let mut is_buys = Vec::with_capacity(to_insert.transactions.len());
+        let mut errors = Vec::with_capacity(to_insert.transactions.len());
+        let mut signatures = Vec::with_capacity(to_insert.transactions.len());
+
+        for transaction in to_insert.transactions {
+            venues.push(transaction.venue);
+            signers.push(transaction.signer.0);
+            token_pairs.push(transaction.token_pair.map(|tp| tp.0));
+            is_buys.push(transaction.is_buy);
+            errors.push(transaction.error);
+            signatures.push(transaction.signature);
+        }
+
+        query(
+            r#"
+insert into solana.failed_transaction (slot, timestamp, venue, token_pair_id, address_id, is_buy, error, signature)
+select $1, $2, f.venue, f.token_pair_id, f.address_id, f.is_buy, f.error, f.signature
+from unnest($3::text[], $4::int8[], $5::int8[], $6::bool[], $7::text[], $8::text[])
+    with ordinality as f(venue, token_pair_id, address_id, is_buy, error, signature, ordinality)
+order by f.ordinality;
+"#,
+        )
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .bind(&venues)
+        .bind(&token_pairs)
+        .bind(&signers)
+        .bind(&is_buys)
+        .bind(&errors)
+        .bind(&signatures)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -3,4 +3,69 @@
 
 pub(crate) use swap::index_swap;
 
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction};
+use base::model::PublicKey;
+use common::repo::Tx;
+use solana::jupiter::model::Instruction;
+use solana::jupiter::parse::JupiterParser;
+use solana::jupiter::repo::{SlotSwap, SlotSwaps};
+use solana::parse::InstructionParser;
+
 mod swap;
+
+pub struct JupiterIndexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for JupiterIndexer {
+    type Parsed = SlotSwaps;
+
+    fn name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        let mut result = SlotSwaps {
+            slot: block.slot,
+            timestamp: block.timestamp,
+            swaps: vec![],
+        };
+
+        for transaction in transactions {
+            let Ok(instructions) = JupiterParser::parse_instructions(transaction) else {
+                continue;
+            };
+
+            for instruction in instructions {
+                match instruction {
+                    Instruction::Swap { swaps, signer } => {
+                        for swap in &swaps {
+                            result.swaps.push(SlotSwap {
+                                input_mint: swap.input_mint.clone(),
+                                input_amount: swap.input_amount.clone(),
+                                output_mint: swap.output_mint.clone(),
+                                output_amount: swap.output_amount.clone(),
+                                wallet: signer.clone(),
+                                signature: transaction.signature.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub(crate) use swap::index_swap;
+
+use crate::solana::block::meteora::parse::{parse_swaps, MeteoraProgram, QuotedSwap};
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Slot, Transaction};
+use base::model::PublicKey;
+use common::model::Timestamp;
+use common::repo::Tx;
+
+pub mod parse;
+pub mod repo;
+mod swap;
+
+pub struct MeteoraParsed {
+    pub program: MeteoraProgram,
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    /// swaps with the signature of their transaction
+    pub swaps: Vec<(QuotedSwap, String)>,
+}
+
+fn parse(
+    program: MeteoraProgram,
+    program_id: &PublicKey,
+    block: &Block,
+    transactions: &[&Transaction],
+) -> MeteoraParsed {
+    let mut result = MeteoraParsed {
+        program,
+        slot: block.slot,
+        timestamp: block.timestamp.0,
+        swaps: vec![],
+    };
+
+    for transaction in transactions {
+        for swap in parse_swaps(program, program_id, transaction) {
+            if let Some(swap) = swap.quoted() {
+                result.swaps.push((swap, transaction.signature.to_string()));
+            }
+        }
+    }
+
+    result
+}
+
+pub struct MeteoraDlmmIndexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for MeteoraDlmmIndexer {
+    type Parsed = MeteoraParsed;
+
+    fn name(&self) -> &'static str {
+        "meteora_dlmm"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        parse(MeteoraProgram::Dlmm, &self.program_id, block, transactions)
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
+
+pub struct MeteoraDammIndexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for MeteoraDammIndexer {
+    type Parsed = MeteoraParsed;
+
+    fn name(&self) -> &'static str {
+        "meteora_damm"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        parse(MeteoraProgram::Damm, &self.program_id, block, transactions)
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
+
+pub struct MeteoraDammV1Indexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for MeteoraDammV1Indexer {
+    type Parsed = MeteoraParsed;
+
+    fn name(&self) -> &'static str {
+        "meteora_damm_v1"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        parse(
+            MeteoraProgram::DammV1,
+            &self.program_id,
+            block,
+            transactions,
+        )
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
@@ -0,0 +1,473 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Transaction;
+use base::model::PublicKey;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Prefix of the instruction data anchor uses to emit an event through a self cpi
+const EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Prefix of the log line anchor emits an event with
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Discriminator of the dlmm and damm v1 swap events - sha256("event:Swap")[..8]
+const DLMM_SWAP: [u8; 8] = [0x51, 0x6c, 0xe3, 0xbe, 0xcd, 0xd0, 0x0a, 0xc4];
+
+/// Discriminator of the damm v1 swap instruction - sha256("global:swap")[..8]
+const DAMM_V1_SWAP_IX: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+
+/// Discriminator of the damm swap event - sha256("event:EvtSwap")[..8]
+const DAMM_SWAP: [u8; 8] = [0x1b, 0x3c, 0x15, 0xd5, 0x8a, 0xaa, 0xbb, 0x93];
+
+const WSOL: &str = "So11111111111111111111111111111111111111112";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeteoraProgram {
+    /// dynamic liquidity market maker - LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo
+    Dlmm,
+    /// dynamic amm v2 - cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG
+    Damm,
+    /// dynamic amm v1 - Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB
+    DammV1,
+}
+
+impl MeteoraProgram {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MeteoraProgram::Dlmm => "dlmm",
+            MeteoraProgram::Damm => "damm",
+            MeteoraProgram::DammV1 => "damm_v1",
+        }
+    }
+}
+
+/// Swap between the two tokens of a meteora pool, x is token a for damm pools
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeteoraSwap {
+    pub pool: PublicKey,
+    pub mint_x: PublicKey,
+    pub mint_y: PublicKey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// whether token x got swapped for token y
+    pub x_for_y: bool,
+    pub signer: PublicKey,
+}
+
+/// Swap oriented towards the traded token, quoted in sol
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuotedSwap {
+    pub pool: PublicKey,
+    pub base: PublicKey,
+    pub quote: PublicKey,
+    pub amount_base: u64,
+    pub amount_quote: u64,
+    pub is_buy: bool,
+    pub signer: PublicKey,
+}
+
+impl MeteoraSwap {
+    /// Pools which do not trade against wsol are not supported
+    pub fn quoted(self) -> Option<QuotedSwap> {
+        let wsol = PublicKey::from_str(WSOL).unwrap();
+
+        let (base, quote, base_is_x) = if self.mint_y == wsol {
+            (self.mint_x, self.mint_y, true)
+        } else if self.mint_x == wsol {
+            (self.mint_y, self.mint_x, false)
+        } else {
+            return None;
+        };
+
+        // selling the base token, if it was the token going into the pool
+        let sell = self.x_for_y == base_is_x;
+        let (amount_base, amount_quote) = if sell {
+            (self.amount_in, self.amount_out)
+        } else {
+            (self.amount_out, self.amount_in)
+        };
+
+        Some(QuotedSwap {
+            pool: self.pool,
+            base,
+            quote,
+            amount_base,
+            amount_quote,
+            is_buy: !sell,
+            signer: self.signer,
+        })
+    }
+}
+
+/// Parses the swap events the program emitted within the transaction. The mints are not part of
+/// the events, they are taken from the accounts of the instruction which emitted the event.
+pub fn parse_swaps(
+    program: MeteoraProgram,
+    program_id: &PublicKey,
+    transaction: &Transaction,
+) -> Vec<MeteoraSwap> {
+    if program == MeteoraProgram::DammV1 {
+        return parse_damm_v1_swaps(program_id, transaction);
+    }
+
+    let mut result = vec![];
+    let mut accounts: &[PublicKey] = &[];
+
+    for instruction in &transaction.instructions {
+        if &instruction.program_id != program_id {
+            continue;
+        }
+
+        let Some(event) = instruction.data.strip_prefix(&EVENT_IX_TAG) else {
+            accounts = &instruction.accounts;
+            continue;
+        };
+
+        let swap = match program {
+            MeteoraProgram::Dlmm => decode_dlmm_swap(event, accounts),
+            MeteoraProgram::Damm => decode_damm_swap(event, accounts),
+            MeteoraProgram::DammV1 => None,
+        };
+
+        if let Some(swap) = swap {
+            result.push(swap);
+        }
+    }
+
+    result
+}
+
+/// swap(2) accounts: lb_pair, bitmap_extension, reserve_x, reserve_y, user_token_in,
+/// user_token_out, token_x_mint, token_y_mint, ..
+fn decode_dlmm_swap(event: &[u8], accounts: &[PublicKey]) -> Option<MeteoraSwap> {
+    let mut reader = Reader::new(event.strip_prefix(&DLMM_SWAP)?);
+
+    let lb_pair = reader.public_key()?;
+    let from = reader.public_key()?;
+    let _start_bin_id = reader.i32()?;
+    let _end_bin_id = reader.i32()?;
+    let amount_in = reader.u64()?;
+    let amount_out = reader.u64()?;
+    let swap_for_y = reader.bool()?;
+
+    Some(MeteoraSwap {
+        pool: lb_pair,
+        mint_x: accounts.get(6)?.clone(),
+        mint_y: accounts.get(7)?.clone(),
+        amount_in,
+        amount_out,
+        x_for_y: swap_for_y,
+        signer: from,
+    })
+}
+
+/// swap accounts: pool_authority, pool, input_token_account, output_token_account, token_a_vault,
+/// token_b_vault, token_a_mint, token_b_mint, payer, ..
+fn decode_damm_swap(event: &[u8], accounts: &[PublicKey]) -> Option<MeteoraSwap> {
+    let mut reader = Reader::new(event.strip_prefix(&DAMM_SWAP)?);
+
+    let pool = reader.public_key()?;
+    let trade_direction = reader.u8()?;
+    let _has_referral = reader.bool()?;
+    let _amount_in = reader.u64()?;
+    let _minimum_amount_out = reader.u64()?;
+    let output_amount = reader.u64()?;
+    let _next_sqrt_price = reader.u128()?;
+    let _lp_fee = reader.u64()?;
+    let _protocol_fee = reader.u64()?;
+    let _partner_fee = reader.u64()?;
+    let _referral_fee = reader.u64()?;
+    let actual_amount_in = reader.u64()?;
+
+    Some(MeteoraSwap {
+        pool,
+        mint_x: accounts.get(6)?.clone(),
+        mint_y: accounts.get(7)?.clone(),
+        amount_in: actual_amount_in,
+        amount_out: output_amount,
+        // 0 - a to b, 1 - b to a
+        x_for_y: trade_direction == 0,
+        signer: accounts.get(8)?.clone(),
+    })
+}
+
+/// Damm v1 logs its swap events instead of emitting them through a self cpi, they get matched with
+/// the swap instructions of the program in order. Neither carries the mints, they are taken from
+/// the token balances of the two vaults, the vault of the token going in is the one which grew.
+///
+/// swap accounts: pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault,
+/// b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, protocol_token_fee,
+/// user, ..
+fn parse_damm_v1_swaps(program_id: &PublicKey, transaction: &Transaction) -> Vec<MeteoraSwap> {
+    let instructions = transaction
+        .instructions
+        .iter()
+        .filter(|i| &i.program_id == program_id && i.data.starts_with(&DAMM_V1_SWAP_IX));
+
+    let events = program_data(program_id, &transaction.log_messages)
+        .into_iter()
+        .filter_map(|data| decode_damm_v1_swap(&data));
+
+    instructions
+        .zip(events)
+        .filter_map(|(instruction, (amount_in, amount_out))| {
+            let accounts = &instruction.accounts;
+            let vault = |vault: &PublicKey| {
+                transaction
+                    .balance
+                    .token
+                    .iter()
+                    .find(|t| &t.address == vault)
+            };
+            let a = vault(accounts.get(3)?)?;
+            let b = vault(accounts.get(4)?)?;
+
+            Some(MeteoraSwap {
+                pool: accounts.first()?.clone(),
+                mint_x: PublicKey::from_str(&a.mint.to_string()).ok()?,
+                mint_y: PublicKey::from_str(&b.mint.to_string()).ok()?,
+                amount_in,
+                amount_out,
+                x_for_y: a.post > a.pre,
+                signer: accounts.get(12)?.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Returns in_amount and out_amount of the damm v1 swap event
+fn decode_damm_v1_swap(data: &[u8]) -> Option<(u64, u64)> {
+    let mut reader = Reader::new(data.strip_prefix(&DLMM_SWAP)?);
+    Some((reader.u64()?, reader.u64()?))
+}
+
+/// Data logged by the program itself, not by the programs it invoked
+fn program_data(program_id: &PublicKey, log_messages: &[String]) -> Vec<Vec<u8>> {
+    let program_id = program_id.to_string();
+    let mut invoked: Vec<bool> = vec![];
+    let mut result = vec![];
+
+    for line in log_messages {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if invoked.last() == Some(&true) {
+                if let Ok(data) = STANDARD.decode(data) {
+                    result.push(data);
+                }
+            }
+            continue;
+        }
+
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["Program", program, "invoke", _] => invoked.push(program == program_id),
+            ["Program", _, "success"] => {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Reads borsh encoded event fields
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (value, rest) = self.data.split_first_chunk::<N>()?;
+        self.data = rest;
+        Some(*value)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|v| v[0])
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        self.u8().map(|v| v != 0)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.take().map(i32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn u128(&mut self) -> Option<u128> {
+        self.take().map(u128::from_le_bytes)
+    }
+
+    fn public_key(&mut self) -> Option<PublicKey> {
+        let key = Pubkey::new_from_array(self.take()?);
+        PublicKey::from_str(&key.to_string()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(value: &str) -> PublicKey {
+        PublicKey::from_str(value).unwrap()
+    }
+
+    fn bytes(value: &str) -> [u8; 32] {
+        Pubkey::from_str(value).unwrap().to_bytes()
+    }
+
+    const POOL: &str = "5rCf1DM8LjKTw4YqhnoLcngyZYeNnQqztScTogYHAS6";
+    const USER: &str = "DfZ5ZZ8ST9nDJ5rPmATMkZ8mmGvBFoREBBPVvB8MHKHG";
+    const MINT: &str = "9kvTPjemayUL7XKPyjhqavbcLtY5VP2ha1G5vPuppump";
+
+    fn accounts(mint_x: &str, mint_y: &str) -> Vec<PublicKey> {
+        let mut result = vec![key(POOL); 6];
+        result.push(key(mint_x));
+        result.push(key(mint_y));
+        result.push(key(USER));
+        result
+    }
+
+    #[test]
+    fn test_decode_dlmm_swap() {
+        let mut event = DLMM_SWAP.to_vec();
+        event.extend(bytes(POOL));
+        event.extend(bytes(USER));
+        event.extend((-12i32).to_le_bytes());
+        event.extend((-11i32).to_le_bytes());
+        event.extend(2_000_000_000u64.to_le_bytes());
+        event.extend(1_500_000u64.to_le_bytes());
+        event.push(0);
+        event.extend(5_000u64.to_le_bytes());
+        event.extend(1_000u64.to_le_bytes());
+        event.extend(25u128.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+
+        let swap = decode_dlmm_swap(&event, &accounts(MINT, WSOL)).unwrap();
+        assert_eq!(
+            swap,
+            MeteoraSwap {
+                pool: key(POOL),
+                mint_x: key(MINT),
+                mint_y: key(WSOL),
+                amount_in: 2_000_000_000,
+                amount_out: 1_500_000,
+                x_for_y: false,
+                signer: key(USER),
+            }
+        );
+
+        // sol went in, the token came out
+        let quoted = swap.quoted().unwrap();
+        assert!(quoted.is_buy);
+        assert_eq!(quoted.base, key(MINT));
+        assert_eq!(quoted.amount_base, 1_500_000);
+        assert_eq!(quoted.amount_quote, 2_000_000_000);
+    }
+
+    #[test]
+    fn test_decode_damm_swap() {
+        let mut event = DAMM_SWAP.to_vec();
+        event.extend(bytes(POOL));
+        event.push(1);
+        event.push(0);
+        event.extend(3_000_000u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(7_000_000u64.to_le_bytes());
+        event.extend(123u128.to_le_bytes());
+        event.extend(10u64.to_le_bytes());
+        event.extend(2u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(3_000_000u64.to_le_bytes());
+        event.extend(1_745_000_000u64.to_le_bytes());
+
+        // token a is sol, swapped b to a - the token got sold
+        let quoted = decode_damm_swap(&event, &accounts(WSOL, MINT))
+            .unwrap()
+            .quoted()
+            .unwrap();
+        assert_eq!(
+            quoted,
+            QuotedSwap {
+                pool: key(POOL),
+                base: key(MINT),
+                quote: key(WSOL),
+                amount_base: 3_000_000,
+                amount_quote: 7_000_000,
+                is_buy: false,
+                signer: key(USER),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_damm_v1_swap() {
+        let mut event = DLMM_SWAP.to_vec();
+        event.extend(2_000_000u64.to_le_bytes());
+        event.extend(5_000_000u64.to_le_bytes());
+        event.extend(5_000u64.to_le_bytes());
+        event.extend(1_000u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+
+        assert_eq!(decode_damm_v1_swap(&event), Some((2_000_000, 5_000_000)));
+        assert_eq!(decode_damm_v1_swap(&DAMM_SWAP), None);
+    }
+
+    #[test]
+    fn test_program_data() {
+        let program_id = key("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+        let logs: Vec<String> = [
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program data: AQ==",
+            "Program Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB invoke [2]",
+            "Program 24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi invoke [3]",
+            "Program data: Ag==",
+            "Program 24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi success",
+            "Program log: swap success",
+            "Program data: Aw==",
+            "Program Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB consumed 60000 of 200000 compute units",
+            "Program Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB success",
+            "Program data: BA==",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        // only what damm v1 logged itself, not its vault program nor the jupiter route
+        assert_eq!(program_data(&program_id, &logs), vec![vec![3u8]]);
+    }
+
+    #[test]
+    fn test_truncated_event() {
+        let mut event = DLMM_SWAP.to_vec();
+        event.extend(bytes(POOL));
+        assert_eq!(decode_dlmm_swap(&event, &accounts(MINT, WSOL)), None);
+    }
+
+    #[test]
+    fn test_not_quoted_in_sol() {
+        let swap = MeteoraSwap {
+            pool: key(POOL),
+            mint_x: key(MINT),
+            mint_y: key(USER),
+            amount_in: 1,
+            amount_out: 1,
+            x_for_y: true,
+            signer: key(USER),
+        };
+        assert_eq!(swap.quoted(), None);
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, TokenPairId};
+use common::model::Timestamp;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+pub struct SwapToInsert {
+    pub pool: AddressId,
+    pub token_pair: TokenPairId,
+    /// raw amount, without decimals applied
+    pub amount_base: u64,
+    /// raw amount, without decimals applied
+    pub amount_quote: u64,
+    pub is_buy: bool,
+    pub signer: AddressId,
+    pub signature: String,
+}
+
+pub struct SwapsToInsert {
+    pub program: &'static str,
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    pub swaps: Vec<SwapToInsert>,
+}
+
+pub struct SwapRepo {}
+
+impl SwapRepo {
+    /// Inserts the swaps, together with their pools if not known yet.
+    /// Returns the token pairs which got swapped.
+    pub async fn insert_swaps(
+        mut executor: impl AsSqlExecutor,
+        to_insert: SwapsToInsert,
+    ) -> RepoResult<Vec<TokenPairId>> {
+        if to_insert.swaps.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut pools = Vec::with_capacity(to_insert.swaps.len());
+        let mut token_pairs = Vec::with_capacity(to_insert.swaps.len());
+        let mut amount_bases = Vec::with_capacity(to_insert.swaps.len());
+        let mut amount_quotes = Vec::with_capacity(to_insert.swaps.len());
+        let mut is_buys = Vec::with_capacity(to_insert.swaps.len());
+        let mut signers = Vec::with_capacity(to_insert.swaps.len());
+        let mut signatures = Vec::with_capacity(to_insert.swaps.len());
+
+        for swap in to_insert.swaps {
+            pools.push(swap.pool.0);
+            token_pairs.push(swap.token_pair.0);
+            amount_bases.push(swap.amount_base.to_string());
+            amount_quotes.push(swap.amount_quote.to_string());
+            is_buys.push(swap.is_buy);
+            signers.push(swap.signer.0);
+            signatures.push(swap.signature);
+        }
+
+        query(
+            r#"
+insert into meteora.pool (id, program, token_pair_id, slot, timestamp)
+select distinct on (p.id) p.id, $3, p.token_pair_id, $4, $5
+from unnest($1::int8[], $2::int8[]) as p(id, token_pair_id)
+on conflict (id) do nothing;
+"#,
+        )
+        .bind(&pools)
+        .bind(&token_pairs)
+        .bind(to_insert.program)
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(query(
+            r#"
+insert into meteora.swap (slot, timestamp, pool_id, token_pair_id, address_id, amount_base, amount_quote, price, is_buy, signature)
+select
+    $1,
+    $2,
+    s.pool_id,
+    s.token_pair_id,
+    s.address_id,
+    s.amount_base::numeric / power(10, b.decimals),
+    s.amount_quote::numeric / power(10, q.decimals),
+    case
+        when s.amount_base::numeric = 0 then 0
+        else (s.amount_quote::numeric / power(10, q.decimals)) / (s.amount_base::numeric / power(10, b.decimals))
+    end,
+    s.is_buy,
+    s.signature
+from unnest($3::int8[], $4::int8[], $5::int8[], $6::text[], $7::text[], $8::bool[], $9::text[])
+    with ordinality as s(pool_id, address_id, token_pair_id, amount_base, amount_quote, is_buy, signature, ordinality)
+join solana.token_pair tp on tp.id = s.token_pair_id
+join solana.token b on b.id = tp.base_id
+join solana.token q on q.id = tp.quote_id
+order by s.ordinality
+returning token_pair_id;
+"#,
+        )
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .bind(&pools)
+        .bind(&signers)
+        .bind(&token_pairs)
+        .bind(&amount_bases)
+        .bind(&amount_quotes)
+        .bind(&is_buys)
+        .bind(&signatures)
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| r.get::<TokenPairId, _>("token_pair_id"))
+        .collect())
+    }
+}
+
+pub struct CurrentRepo {}
+
+impl CurrentRepo {
+    /// Moves the current price of every token pair swapped within the slot to its last swap
+    pub async fn upsert(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into meteora.current (id, slot, price, updated_at)
+select distinct on (token_pair_id) token_pair_id, slot, price, now()
+from meteora.swap
+where slot = $1
+order by token_pair_id, id desc
+on conflict (id) do update set
+    slot = excluded.slot,
+    price = excluded.price,
+    updated_at = now()
+where meteora.current.slot <= excluded.slot;
+"#,
+        )
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::meteora::repo::{CurrentRepo, SwapRepo, SwapToInsert, SwapsToInsert};
+use crate::solana::block::meteora::MeteoraParsed;
+use crate::solana::block::state::State;
+use base::model::{AddressId, Mint, PublicKey, TokenPairId};
+use common::repo::Tx;
+use log::debug;
+use std::collections::HashMap;
+
+pub(crate) async fn index_swap<'a>(tx: &mut Tx<'a>, state: State, parsed: MeteoraParsed) {
+    if parsed.swaps.is_empty() {
+        return;
+    }
+
+    let start = std::time::Instant::now();
+
+    let mut keys = vec![];
+    let mut pairs = vec![];
+    for (swap, _) in &parsed.swaps {
+        keys.push(swap.pool.clone());
+        keys.push(swap.signer.clone());
+        pairs.push(pair(&swap.base, &swap.quote));
+    }
+    keys.sort();
+    keys.dedup();
+    pairs.sort();
+    pairs.dedup();
+
+    let addresses: HashMap<PublicKey, AddressId> = state
+        .address_repo
+        .list_or_populate(&mut *tx, keys)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.address, a.id))
+        .collect();
+
+    let token_pairs: HashMap<(Mint, Mint), TokenPairId> = state
+        .token_pair_repo
+        .list_or_populate(&mut *tx, pairs)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tp| ((tp.base.mint, tp.quote.mint), tp.id))
+        .collect();
+
+    let swaps = parsed
+        .swaps
+        .into_iter()
+        .map(|(swap, signature)| SwapToInsert {
+            pool: addresses[&swap.pool],
+            token_pair: token_pairs[&pair(&swap.base, &swap.quote)],
+            amount_base: swap.amount_base,
+            amount_quote: swap.amount_quote,
+            is_buy: swap.is_buy,
+            signer: addresses[&swap.signer],
+            signature,
+        })
+        .collect();
+
+    let inserted = SwapRepo::insert_swaps(
+        &mut *tx,
+        SwapsToInsert {
+            program: parsed.program.name(),
+            slot: parsed.slot,
+            timestamp: parsed.timestamp,
+            swaps,
+        },
+    )
+    .await
+    .unwrap();
+    debug!(
+        "{} swap insert {} took: {:?} ms",
+        parsed.program.name(),
+        inserted.len(),
+        start.elapsed().as_millis()
+    );
+
+    CurrentRepo::upsert(&mut *tx, parsed.slot).await.unwrap();
+}
+
+fn pair(base: &PublicKey, quote: &PublicKey) -> (Mint, Mint) {
+    (Mint::from(base.to_string()), Mint::from(quote.to_string()))
+}
@@ -0,0 +1,65 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub(crate) use swap::index_swap;
+
+use crate::solana::block::orca::parse::{parse_swaps, QuotedSwap};
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Slot, Transaction};
+use base::model::PublicKey;
+use common::model::Timestamp;
+use common::repo::Tx;
+
+pub mod parse;
+pub mod repo;
+mod swap;
+pub mod tick;
+
+pub struct OrcaParsed {
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    /// swaps with the signature of their transaction
+    pub swaps: Vec<(QuotedSwap, String)>,
+}
+
+/// Orca whirlpools - whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc
+pub struct OrcaIndexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for OrcaIndexer {
+    type Parsed = OrcaParsed;
+
+    fn name(&self) -> &'static str {
+        "orca"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        let mut result = OrcaParsed {
+            slot: block.slot,
+            timestamp: block.timestamp.0,
+            swaps: vec![],
+        };
+
+        for transaction in transactions {
+            for swap in parse_swaps(transaction) {
+                if let Some(swap) = swap.quoted() {
+                    result.swaps.push((swap, transaction.signature.to_string()));
+                }
+            }
+        }
+
+        result
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::orca::tick::tick_index;
+use base::model::solana::Transaction;
+use base::model::{Mint, PublicKey};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Prefix of the log line anchor emits an event with
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Discriminator of the whirlpool traded event - sha256("event:Traded")[..8]
+const TRADED: [u8; 8] = [0xe1, 0xca, 0x49, 0xaf, 0x93, 0x2b, 0xa0, 0x96];
+
+/// Swap on a whirlpool, emitted once per pool, a two hop swap emits two of them
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhirlpoolSwap {
+    pub whirlpool: PublicKey,
+    pub mint_a: Mint,
+    pub mint_b: Mint,
+    pub a_to_b: bool,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub signer: PublicKey,
+}
+
+/// Swap oriented towards the traded token, quoted in sol
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuotedSwap {
+    pub pool: PublicKey,
+    pub base: Mint,
+    pub quote: Mint,
+    /// the base token is token b of the pool, the sqrt price is the one of a in b
+    pub inverted: bool,
+    pub amount_base: u64,
+    pub amount_quote: u64,
+    pub is_buy: bool,
+    pub sqrt_price: u128,
+    pub tick_before: i32,
+    pub tick_after: i32,
+    pub signer: PublicKey,
+}
+
+impl WhirlpoolSwap {
+    /// Pools which do not trade against wsol are not supported
+    pub fn quoted(self) -> Option<QuotedSwap> {
+        let wsol = Mint::wsol();
+
+        let (base, quote, inverted) = if self.mint_b == wsol {
+            (self.mint_a, self.mint_b, false)
+        } else if self.mint_a == wsol {
+            (self.mint_b, self.mint_a, true)
+        } else {
+            return None;
+        };
+
+        let (amount_a, amount_b) = if self.a_to_b {
+            (self.input_amount, self.output_amount)
+        } else {
+            (self.output_amount, self.input_amount)
+        };
+
+        let (amount_base, amount_quote, is_buy) = if inverted {
+            (amount_b, amount_a, self.a_to_b)
+        } else {
+            (amount_a, amount_b, !self.a_to_b)
+        };
+
+        Some(QuotedSwap {
+            pool: self.whirlpool,
+            base,
+            quote,
+            inverted,
+            amount_base,
+            amount_quote,
+            is_buy,
+            sqrt_price: self.post_sqrt_price,
+            tick_before: tick_index(self.pre_sqrt_price),
+            tick_after: tick_index(self.post_sqrt_price),
+            signer: self.signer,
+        })
+    }
+}
+
+/// Parses the traded events of the transaction. A swap might cross several tick arrays, the
+/// event reports the sqrt price before and after the whole swap regardless.
+/// The event does not carry the mints, they are taken from the token balances of the pool vaults,
+/// whirlpools require the key of mint a to be smaller than the one of mint b.
+pub fn parse_swaps(transaction: &Transaction) -> Vec<WhirlpoolSwap> {
+    let Some(signer) = transaction.keys.first() else {
+        return vec![];
+    };
+
+    transaction
+        .log_messages
+        .iter()
+        .filter_map(|line| line.strip_prefix(PROGRAM_DATA))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|data| decode_traded(&data))
+        .filter_map(|traded| {
+            let mut mints: Vec<Mint> = transaction
+                .balance
+                .token
+                .iter()
+                .filter(|t| t.address == traded.whirlpool)
+                .map(|t| t.mint.clone())
+                .collect();
+            mints.sort_by_key(|m| Pubkey::from_str(&m.to_string()).ok());
+            mints.dedup();
+
+            let [mint_a, mint_b] = <[Mint; 2]>::try_from(mints).ok()?;
+
+            Some(WhirlpoolSwap {
+                whirlpool: traded.whirlpool,
+                mint_a,
+                mint_b,
+                a_to_b: traded.a_to_b,
+                pre_sqrt_price: traded.pre_sqrt_price,
+                post_sqrt_price: traded.post_sqrt_price,
+                input_amount: traded.input_amount,
+                output_amount: traded.output_amount,
+                signer: signer.clone(),
+            })
+        })
+        .collect()
+}
+
+struct Traded {
+    whirlpool: PublicKey,
+    a_to_b: bool,
+    pre_sqrt_price: u128,
+    post_sqrt_price: u128,
+    input_amount: u64,
+    output_amount: u64,
+}
+
+fn decode_traded(data: &[u8]) -> Option<Traded> {
+    let data = data.strip_prefix(&TRADED)?;
+    let (whirlpool, data) = data.split_first_chunk::<32>()?;
+    let (a_to_b, data) = data.split_first()?;
+    let (pre_sqrt_price, data) = data.split_first_chunk::<16>()?;
+    let (post_sqrt_price, data) = data.split_first_chunk::<16>()?;
+    let (input_amount, data) = data.split_first_chunk::<8>()?;
+    let (output_amount, _) = data.split_first_chunk::<8>()?;
+
+    Some(Traded {
+        whirlpool: PublicKey::from_str(&Pubkey::new_from_array(*whirlpool).to_string()).ok()?,
+        a_to_b: *a_to_b != 0,
+        pre_sqrt_price: u128::from_le_bytes(*pre_sqrt_price),
+        post_sqrt_price: u128::from_le_bytes(*post_sqrt_price),
+        input_amount: u64::from_le_bytes(*input_amount),
+        output_amount: u64::from_le_bytes(*output_amount),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL: &str = "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE";
+    const USER: &str = "DfZ5ZZ8ST9nDJ5rPmATMkZ8mmGvBFoREBBPVvB8MHKHG";
+
+    fn swap(a_to_b: bool, mint_a: Mint, mint_b: Mint) -> WhirlpoolSwap {
+        WhirlpoolSwap {
+            whirlpool: PublicKey::from_str(POOL).unwrap(),
+            mint_a,
+            mint_b,
+            a_to_b,
+            pre_sqrt_price: 1 << 64,
+            post_sqrt_price: 2 << 64,
+            input_amount: 100,
+            output_amount: 40,
+            signer: PublicKey::from_str(USER).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_decode_traded() {
+        let mut data = TRADED.to_vec();
+        data.extend(Pubkey::from_str(POOL).unwrap().to_bytes());
+        data.push(1);
+        data.extend((1u128 << 64).to_le_bytes());
+        data.extend((3u128 << 62).to_le_bytes());
+        data.extend(1_000u64.to_le_bytes());
+        data.extend(900u64.to_le_bytes());
+        data.extend([0u8; 32]);
+
+        let traded = decode_traded(&data).unwrap();
+        assert_eq!(traded.whirlpool, PublicKey::from_str(POOL).unwrap());
+        assert!(traded.a_to_b);
+        assert_eq!(traded.pre_sqrt_price, 1 << 64);
+        assert_eq!(traded.post_sqrt_price, 3 << 62);
+        assert_eq!(traded.input_amount, 1_000);
+        assert_eq!(traded.output_amount, 900);
+
+        assert!(decode_traded(&data[..40]).is_none());
+        assert!(decode_traded(&data[8..]).is_none());
+    }
+
+    #[test]
+    fn test_quoted() {
+        let token = Mint::usdt();
+
+        // token is a, sold for sol
+        let quoted = swap(true, token.clone(), Mint::wsol()).quoted().unwrap();
+        assert!(!quoted.inverted);
+        assert!(!quoted.is_buy);
+        assert_eq!(quoted.base, token);
+        assert_eq!(quoted.amount_base, 100);
+        assert_eq!(quoted.amount_quote, 40);
+        assert_eq!(quoted.tick_before, 0);
+        assert_eq!(quoted.tick_after, 13863);
+
+        // token is b, bought with sol
+        let quoted = swap(true, Mint::wsol(), token.clone()).quoted().unwrap();
+        assert!(quoted.inverted);
+        assert!(quoted.is_buy);
+        assert_eq!(quoted.base, token);
+        assert_eq!(quoted.amount_base, 40);
+        assert_eq!(quoted.amount_quote, 100);
+
+        assert!(swap(true, token.clone(), token).quoted().is_none());
+    }
+}
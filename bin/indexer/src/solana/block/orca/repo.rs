@@ -0,0 +1,211 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, TokenPairId};
+use common::model::Timestamp;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+pub struct SwapToInsert {
+    pub pool: AddressId,
+    pub token_pair: TokenPairId,
+    /// the base token is token b of the pool
+    pub inverted: bool,
+    /// raw amount, without decimals applied
+    pub amount_base: u64,
+    /// raw amount, without decimals applied
+    pub amount_quote: u64,
+    pub is_buy: bool,
+    /// Q64.64 sqrt price of token a in token b after the swap
+    pub sqrt_price: u128,
+    pub tick_before: i32,
+    pub tick_after: i32,
+    pub signer: AddressId,
+    pub signature: String,
+}
+
+pub struct SwapsToInsert {
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    pub swaps: Vec<SwapToInsert>,
+}
+
+pub struct SwapRepo {}
+
+impl SwapRepo {
+    /// Inserts the swaps, together with their pools if not known yet.
+    /// The price of a swap is the one of the pool after the swap, derived from its sqrt price.
+    /// Returns the token pairs which got swapped.
+    pub async fn insert_swaps(
+        mut executor: impl AsSqlExecutor,
+        to_insert: SwapsToInsert,
+    ) -> RepoResult<Vec<TokenPairId>> {
+        if to_insert.swaps.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut pools = Vec::with_capacity(to_insert.swaps.len());
+        let mut token_pairs = Vec::with_capacity(to_insert.swaps.len());
+        let mut inverteds = Vec::with_capacity(to_insert.swaps.len());
+        let mut amount_bases = Vec::with_capacity(to_insert.swaps.len());
+        let mut amount_quotes = Vec::with_capacity(to_insert.swaps.len());
+        let mut is_buys = Vec::with_capacity(to_insert.swaps.len());
+        let mut sqrt_prices = Vec::with_capacity(to_insert.swaps.len());
+        let mut tick_befores = Vec::with_capacity(to_insert.swaps.len());
+        let mut tick_afters = Vec::with_capacity(to_insert.swaps.len());
+        let mut signers = Vec::with_capacity(to_insert.swaps.len());
+        let mut signatures = Vec::with_capacity(to_insert.swaps.len());
+
+        for swap in to_insert.swaps {
+            pools.push(swap.pool.0);
+            token_pairs.push(swap.token_pair.0);
+            inverteds.push(swap.inverted);
+            amount_bases.push(swap.amount_base.to_string());
+            amount_quotes.push(swap.amount_quote.to_string());
+            is_buys.push(swap.is_buy);
+            sqrt_prices.push(swap.sqrt_price.to_string());
+            tick_befores.push(swap.tick_before);
+            tick_afters.push(swap.tick_after);
+            signers.push(swap.signer.0);
+            signatures.push(swap.signature);
+        }
+
+        query(
+            r#"
+insert into orca.pool (id, token_pair_id, inverted, slot, timestamp)
+select distinct on (p.id) p.id, p.token_pair_id, p.inverted, $4, $5
+from unnest($1::int8[], $2::int8[], $3::bool[]) as p(id, token_pair_id, inverted)
+on conflict (id) do nothing;
+"#,
+        )
+        .bind(&pools)
+        .bind(&token_pairs)
+        .bind(&inverteds)
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .execute(executor.as_executor())
+        .await?;
+
+        // raw price of a in b is (sqrt_price / 2^64)^2
+        Ok(query(
+            r#"
+with s as (
+    select
+        s.*,
+        s.sqrt_price::numeric * s.sqrt_price::numeric / 340282366920938463463374607431768211456 as raw_price
+    from unnest($3::int8[], $4::int8[], $5::int8[], $6::text[], $7::text[], $8::bool[], $9::text[], $10::int4[], $11::int4[], $12::text[])
+        with ordinality as s(pool_id, address_id, token_pair_id, amount_base, amount_quote, is_buy, sqrt_price, tick_before, tick_after, signature, ordinality)
+)
+insert into orca.swap (slot, timestamp, pool_id, token_pair_id, address_id, amount_base, amount_quote, price, sqrt_price, tick_before, tick_after, is_buy, signature)
+select
+    $1,
+    $2,
+    s.pool_id,
+    s.token_pair_id,
+    s.address_id,
+    s.amount_base::numeric / power(10, b.decimals),
+    s.amount_quote::numeric / power(10, q.decimals),
+    case
+        when s.raw_price = 0 then 0
+        when p.inverted then power(10::numeric, b.decimals - q.decimals) / s.raw_price
+        else s.raw_price * power(10::numeric, b.decimals - q.decimals)
+    end,
+    s.sqrt_price::numeric,
+    s.tick_before,
+    s.tick_after,
+    s.is_buy,
+    s.signature
+from s
+join orca.pool p on p.id = s.pool_id
+join solana.token_pair tp on tp.id = s.token_pair_id
+join solana.token b on b.id = tp.base_id
+join solana.token q on q.id = tp.quote_id
+order by s.ordinality
+returning token_pair_id;
+"#,
+        )
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .bind(&pools)
+        .bind(&signers)
+        .bind(&token_pairs)
+        .bind(&amount_bases)
+        .bind(&amount_quotes)
+        .bind(&is_buys)
+        .bind(&sqrt_prices)
+        .bind(&tick_befores)
+        .bind(&tick_afters)
+        .bind(&signatures)
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| r.get::<TokenPairId, _>("token_pair_id"))
+        .collect())
+    }
+}
+
+pub struct PoolRepo {}
+
+impl PoolRepo {
+    /// Moves the state of every pool swapped within the slot to the one after its last swap
+    pub async fn update_state(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update orca.pool p set
+    sqrt_price = s.sqrt_price,
+    tick_current_index = s.tick_after,
+    price = s.price,
+    updated_slot = s.slot,
+    updated_at = now()
+from (
+    select distinct on (pool_id) pool_id, slot, sqrt_price, tick_after, price
+    from orca.swap
+    where slot = $1
+    order by pool_id, id desc
+) s
+where p.id = s.pool_id
+  and coalesce(p.updated_slot, 0) <= s.slot;
+"#,
+        )
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub struct CurrentRepo {}
+
+impl CurrentRepo {
+    /// Moves the current price of every token pair swapped within the slot to its last swap
+    pub async fn upsert(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into orca.current (id, slot, price, updated_at)
+select distinct on (token_pair_id) token_pair_id, slot, price, now()
+from orca.swap
+where slot = $1
+order by token_pair_id, id desc
+on conflict (id) do update set
+    slot = excluded.slot,
+    price = excluded.price,
+    updated_at = now()
+where orca.current.slot <= excluded.slot;
+"#,
+        )
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
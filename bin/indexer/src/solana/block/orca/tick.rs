@@ -0,0 +1,177 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+//! Tick math of whirlpools in integer arithmetic, the price of a tick i is 1.0001^i
+
+pub const MIN_TICK: i32 = -443636;
+pub const MAX_TICK: i32 = 443636;
+
+/// sqrt(1.0001)^(2^k) as Q32.96
+const POSITIVE: [u128; 19] = [
+    79232123823359799118286999567,
+    79236085330515764027303304731,
+    79244008939048815603706035061,
+    79259858533276714757314932305,
+    79291567232598584799939703904,
+    79355022692464371645785046466,
+    79482085999252804386437311141,
+    79736823300114093921829183326,
+    80248749790819932309965073892,
+    81282483887344747381513967011,
+    83390072131320151908154831281,
+    87770609709833776024991924138,
+    97234110755111693312479820773,
+    119332217159966728226237229890,
+    179736315981702064433883588727,
+    407748233172238350107850275304,
+    2098478828474011932436660412517,
+    55581415166113811149459800483533,
+    38992368544603139932233054999993551,
+];
+
+/// 1 / sqrt(1.0001)^(2^k) as Q64.64
+const NEGATIVE: [u128; 19] = [
+    18445821805675392311,
+    18444899583751176498,
+    18443055278223354162,
+    18439367220385604838,
+    18431993317065449817,
+    18417254355718160513,
+    18387811781193591352,
+    18329067761203520168,
+    18212142134806087854,
+    17980523815641551639,
+    17526086738831147013,
+    16651378430235024244,
+    15030750278693429944,
+    12247334978882834399,
+    8131365268884726200,
+    3584323654723342297,
+    696457651847595233,
+    26294789957452057,
+    37481735321082,
+];
+
+/// 2 / log2(1.0001) as Q64.64, turns the log2 of a sqrt price into a tick
+const TICKS_PER_LOG2: u128 = 255738958999603826347140;
+
+/// Tick of a Q64.64 sqrt price, the highest tick whose sqrt price is not above it
+pub fn tick_index(sqrt_price: u128) -> i32 {
+    if sqrt_price == 0 {
+        return MIN_TICK;
+    }
+
+    // the estimate is off by one at most, next to the sqrt price of a tick
+    let estimate = estimate_tick(sqrt_price).clamp(MIN_TICK, MAX_TICK);
+    if estimate < MAX_TICK && sqrt_price_at_tick(estimate + 1) <= sqrt_price {
+        estimate + 1
+    } else if estimate > MIN_TICK && sqrt_price_at_tick(estimate) > sqrt_price {
+        estimate - 1
+    } else {
+        estimate
+    }
+}
+
+/// Q64.64 sqrt price of a tick within [MIN_TICK, MAX_TICK]
+pub fn sqrt_price_at_tick(tick: i32) -> u128 {
+    let abs = tick.unsigned_abs();
+    if tick >= 0 {
+        let mut ratio: u128 = 1 << 96;
+        for (bit, factor) in POSITIVE.iter().enumerate() {
+            if abs & (1 << bit) != 0 {
+                ratio = mul_shift_96(ratio, *factor);
+            }
+        }
+        ratio >> 32
+    } else {
+        let mut ratio: u128 = 1 << 64;
+        for (bit, factor) in NEGATIVE.iter().enumerate() {
+            if abs & (1 << bit) != 0 {
+                ratio = (ratio * factor) >> 64;
+            }
+        }
+        ratio
+    }
+}
+
+/// floor(log_sqrt(1.0001)(sqrt_price)), the fractional bits of log2 are taken by repeated squaring
+fn estimate_tick(sqrt_price: u128) -> i32 {
+    let msb = 127 - sqrt_price.leading_zeros();
+
+    // normalized into [1, 2) as Q1.63
+    let mut r = if msb >= 63 {
+        sqrt_price >> (msb - 63)
+    } else {
+        sqrt_price << (63 - msb)
+    };
+
+    let mut log2: i128 = (msb as i128 - 64) << 64;
+    let mut bit: i128 = 1 << 63;
+    while bit > 0 {
+        r *= r;
+        let above_two = r >> 127;
+        r >>= 63 + above_two;
+        if above_two == 1 {
+            log2 += bit;
+        }
+        bit >>= 1;
+    }
+
+    let ticks = mul_shift_64(log2.unsigned_abs(), TICKS_PER_LOG2) as i128;
+    let ticks = if log2 < 0 { -ticks } else { ticks };
+    (ticks >> 64) as i32
+}
+
+/// (a * b) >> 64, for a below 2^72 and b below 2^80
+fn mul_shift_64(a: u128, b: u128) -> u128 {
+    let (a_hi, a_lo) = (a >> 64, a as u64 as u128);
+    let (b_hi, b_lo) = (b >> 64, b as u64 as u128);
+    ((a_hi * b_hi) << 64) + a_hi * b_lo + a_lo * b_hi + ((a_lo * b_lo) >> 64)
+}
+
+/// (a * b) >> 96 over the full 256 bit product, the result has to fit into 128 bits
+fn mul_shift_96(a: u128, b: u128) -> u128 {
+    let (a_hi, a_lo) = (a >> 64, a as u64 as u128);
+    let (b_hi, b_lo) = (b >> 64, b as u64 as u128);
+
+    let (mid, mid_carry) = (a_lo * b_hi).overflowing_add(a_hi * b_lo);
+    let (lo, lo_carry) = (a_lo * b_lo).overflowing_add(mid << 64);
+    let hi = a_hi * b_hi + (mid >> 64) + ((mid_carry as u128) << 64) + lo_carry as u128;
+
+    (hi << 32) | (lo >> 96)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_price_at_tick() {
+        assert_eq!(sqrt_price_at_tick(0), 1 << 64);
+        assert_eq!(sqrt_price_at_tick(MIN_TICK), 4295048016);
+        assert_eq!(sqrt_price_at_tick(MAX_TICK), 79226673515401279992447579055);
+    }
+
+    #[test]
+    fn test_tick_index() {
+        assert_eq!(tick_index(1 << 64), 0);
+        // price 4, between the ticks 13863 and 13864
+        assert_eq!(tick_index(2 << 64), 13863);
+        // price 0.25
+        assert_eq!(tick_index(1 << 63), -13864);
+        // rounds towards negative infinity, just below price 1
+        assert_eq!(tick_index((1 << 64) - (1 << 40)), -1);
+        assert_eq!(tick_index(4295048016), MIN_TICK);
+        assert_eq!(tick_index(79226673515401279992447579055), MAX_TICK);
+    }
+
+    #[test]
+    fn test_tick_index_at_tick_boundary() {
+        for tick in [-443635, -200001, -13864, -1, 1, 13863, 200001, 443635] {
+            let sqrt_price = sqrt_price_at_tick(tick);
+            assert_eq!(tick_index(sqrt_price), tick);
+            assert_eq!(tick_index(sqrt_price + 1), tick);
+            assert_eq!(tick_index(sqrt_price - 1), tick - 1);
+        }
+    }
+}
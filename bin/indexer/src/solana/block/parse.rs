@@ -0,0 +1,85 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::failed::parse::FailedTransaction;
+use crate::solana::block::venue::{ParsedVenue, VenueRegistry};
+use base::model::solana::{Block, TransactionStatus};
+use base::model::{Mint, PublicKey};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Everything of a block which can be derived without the database
+pub struct ParsedBlock {
+    pub block: Block,
+    /// one per registered venue, in the order of registration
+    pub venues: Vec<ParsedVenue>,
+    /// failed transactions calling into the program of a supported venue
+    pub failed: Vec<FailedTransaction>,
+    /// indices of successful transactions touching a supported venue, their token balances get tracked
+    pub tracked: Vec<usize>,
+    pub addresses: Vec<PublicKey>,
+    pub mints: Vec<Mint>,
+    /// latest token balance of every holder within the block, as (transaction index, balance index),
+    /// no matter which program moved the tokens
+    pub holders: Vec<(usize, usize)>,
+    pub parsing: Duration,
+}
+
+/// Parses the transactions of a block, cpu bound and therefore meant to run on rayon
+pub fn parse_block(venues: &VenueRegistry, block: Block) -> ParsedBlock {
+    let start = Instant::now();
+
+    let parsed = venues.parse(&block);
+    let failed = venues.parse_failed(&block);
+
+    // only track account balances of tokens traded in supported venues
+    let mut tracked = Vec::new();
+    let mut seen_addresses = HashSet::new();
+    let mut addresses = Vec::new();
+    let mut seen_mints = HashSet::new();
+    let mut mints = Vec::new();
+
+    let mut holders = Vec::new();
+    let mut seen_holders: HashMap<(Mint, PublicKey), usize> = HashMap::new();
+
+    for (index, transaction) in block.transactions.iter().enumerate() {
+        if transaction.status != TransactionStatus::Success {
+            continue;
+        }
+
+        for (balance, t) in transaction.balance.token.iter().enumerate() {
+            match seen_holders.entry((t.mint.clone(), t.address.clone())) {
+                Entry::Occupied(position) => holders[*position.get()] = (index, balance),
+                Entry::Vacant(position) => {
+                    position.insert(holders.len());
+                    holders.push((index, balance));
+                }
+            }
+        }
+
+        if venues.touched_by(&transaction.keys) {
+            tracked.push(index);
+            for t in &transaction.balance.token {
+                if seen_addresses.insert(t.address.clone()) {
+                    addresses.push(t.address.clone());
+                }
+
+                if seen_mints.insert(t.mint.clone()) {
+                    mints.push(t.mint.clone());
+                }
+            }
+        }
+    }
+
+    ParsedBlock {
+        block,
+        venues: parsed,
+        failed,
+        tracked,
+        addresses,
+        mints,
+        holders,
+        parsing: start.elapsed(),
+    }
+}
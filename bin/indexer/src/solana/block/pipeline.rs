@@ -0,0 +1,206 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::parse::{parse_block, ParsedBlock};
+use crate::solana::block::state::State;
+use crate::solana::block::{write_block, Cursor};
+use crate::solana::gap::GapRepo;
+use base::model::solana::{Block, Slot};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::join;
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+use tokio::sync::oneshot;
+
+/// Blocks after which the metrics get logged
+const LOG_EVERY: u64 = 100;
+
+/// Latency of a single pipeline stage
+#[derive(Debug, Default)]
+pub struct StageMetric {
+    count: AtomicU64,
+    total_us: AtomicU64,
+    max_us: AtomicU64,
+}
+
+impl StageMetric {
+    pub fn record(&self, duration: Duration) {
+        let us = duration.as_micros() as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_us.fetch_add(us, Ordering::Relaxed);
+        self.max_us.fetch_max(us, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn average(&self) -> Duration {
+        let count = self.count();
+        if count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros(self.total_us.load(Ordering::Relaxed) / count)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_us.load(Ordering::Relaxed))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PipelineMetrics {
+    /// transaction parsing on rayon
+    pub parse: StageMetric,
+    /// time a block waited besides parsing, e.g. for the blocks before it to be written
+    pub queue: StageMetric,
+    /// database writes including the cursor update
+    pub write: StageMetric,
+    /// from receiving a block until it got committed
+    pub total: StageMetric,
+}
+
+impl PipelineMetrics {
+    fn log(&self) {
+        info!(
+            "pipeline - blocks: {} parse: {:?}/{:?} queue: {:?}/{:?} write: {:?}/{:?} total: {:?}/{:?} (avg/max)",
+            self.total.count(),
+            self.parse.average(),
+            self.parse.max(),
+            self.queue.average(),
+            self.queue.max(),
+            self.write.average(),
+            self.write.max(),
+            self.total.average(),
+            self.total.max(),
+        );
+    }
+}
+
+struct InFlight {
+    slot: Slot,
+    received: Instant,
+    parsed: oneshot::Receiver<ParsedBlock>,
+}
+
+/// Parses several blocks in parallel on the rayon pool, while writing the blocks in flight in slot order.
+/// At most depth blocks are in flight, beyond that no further blocks are taken from the stream.
+/// A block arriving after a later slot got written is still written, but the cursor stays put.
+pub struct Pipeline {
+    state: State,
+    cursor: Cursor,
+    depth: usize,
+    pub metrics: Arc<PipelineMetrics>,
+}
+
+impl Pipeline {
+    pub fn new(state: State, cursor: Cursor, depth: usize) -> Self {
+        Self {
+            state,
+            cursor,
+            depth: depth.max(1),
+            metrics: Arc::new(PipelineMetrics::default()),
+        }
+    }
+
+    /// Runs until the block stream ends, every written slot gets sent to indexed
+    pub async fn run(&self, mut blocks: Receiver<Block>, indexed: Sender<Slot>) {
+        // the writer holds the blocks in flight, so that it can pick the lowest slot
+        let (in_flight_tx, mut in_flight_rx) = channel::<InFlight>(1);
+
+        let parse = async move {
+            while let Some(block) = blocks.recv().await {
+                let (tx, rx) = oneshot::channel();
+                let in_flight = InFlight {
+                    slot: block.slot,
+                    received: Instant::now(),
+                    parsed: rx,
+                };
+
+                let state = self.state.clone();
+                rayon::spawn(move || {
+                    let _ = tx.send(parse_block(&state.venues, block));
+                });
+
+                // waits while the pipeline is full
+                if in_flight_tx.send(in_flight).await.is_err() {
+                    return;
+                }
+            }
+        };
+
+        let write = async {
+            let mut pending: BTreeMap<i64, InFlight> = BTreeMap::new();
+            loop {
+                if pending.is_empty() {
+                    let Some(in_flight) = in_flight_rx.recv().await else {
+                        break;
+                    };
+                    pending.insert(in_flight.slot.0, in_flight);
+                }
+
+                while pending.len() < self.depth {
+                    let Ok(in_flight) = in_flight_rx.try_recv() else {
+                        break;
+                    };
+                    pending.insert(in_flight.slot.0, in_flight);
+                }
+
+                let Some((_, in_flight)) = pending.pop_first() else {
+                    break;
+                };
+
+                let Ok(parsed) = in_flight.parsed.await else {
+                    warn!("failed to parse block {}", in_flight.slot);
+                    // the cursor moves past the slot, the repairer indexes it again later on
+                    if self.cursor != Cursor::Replay {
+                        GapRepo::queue(&self.state.pool, in_flight.slot, in_flight.slot)
+                            .await
+                            .unwrap();
+                    }
+                    continue;
+                };
+
+                self.metrics.parse.record(parsed.parsing);
+                self.metrics
+                    .queue
+                    .record(in_flight.received.elapsed().saturating_sub(parsed.parsing));
+
+                let write_start = Instant::now();
+                write_block(self.state.clone(), parsed, self.cursor).await;
+                self.metrics.write.record(write_start.elapsed());
+                self.metrics.total.record(in_flight.received.elapsed());
+
+                let _ = indexed.send(in_flight.slot).await;
+
+                if self.metrics.total.count() % LOG_EVERY == 0 {
+                    self.metrics.log();
+                }
+            }
+        };
+
+        join!(parse, write);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::block::pipeline::StageMetric;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stage_metric() {
+        let metric = StageMetric::default();
+        assert_eq!(metric.average(), Duration::ZERO);
+
+        metric.record(Duration::from_millis(10));
+        metric.record(Duration::from_millis(30));
+
+        assert_eq!(metric.count(), 2);
+        assert_eq!(metric.average(), Duration::from_millis(20));
+        assert_eq!(metric.max(), Duration::from_millis(30));
+    }
+}
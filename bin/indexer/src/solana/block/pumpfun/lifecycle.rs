@@ -0,0 +1,86 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::pumpfun::parse::LifecycleEvent;
+use crate::solana::block::pumpfun::repo::{LifecycleRepo, LifecycleToInsert, LifecyclesToInsert};
+use crate::solana::block::state::State;
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, PublicKey, TokenPairId};
+use common::model::Timestamp;
+use common::repo::Tx;
+use log::debug;
+use std::collections::HashMap;
+use std::time::Instant;
+
+pub(crate) async fn index_lifecycle<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    slot: Slot,
+    timestamp: Timestamp,
+    events: Vec<(LifecycleEvent, String)>,
+) {
+    if events.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+
+    let mut keys = vec![];
+    let mut pairs = vec![];
+    for (event, _) in &events {
+        keys.push(event.bonding_curve.clone());
+        keys.push(event.user.clone());
+        keys.extend(event.pool.clone());
+        pairs.push((Mint::from(event.mint.to_string()), Mint::wsol()));
+    }
+    keys.sort();
+    keys.dedup();
+    pairs.sort();
+    pairs.dedup();
+
+    let addresses: HashMap<PublicKey, AddressId> = state
+        .address_repo
+        .list_or_populate(&mut *tx, keys)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.address, a.id))
+        .collect();
+
+    // bonding curves trade against sol
+    let token_pairs: HashMap<Mint, TokenPairId> = state
+        .token_pair_repo
+        .list_or_populate(&mut *tx, pairs)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tp| (tp.base.mint, tp.id))
+        .collect();
+
+    let events = events
+        .into_iter()
+        .map(|(event, signature)| LifecycleToInsert {
+            kind: event.kind.name(),
+            token_pair: token_pairs[&Mint::from(event.mint.to_string())],
+            bonding_curve: addresses[&event.bonding_curve],
+            user: addresses[&event.user],
+            pool: event.pool.map(|pool| addresses[&pool]),
+            signature,
+        })
+        .collect();
+
+    LifecycleRepo::insert_events(
+        &mut *tx,
+        LifecyclesToInsert {
+            slot,
+            timestamp,
+            events,
+        },
+    )
+    .await
+    .unwrap();
+    debug!(
+        "lifecycle insert took: {:?} ms",
+        start.elapsed().as_millis()
+    );
+}
@@ -1,7 +1,149 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
-pub(crate)use mint::index_token;
+pub(crate) use lifecycle::index_lifecycle;
+pub(crate) use mint::index_token;
 pub(crate) use swap::index_swap;
 
-mod swap;
+use crate::solana::block::failed::parse::FailedTrade;
+use crate::solana::block::pumpfun::parse::{parse_failed_trade, parse_lifecycle, LifecycleEvent};
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction};
+use base::model::PublicKey;
+use base::repo::TokenToInsert;
+use common::model::{DecimalAmount, Decimals};
+use common::repo::Tx;
+use solana::parse::InstructionParser;
+use solana::pumpfun::model::Instruction;
+use solana::pumpfun::repo::{SwapToInsert, SwapsToInsert};
+use solana::pumpfun::PumpFunParser;
+
+mod lifecycle;
 mod mint;
+pub mod parse;
+pub mod repo;
+mod swap;
+
+pub struct PumpfunIndexer {
+    pub program_id: PublicKey,
+}
+
+pub struct PumpfunParsed {
+    /// create instructions, the creator gets resolved when the block gets persisted
+    creates: Vec<Instruction>,
+    swaps: SwapsToInsert,
+    /// complete, withdraw and migrate events with the signature of their transaction
+    lifecycle: Vec<(LifecycleEvent, String)>,
+}
+
+#[async_trait]
+impl VenueIndexer for PumpfunIndexer {
+    type Parsed = PumpfunParsed;
+
+    fn name(&self) -> &'static str {
+        "pumpfun"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        let mut result = PumpfunParsed {
+            creates: vec![],
+            swaps: SwapsToInsert {
+                slot: block.slot,
+                timestamp: block.timestamp,
+                swaps: vec![],
+            },
+            lifecycle: vec![],
+        };
+
+        for transaction in transactions {
+            for event in parse_lifecycle(&self.program_id, transaction) {
+                result
+                    .lifecycle
+                    .push((event, transaction.signature.to_string()));
+            }
+
+            let Ok(instructions) = PumpFunParser::parse_instructions(transaction) else {
+                continue;
+            };
+
+            for instruction in instructions {
+                match instruction {
+                    create @ Instruction::Create { .. } => result.creates.push(create),
+                    Instruction::Swap {
+                        mint,
+                        sol_amount,
+                        token_amount,
+                        is_buy,
+                        user,
+                        virtual_sol_reserves,
+                        virtual_token_reserves,
+                        ..
+                    } => {
+                        result.swaps.swaps.push(SwapToInsert {
+                            base: mint,
+                            amount_base: token_amount,
+                            amount_quote: sol_amount,
+                            is_buy,
+                            wallet: user,
+                            virtual_base_reserves: virtual_token_reserves,
+                            virtual_quote_reserves: virtual_sol_reserves,
+                            signature: transaction.signature.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn parse_failed(&self, transaction: &Transaction) -> Option<FailedTrade> {
+        parse_failed_trade(&self.program_id, transaction)
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        let mut mints = vec![];
+        for create in parsed.creates {
+            if let Instruction::Create {
+                name,
+                symbol,
+                uri,
+                mint,
+                user,
+                ..
+            } = create
+            {
+                let creator = state
+                    .address_repo
+                    .get_or_populate_by_key(&mut *tx, user)
+                    .await
+                    .unwrap();
+
+                mints.push(TokenToInsert {
+                    mint,
+                    name: Some(name),
+                    symbol: Some(symbol),
+                    decimals: Decimals::from(6),
+                    supply: Some(DecimalAmount::from(1_000_000_000i64)),
+                    metadata: Some(uri),
+                    description: None,
+                    image: None,
+                    website: None,
+                    creator: Some(creator.id),
+                    block: Some(parsed.swaps.slot.into()),
+                    block_time: Some(parsed.swaps.timestamp),
+                })
+            }
+        }
+
+        index_token(tx, state.clone(), mints).await;
+        let (slot, timestamp) = (parsed.swaps.slot, parsed.swaps.timestamp.0);
+        index_swap(tx, state.clone(), parsed.swaps).await;
+        index_lifecycle(tx, state.clone(), slot, timestamp, parsed.lifecycle).await;
+    }
+}
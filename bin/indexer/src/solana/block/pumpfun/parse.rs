@@ -0,0 +1,211 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::failed::parse::FailedTrade;
+use base::model::solana::Transaction;
+use base::model::{Mint, PublicKey};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Prefix of the instruction data anchor uses to emit an event through a self cpi
+const EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Discriminator of the complete event - sha256("event:CompleteEvent")[..8]
+const COMPLETE_EVENT: [u8; 8] = [0x5f, 0x72, 0x61, 0x9c, 0xd4, 0x2e, 0x98, 0x08];
+
+/// Discriminator of the withdraw instruction - sha256("global:withdraw")[..8]
+const WITHDRAW: [u8; 8] = [0xb7, 0x12, 0x46, 0x9c, 0x94, 0x6d, 0xa1, 0x22];
+
+/// Discriminator of the migrate instruction - sha256("global:migrate")[..8]
+const MIGRATE: [u8; 8] = [0x9b, 0xea, 0xe7, 0x92, 0xec, 0x9e, 0xa2, 0x1e];
+
+/// Discriminator of the buy instruction - sha256("global:buy")[..8]
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+
+/// Discriminator of the sell instruction - sha256("global:sell")[..8]
+const SELL: [u8; 8] = [0x33, 0xe6, 0x85, 0xa4, 0x01, 0x7f, 0x83, 0xad];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LifecycleKind {
+    /// the last buy filled the bonding curve
+    Complete,
+    /// the liquidity got withdrawn from the curve, to be migrated off chain - raydium
+    Withdraw,
+    /// the liquidity got migrated into a pumpswap pool
+    Migrate,
+}
+
+impl LifecycleKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LifecycleKind::Complete => "complete",
+            LifecycleKind::Withdraw => "withdraw",
+            LifecycleKind::Migrate => "migrate",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LifecycleEvent {
+    pub kind: LifecycleKind,
+    pub mint: PublicKey,
+    pub bonding_curve: PublicKey,
+    pub user: PublicKey,
+    /// pumpswap pool the liquidity got migrated into
+    pub pool: Option<PublicKey>,
+}
+
+/// Parses the lifecycle events of the bonding curves touched by the transaction, the complete
+/// event gets emitted by the buy which fills the curve.
+pub fn parse_lifecycle(program_id: &PublicKey, transaction: &Transaction) -> Vec<LifecycleEvent> {
+    transaction
+        .instructions
+        .iter()
+        .filter(|instruction| &instruction.program_id == program_id)
+        .filter_map(|instruction| {
+            let data = &instruction.data;
+            if let Some(event) = data.strip_prefix(&EVENT_IX_TAG) {
+                decode_complete(event)
+            } else if data.starts_with(&WITHDRAW) {
+                decode_withdraw(&instruction.accounts)
+            } else if data.starts_with(&MIGRATE) {
+                decode_migrate(&instruction.accounts)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// user, mint, bonding_curve, timestamp
+fn decode_complete(event: &[u8]) -> Option<LifecycleEvent> {
+    let event = event.strip_prefix(&COMPLETE_EVENT)?;
+    let (user, event) = event.split_first_chunk::<32>()?;
+    let (mint, event) = event.split_first_chunk::<32>()?;
+    let (bonding_curve, _) = event.split_first_chunk::<32>()?;
+
+    Some(LifecycleEvent {
+        kind: LifecycleKind::Complete,
+        mint: public_key(mint)?,
+        bonding_curve: public_key(bonding_curve)?,
+        user: public_key(user)?,
+        pool: None,
+    })
+}
+
+/// withdraw accounts: global, last_withdraw, mint, bonding_curve, associated_bonding_curve, user, ..
+fn decode_withdraw(accounts: &[PublicKey]) -> Option<LifecycleEvent> {
+    Some(LifecycleEvent {
+        kind: LifecycleKind::Withdraw,
+        mint: accounts.get(2)?.clone(),
+        bonding_curve: accounts.get(3)?.clone(),
+        user: accounts.get(5)?.clone(),
+        pool: None,
+    })
+}
+
+/// migrate accounts: global, withdraw_authority, mint, bonding_curve, associated_bonding_curve,
+/// user, system_program, token_program, pump_amm, pool, ..
+fn decode_migrate(accounts: &[PublicKey]) -> Option<LifecycleEvent> {
+    Some(LifecycleEvent {
+        kind: LifecycleKind::Migrate,
+        mint: accounts.get(2)?.clone(),
+        bonding_curve: accounts.get(3)?.clone(),
+        user: accounts.get(5)?.clone(),
+        pool: Some(accounts.get(9)?.clone()),
+    })
+}
+
+/// Decodes the first buy or sell of a failed transaction, its swap event never got emitted.
+/// buy and sell accounts: global, fee_recipient, mint, bonding_curve, ..
+pub fn parse_failed_trade(
+    program_id: &PublicKey,
+    transaction: &Transaction,
+) -> Option<FailedTrade> {
+    transaction
+        .instructions
+        .iter()
+        .filter(|instruction| &instruction.program_id == program_id)
+        .find_map(|instruction| {
+            let is_buy = if instruction.data.starts_with(&BUY) {
+                true
+            } else if instruction.data.starts_with(&SELL) {
+                false
+            } else {
+                return None;
+            };
+
+            Some(FailedTrade {
+                mint: Mint::from(instruction.accounts.get(2)?.to_string()),
+                is_buy,
+            })
+        })
+}
+
+fn public_key(bytes: &[u8; 32]) -> Option<PublicKey> {
+    PublicKey::from_str(&Pubkey::new_from_array(*bytes).to_string()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "9kvTPjemayUL7XKPyjhqavbcLtY5VP2ha1G5vPuppump";
+    const CURVE: &str = "5rCf1DM8LjKTw4YqhnoLcngyZYeNnQqztScTogYHAS6";
+    const USER: &str = "DfZ5ZZ8ST9nDJ5rPmATMkZ8mmGvBFoREBBPVvB8MHKHG";
+    const POOL: &str = "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE";
+
+    fn key(value: &str) -> PublicKey {
+        PublicKey::from_str(value).unwrap()
+    }
+
+    fn accounts() -> Vec<PublicKey> {
+        let mut result = vec![key(USER); 10];
+        result[2] = key(MINT);
+        result[3] = key(CURVE);
+        result[9] = key(POOL);
+        result
+    }
+
+    #[test]
+    fn test_decode_complete() {
+        let mut event = COMPLETE_EVENT.to_vec();
+        event.extend(Pubkey::from_str(USER).unwrap().to_bytes());
+        event.extend(Pubkey::from_str(MINT).unwrap().to_bytes());
+        event.extend(Pubkey::from_str(CURVE).unwrap().to_bytes());
+        event.extend(1_748_246_400i64.to_le_bytes());
+
+        assert_eq!(
+            decode_complete(&event),
+            Some(LifecycleEvent {
+                kind: LifecycleKind::Complete,
+                mint: key(MINT),
+                bonding_curve: key(CURVE),
+                user: key(USER),
+                pool: None,
+            })
+        );
+
+        assert_eq!(decode_complete(&event[..72]), None);
+        assert_eq!(decode_complete(&event[8..]), None);
+    }
+
+    #[test]
+    fn test_decode_migrate() {
+        let event = decode_migrate(&accounts()).unwrap();
+        assert_eq!(event.kind, LifecycleKind::Migrate);
+        assert_eq!(event.mint, key(MINT));
+        assert_eq!(event.bonding_curve, key(CURVE));
+        assert_eq!(event.pool, Some(key(POOL)));
+
+        assert_eq!(decode_migrate(&accounts()[..9]), None);
+    }
+
+    #[test]
+    fn test_decode_withdraw() {
+        let event = decode_withdraw(&accounts()).unwrap();
+        assert_eq!(event.kind, LifecycleKind::Withdraw);
+        assert_eq!(event.mint, key(MINT));
+        assert_eq!(event.pool, None);
+    }
+}
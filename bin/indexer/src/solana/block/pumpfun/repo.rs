@@ -0,0 +1,116 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, TokenPairId};
+use common::model::Timestamp;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::query;
+
+pub struct LifecycleToInsert {
+    pub kind: &'static str,
+    pub token_pair: TokenPairId,
+    pub bonding_curve: AddressId,
+    pub user: AddressId,
+    pub pool: Option<AddressId>,
+    pub signature: String,
+}
+
+pub struct LifecyclesToInsert {
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    pub events: Vec<LifecycleToInsert>,
+}
+
+pub struct LifecycleRepo {}
+
+impl LifecycleRepo {
+    /// Inserts the lifecycle events and moves the lifecycle of their tokens along - a completed curve
+    /// graduates the token, a migration links the pumpswap pool to the pumpfun token pair.
+    pub async fn insert_events(
+        mut executor: impl AsSqlExecutor,
+        to_insert: LifecyclesToInsert,
+    ) -> RepoResult<()> {
+        if to_insert.events.is_empty() {
+            return Ok(());
+        }
+
+        let mut kinds = Vec::with_capacity(to_insert.events.len());
+        let mut token_pairs = Vec::with_capacity(to_insert.events.len());
+        let mut bonding_curves = Vec::with_capacity(to_insert.events.len());
+        let mut users = Vec::with_capacity(to_insert.events.len());
+        let mut pools = Vec::with_capacity(to_insert.events.len());
+        let mut signatures = Vec::with_capacity(to_insert.events.len());
+
+        for event in to_insert.events {
+            kinds.push(event.kind);
+            token_pairs.push(event.token_pair.0);
+            bonding_curves.push(event.bonding_curve.0);
+            users.push(event.user.0);
+            pools.push(event.pool.map(|p| p.0));
+            signatures.push(event.signature);
+        }
+
+        query(
+            r#"
+insert into pumpfun.lifecycle (slot, timestamp, kind, token_pair_id, bonding_curve_id, address_id, pool_id, signature)
+select $1, $2, e.kind, e.token_pair_id, e.bonding_curve_id, e.address_id, e.pool_id, e.signature
+from unnest($3::text[], $4::int8[], $5::int8[], $6::int8[], $7::int8[], $8::text[])
+    with ordinality as e(kind, token_pair_id, bonding_curve_id, address_id, pool_id, signature, ordinality)
+order by e.ordinality;
+"#,
+        )
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .bind(&kinds)
+        .bind(&token_pairs)
+        .bind(&bonding_curves)
+        .bind(&users)
+        .bind(&pools)
+        .bind(&signatures)
+        .execute(executor.as_executor())
+        .await?;
+
+        query(
+            r#"
+insert into solana.token_lifecycle (token_id, pumpfun_token_pair_id, graduated_slot, graduated_at)
+select distinct on (tp.base_id) tp.base_id, tp.id, l.slot, l.timestamp
+from pumpfun.lifecycle l
+join solana.token_pair tp on tp.id = l.token_pair_id
+where l.slot = $1
+  and l.kind = 'complete'
+order by tp.base_id, l.id
+on conflict (token_id) do nothing;
+"#,
+        )
+        .bind(to_insert.slot)
+        .execute(executor.as_executor())
+        .await?;
+
+        // the migrated pool trades the same token pair, tokens whose completion was not indexed
+        // graduate with their migration
+        query(
+            r#"
+insert into solana.token_lifecycle (token_id, pumpfun_token_pair_id, pumpswap_token_pair_id, pumpswap_pool_id, graduated_slot, graduated_at, migrated_slot, migrated_at)
+select distinct on (tp.base_id) tp.base_id, tp.id, tp.id, l.pool_id, l.slot, l.timestamp, l.slot, l.timestamp
+from pumpfun.lifecycle l
+join solana.token_pair tp on tp.id = l.token_pair_id
+where l.slot = $1
+  and l.kind = 'migrate'
+order by tp.base_id, l.id
+on conflict (token_id) do update set
+    pumpswap_token_pair_id = coalesce(solana.token_lifecycle.pumpswap_token_pair_id, excluded.pumpswap_token_pair_id),
+    pumpswap_pool_id = excluded.pumpswap_pool_id,
+    migrated_slot = excluded.migrated_slot,
+    migrated_at = excluded.migrated_at,
+    updated_at = now();
+"#,
+        )
+        .bind(to_insert.slot)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::pumpswap::parse::LiquidityEvent;
+use crate::solana::block::pumpswap::repo::{LiquiditiesToInsert, LiquidityRepo, LiquidityToInsert};
+use crate::solana::block::state::State;
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, PublicKey, TokenPairId};
+use common::model::Timestamp;
+use common::repo::Tx;
+use log::debug;
+use std::collections::HashMap;
+use std::time::Instant;
+
+pub(crate) async fn index_liquidity<'a>(
+    tx: &mut Tx<'a>,
+    state: State,
+    slot: Slot,
+    timestamp: Timestamp,
+    events: Vec<(LiquidityEvent, String)>,
+) {
+    let wsol = Mint::wsol();
+
+    // pools are quoted in sol, the token is the base of the token pair no matter its side in the pool
+    let events: Vec<(LiquidityEvent, Mint, bool, String)> = events
+        .into_iter()
+        .filter_map(|(event, signature)| {
+            let base_mint = Mint::from(event.base_mint.to_string());
+            let quote_mint = Mint::from(event.quote_mint.to_string());
+            if quote_mint == wsol {
+                Some((event, base_mint, false, signature))
+            } else if base_mint == wsol {
+                Some((event, quote_mint, true, signature))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if events.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+
+    let mut keys = vec![];
+    let mut pairs = vec![];
+    for (event, mint, _, _) in &events {
+        keys.push(event.pool.clone());
+        keys.push(event.user.clone());
+        pairs.push((mint.clone(), wsol.clone()));
+    }
+    keys.sort();
+    keys.dedup();
+    pairs.sort();
+    pairs.dedup();
+
+    let addresses: HashMap<PublicKey, AddressId> = state
+        .address_repo
+        .list_or_populate(&mut *tx, keys)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.address, a.id))
+        .collect();
+
+    let token_pairs: HashMap<Mint, TokenPairId> = state
+        .token_pair_repo
+        .list_or_populate(&mut *tx, pairs)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tp| (tp.base.mint, tp.id))
+        .collect();
+
+    let events = events
+        .into_iter()
+        .map(|(event, mint, inverted, signature)| {
+            let (amount_base, amount_quote, base_reserves, quote_reserves) = if inverted {
+                (
+                    event.amount_quote,
+                    event.amount_base,
+                    event.quote_reserves,
+                    event.base_reserves,
+                )
+            } else {
+                (
+                    event.amount_base,
+                    event.amount_quote,
+                    event.base_reserves,
+                    event.quote_reserves,
+                )
+            };
+
+            LiquidityToInsert {
+                kind: event.kind.name(),
+                pool: addresses[&event.pool],
+                token_pair: token_pairs[&mint],
+                user: addresses[&event.user],
+                amount_base,
+                amount_quote,
+                lp_amount: event.lp_amount,
+                lp_supply: event.lp_supply,
+                base_reserves,
+                quote_reserves,
+                signature,
+            }
+        })
+        .collect();
+
+    LiquidityRepo::insert_events(
+        &mut *tx,
+        LiquiditiesToInsert {
+            slot,
+            timestamp,
+            events,
+        },
+    )
+    .await
+    .unwrap();
+    debug!(
+        "liquidity insert took: {:?} ms",
+        start.elapsed().as_millis()
+    );
+}
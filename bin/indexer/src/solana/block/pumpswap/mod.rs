@@ -1,6 +1,133 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+pub(crate) use liquidity::index_liquidity;
 pub(crate) use swap::index_swap;
 
+use crate::solana::block::failed::parse::FailedTrade;
+use crate::solana::block::pumpswap::parse::{parse_events, parse_failed_trade, LiquidityEvent};
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction};
+use base::model::PublicKey;
+use common::repo::Tx;
+use solana::parse::InstructionParser;
+use solana::pumpswap::parse::idl_type::{BuyEvent, SellEvent};
+use solana::pumpswap::parse::{Instruction, PumpSwapParser};
+use solana::pumpswap::repo::{SwapToInsert, SwapsToInsert};
+
+mod liquidity;
+pub mod parse;
+pub mod repo;
 mod swap;
+
+pub struct PumpswapIndexer {
+    pub program_id: PublicKey,
+}
+
+pub struct PumpswapParsed {
+    swaps: SwapsToInsert,
+    /// create pool, deposit and withdraw events with the signature of their transaction
+    liquidity: Vec<(LiquidityEvent, String)>,
+}
+
+#[async_trait]
+impl VenueIndexer for PumpswapIndexer {
+    type Parsed = PumpswapParsed;
+
+    fn name(&self) -> &'static str {
+        "pumpswap"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        let mut result = PumpswapParsed {
+            swaps: SwapsToInsert {
+                slot: block.slot,
+                timestamp: block.timestamp.0,
+                swaps: vec![],
+            },
+            liquidity: vec![],
+        };
+
+        for transaction in transactions {
+            // a single pass over the instructions, only transactions which swapped are handed to the
+            // shared parser
+            let events = parse_events(&self.program_id, transaction);
+            for event in events.liquidity {
+                result
+                    .liquidity
+                    .push((event, transaction.signature.to_string()));
+            }
+
+            if !events.has_swap {
+                continue;
+            }
+
+            let Ok(instructions) = PumpSwapParser::parse_instructions(transaction) else {
+                continue;
+            };
+
+            for instruction in instructions {
+                match instruction {
+                    Instruction::BuyEvent(BuyEvent {
+                        pool,
+                        user,
+                        base_amount_out,
+                        quote_amount_in_with_lp_fee,
+                        pool_base_token_reserves,
+                        pool_quote_token_reserves,
+                        ..
+                    }) => {
+                        result.swaps.swaps.push(SwapToInsert {
+                            pool,
+                            amount_base: base_amount_out,
+                            amount_quote: quote_amount_in_with_lp_fee,
+                            is_buy: true,
+                            signer: user,
+                            base_reserves: pool_base_token_reserves,
+                            quote_reserves: pool_quote_token_reserves,
+                            signature: transaction.signature.clone(),
+                        });
+                    }
+                    Instruction::SellEvent(SellEvent {
+                        pool,
+                        user,
+                        base_amount_in,
+                        user_quote_amount_out,
+                        pool_base_token_reserves,
+                        pool_quote_token_reserves,
+                        ..
+                    }) => {
+                        result.swaps.swaps.push(SwapToInsert {
+                            pool,
+                            amount_base: base_amount_in,
+                            amount_quote: user_quote_amount_out,
+                            is_buy: false,
+                            signer: user,
+                            base_reserves: pool_base_token_reserves,
+                            quote_reserves: pool_quote_token_reserves,
+                            signature: transaction.signature.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn parse_failed(&self, transaction: &Transaction) -> Option<FailedTrade> {
+        parse_failed_trade(&self.program_id, transaction)
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        let (slot, timestamp) = (parsed.swaps.slot, parsed.swaps.timestamp);
+        index_swap(tx, state.clone(), parsed.swaps).await;
+        index_liquidity(tx, state.clone(), slot, timestamp, parsed.liquidity).await;
+    }
+}
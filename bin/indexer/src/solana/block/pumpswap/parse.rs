@@ -0,0 +1,412 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::failed::parse::FailedTrade;
+use base::model::solana::Transaction;
+use base::model::{Mint, PublicKey};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Prefix of the instruction data anchor uses to emit an event through a self cpi
+const EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Discriminator of the create pool event - sha256("event:CreatePoolEvent")[..8]
+const CREATE_POOL_EVENT: [u8; 8] = [0xb1, 0x31, 0x0c, 0xd2, 0xa0, 0x76, 0xa7, 0x74];
+
+/// Discriminator of the deposit event - sha256("event:DepositEvent")[..8]
+const DEPOSIT_EVENT: [u8; 8] = [0x78, 0xf8, 0x3d, 0x53, 0x1f, 0x8e, 0x6b, 0x90];
+
+/// Discriminator of the withdraw event - sha256("event:WithdrawEvent")[..8]
+const WITHDRAW_EVENT: [u8; 8] = [0x16, 0x09, 0x85, 0x1a, 0xa0, 0x2c, 0x47, 0xc0];
+
+/// Discriminator of the buy event - sha256("event:BuyEvent")[..8]
+const BUY_EVENT: [u8; 8] = [0x67, 0xf4, 0x52, 0x1f, 0x2c, 0xf5, 0x77, 0x77];
+
+/// Discriminator of the sell event - sha256("event:SellEvent")[..8]
+const SELL_EVENT: [u8; 8] = [0x3e, 0x2f, 0x37, 0x0a, 0xa5, 0x03, 0xdc, 0x2a];
+
+/// Discriminator of the buy instruction - sha256("global:buy")[..8]
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+
+/// Discriminator of the sell instruction - sha256("global:sell")[..8]
+const SELL: [u8; 8] = [0x33, 0xe6, 0x85, 0xa4, 0x01, 0x7f, 0x83, 0xad];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LiquidityKind {
+    CreatePool,
+    Deposit,
+    Withdraw,
+}
+
+impl LiquidityKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiquidityKind::CreatePool => "create_pool",
+            LiquidityKind::Deposit => "deposit",
+            LiquidityKind::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// Liquidity added to or removed from a pumpswap pool
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiquidityEvent {
+    pub kind: LiquidityKind,
+    pub pool: PublicKey,
+    pub base_mint: PublicKey,
+    pub quote_mint: PublicKey,
+    pub user: PublicKey,
+    pub amount_base: u64,
+    pub amount_quote: u64,
+    /// lp tokens minted on create pool and deposit, burned on withdraw
+    pub lp_amount: u64,
+    /// lp token supply after the event
+    pub lp_supply: u64,
+    /// pool reserves after the event
+    pub base_reserves: u64,
+    pub quote_reserves: u64,
+}
+
+/// Events the program emitted within a transaction
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedEvents {
+    pub liquidity: Vec<LiquidityEvent>,
+    /// whether the transaction emitted buy or sell events, those are decoded by `PumpSwapParser`
+    pub has_swap: bool,
+}
+
+/// Parses the liquidity events the program emitted within the transaction. Deposit and withdraw
+/// events do not carry the mints, they are taken from the accounts of the instruction which
+/// emitted the event.
+pub fn parse_events(program_id: &PublicKey, transaction: &Transaction) -> ParsedEvents {
+    let mut result = ParsedEvents::default();
+    let mut accounts: &[PublicKey] = &[];
+
+    for instruction in &transaction.instructions {
+        if &instruction.program_id != program_id {
+            continue;
+        }
+
+        let Some(event) = instruction.data.strip_prefix(&EVENT_IX_TAG) else {
+            accounts = &instruction.accounts;
+            continue;
+        };
+
+        if event.starts_with(&BUY_EVENT) || event.starts_with(&SELL_EVENT) {
+            result.has_swap = true;
+            continue;
+        }
+
+        let liquidity = if let Some(event) = event.strip_prefix(&CREATE_POOL_EVENT) {
+            decode_create_pool(event)
+        } else if let Some(event) = event.strip_prefix(&DEPOSIT_EVENT) {
+            decode_deposit(event, accounts)
+        } else if let Some(event) = event.strip_prefix(&WITHDRAW_EVENT) {
+            decode_withdraw(event, accounts)
+        } else {
+            None
+        };
+
+        if let Some(liquidity) = liquidity {
+            result.liquidity.push(liquidity);
+        }
+    }
+
+    result
+}
+
+/// Decodes the first buy or sell of a failed transaction, buying the base of a pool quoted in sol
+/// buys the token, buying wsol sells it.
+/// buy and sell accounts: pool, user, global_config, base_mint, quote_mint, ..
+pub fn parse_failed_trade(
+    program_id: &PublicKey,
+    transaction: &Transaction,
+) -> Option<FailedTrade> {
+    let wsol = Mint::wsol();
+
+    transaction
+        .instructions
+        .iter()
+        .filter(|instruction| &instruction.program_id == program_id)
+        .find_map(|instruction| {
+            let buys_base = if instruction.data.starts_with(&BUY) {
+                true
+            } else if instruction.data.starts_with(&SELL) {
+                false
+            } else {
+                return None;
+            };
+
+            let base = Mint::from(instruction.accounts.get(3)?.to_string());
+            let quote = Mint::from(instruction.accounts.get(4)?.to_string());
+            if quote == wsol {
+                Some(FailedTrade {
+                    mint: base,
+                    is_buy: buys_base,
+                })
+            } else if base == wsol {
+                Some(FailedTrade {
+                    mint: quote,
+                    is_buy: !buys_base,
+                })
+            } else {
+                None
+            }
+        })
+}
+
+/// timestamp, index, creator, base_mint, quote_mint, base_mint_decimals, quote_mint_decimals,
+/// base_amount_in, quote_amount_in, pool_base_amount, pool_quote_amount, minimum_liquidity,
+/// initial_liquidity, lp_token_amount_out, pool_bump, pool, ..
+fn decode_create_pool(event: &[u8]) -> Option<LiquidityEvent> {
+    let mut reader = Reader::new(event);
+
+    let _timestamp = reader.u64()?;
+    let _index = reader.u16()?;
+    let creator = reader.public_key()?;
+    let base_mint = reader.public_key()?;
+    let quote_mint = reader.public_key()?;
+    let _base_mint_decimals = reader.u8()?;
+    let _quote_mint_decimals = reader.u8()?;
+    let base_amount_in = reader.u64()?;
+    let quote_amount_in = reader.u64()?;
+    let pool_base_amount = reader.u64()?;
+    let pool_quote_amount = reader.u64()?;
+    let _minimum_liquidity = reader.u64()?;
+    let _initial_liquidity = reader.u64()?;
+    let lp_token_amount_out = reader.u64()?;
+    let _pool_bump = reader.u8()?;
+    let pool = reader.public_key()?;
+
+    Some(LiquidityEvent {
+        kind: LiquidityKind::CreatePool,
+        pool,
+        base_mint,
+        quote_mint,
+        user: creator,
+        amount_base: base_amount_in,
+        amount_quote: quote_amount_in,
+        lp_amount: lp_token_amount_out,
+        lp_supply: lp_token_amount_out,
+        base_reserves: pool_base_amount,
+        quote_reserves: pool_quote_amount,
+    })
+}
+
+/// deposit accounts: pool, global_config, user, base_mint, quote_mint, ..
+/// timestamp, lp_token_amount_out, max_base_amount_in, max_quote_amount_in,
+/// user_base_token_reserves, user_quote_token_reserves, pool_base_token_reserves,
+/// pool_quote_token_reserves, base_amount_in, quote_amount_in, lp_mint_supply, pool, user, ..
+fn decode_deposit(event: &[u8], accounts: &[PublicKey]) -> Option<LiquidityEvent> {
+    let mut reader = Reader::new(event);
+
+    let _timestamp = reader.u64()?;
+    let lp_token_amount_out = reader.u64()?;
+    let _max_base_amount_in = reader.u64()?;
+    let _max_quote_amount_in = reader.u64()?;
+    let _user_base_token_reserves = reader.u64()?;
+    let _user_quote_token_reserves = reader.u64()?;
+    let pool_base_token_reserves = reader.u64()?;
+    let pool_quote_token_reserves = reader.u64()?;
+    let base_amount_in = reader.u64()?;
+    let quote_amount_in = reader.u64()?;
+    // supply before the deposit, the amounts got derived from it
+    let lp_mint_supply = reader.u64()?;
+    let pool = reader.public_key()?;
+    let user = reader.public_key()?;
+
+    Some(LiquidityEvent {
+        kind: LiquidityKind::Deposit,
+        pool,
+        base_mint: accounts.get(3)?.clone(),
+        quote_mint: accounts.get(4)?.clone(),
+        user,
+        amount_base: base_amount_in,
+        amount_quote: quote_amount_in,
+        lp_amount: lp_token_amount_out,
+        lp_supply: lp_mint_supply.checked_add(lp_token_amount_out)?,
+        base_reserves: pool_base_token_reserves.checked_add(base_amount_in)?,
+        quote_reserves: pool_quote_token_reserves.checked_add(quote_amount_in)?,
+    })
+}
+
+/// withdraw accounts: pool, global_config, user, base_mint, quote_mint, ..
+/// timestamp, lp_token_amount_in, min_base_amount_out, min_quote_amount_out,
+/// user_base_token_reserves, user_quote_token_reserves, pool_base_token_reserves,
+/// pool_quote_token_reserves, base_amount_out, quote_amount_out, lp_mint_supply, pool, user, ..
+fn decode_withdraw(event: &[u8], accounts: &[PublicKey]) -> Option<LiquidityEvent> {
+    let mut reader = Reader::new(event);
+
+    let _timestamp = reader.u64()?;
+    let lp_token_amount_in = reader.u64()?;
+    let _min_base_amount_out = reader.u64()?;
+    let _min_quote_amount_out = reader.u64()?;
+    let _user_base_token_reserves = reader.u64()?;
+    let _user_quote_token_reserves = reader.u64()?;
+    let pool_base_token_reserves = reader.u64()?;
+    let pool_quote_token_reserves = reader.u64()?;
+    let base_amount_out = reader.u64()?;
+    let quote_amount_out = reader.u64()?;
+    // supply before the withdrawal, the amounts got derived from it
+    let lp_mint_supply = reader.u64()?;
+    let pool = reader.public_key()?;
+    let user = reader.public_key()?;
+
+    Some(LiquidityEvent {
+        kind: LiquidityKind::Withdraw,
+        pool,
+        base_mint: accounts.get(3)?.clone(),
+        quote_mint: accounts.get(4)?.clone(),
+        user,
+        amount_base: base_amount_out,
+        amount_quote: quote_amount_out,
+        lp_amount: lp_token_amount_in,
+        lp_supply: lp_mint_supply.checked_sub(lp_token_amount_in)?,
+        base_reserves: pool_base_token_reserves.checked_sub(base_amount_out)?,
+        quote_reserves: pool_quote_token_reserves.checked_sub(quote_amount_out)?,
+    })
+}
+
+/// Reads borsh encoded event fields
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (value, rest) = self.data.split_first_chunk::<N>()?;
+        self.data = rest;
+        Some(*value)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|v| v[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn public_key(&mut self) -> Option<PublicKey> {
+        let key = Pubkey::new_from_array(self.take()?);
+        PublicKey::from_str(&key.to_string()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL: &str = "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE";
+    const USER: &str = "DfZ5ZZ8ST9nDJ5rPmATMkZ8mmGvBFoREBBPVvB8MHKHG";
+    const MINT: &str = "9kvTPjemayUL7XKPyjhqavbcLtY5VP2ha1G5vPuppump";
+    const WSOL: &str = "So11111111111111111111111111111111111111112";
+
+    fn key(value: &str) -> PublicKey {
+        PublicKey::from_str(value).unwrap()
+    }
+
+    fn bytes(value: &str) -> [u8; 32] {
+        Pubkey::from_str(value).unwrap().to_bytes()
+    }
+
+    fn accounts() -> Vec<PublicKey> {
+        vec![key(POOL), key(POOL), key(USER), key(MINT), key(WSOL)]
+    }
+
+    /// deposit and withdraw events share their layout
+    fn event(lp_amount: u64, amount_base: u64, amount_quote: u64) -> Vec<u8> {
+        let mut event = vec![];
+        event.extend(1_748_246_400u64.to_le_bytes());
+        event.extend(lp_amount.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(0u64.to_le_bytes());
+        event.extend(200_000_000u64.to_le_bytes());
+        event.extend(80_000_000_000u64.to_le_bytes());
+        event.extend(amount_base.to_le_bytes());
+        event.extend(amount_quote.to_le_bytes());
+        event.extend(1_000_000u64.to_le_bytes());
+        event.extend(bytes(POOL));
+        event.extend(bytes(USER));
+        event.extend([0u8; 96]);
+        event
+    }
+
+    #[test]
+    fn test_decode_create_pool() {
+        let mut event = vec![];
+        event.extend(1_748_246_400u64.to_le_bytes());
+        event.extend(0u16.to_le_bytes());
+        event.extend(bytes(USER));
+        event.extend(bytes(MINT));
+        event.extend(bytes(WSOL));
+        event.extend([6u8, 9u8]);
+        event.extend(200_000_000u64.to_le_bytes());
+        event.extend(80_000_000_000u64.to_le_bytes());
+        event.extend(200_000_000u64.to_le_bytes());
+        event.extend(80_000_000_000u64.to_le_bytes());
+        event.extend(100u64.to_le_bytes());
+        event.extend(4_000_000_100u64.to_le_bytes());
+        event.extend(4_000_000_000u64.to_le_bytes());
+        event.push(255);
+        event.extend(bytes(POOL));
+
+        assert_eq!(
+            decode_create_pool(&event),
+            Some(LiquidityEvent {
+                kind: LiquidityKind::CreatePool,
+                pool: key(POOL),
+                base_mint: key(MINT),
+                quote_mint: key(WSOL),
+                user: key(USER),
+                amount_base: 200_000_000,
+                amount_quote: 80_000_000_000,
+                lp_amount: 4_000_000_000,
+                lp_supply: 4_000_000_000,
+                base_reserves: 200_000_000,
+                quote_reserves: 80_000_000_000,
+            })
+        );
+
+        assert_eq!(decode_create_pool(&event[..100]), None);
+    }
+
+    #[test]
+    fn test_decode_deposit() {
+        let deposit =
+            decode_deposit(&event(500_000, 100_000_000, 40_000_000_000), &accounts()).unwrap();
+        assert_eq!(deposit.kind, LiquidityKind::Deposit);
+        assert_eq!(deposit.base_mint, key(MINT));
+        assert_eq!(deposit.quote_mint, key(WSOL));
+        assert_eq!(deposit.lp_amount, 500_000);
+        assert_eq!(deposit.lp_supply, 1_500_000);
+        assert_eq!(deposit.base_reserves, 300_000_000);
+        assert_eq!(deposit.quote_reserves, 120_000_000_000);
+
+        assert_eq!(decode_deposit(&event(1, 1, 1), &accounts()[..4]), None);
+    }
+
+    #[test]
+    fn test_decode_withdraw() {
+        let withdraw =
+            decode_withdraw(&event(1_000_000, 200_000_000, 80_000_000_000), &accounts()).unwrap();
+        assert_eq!(withdraw.kind, LiquidityKind::Withdraw);
+        assert_eq!(withdraw.user, key(USER));
+        assert_eq!(withdraw.lp_supply, 0);
+        assert_eq!(withdraw.base_reserves, 0);
+        assert_eq!(withdraw.quote_reserves, 0);
+
+        // more lp burned than supplied
+        assert_eq!(decode_withdraw(&event(2_000_000, 0, 0), &accounts()), None);
+    }
+}
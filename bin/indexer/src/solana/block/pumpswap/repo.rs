@@ -0,0 +1,118 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, TokenPairId};
+use common::model::Timestamp;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::query;
+
+pub struct LiquidityToInsert {
+    pub kind: &'static str,
+    pub pool: AddressId,
+    pub token_pair: TokenPairId,
+    pub user: AddressId,
+    /// raw amount, without decimals applied
+    pub amount_base: u64,
+    /// raw amount, without decimals applied
+    pub amount_quote: u64,
+    pub lp_amount: u64,
+    pub lp_supply: u64,
+    /// raw reserves after the event, without decimals applied
+    pub base_reserves: u64,
+    pub quote_reserves: u64,
+    pub signature: String,
+}
+
+pub struct LiquiditiesToInsert {
+    pub slot: Slot,
+    pub timestamp: Timestamp,
+    pub events: Vec<LiquidityToInsert>,
+}
+
+pub struct LiquidityRepo {}
+
+impl LiquidityRepo {
+    /// Inserts the liquidity events, events of tokens whose decimals are not known yet are skipped.
+    /// The lp supply of a pool is the one of its latest event - see pumpswap.lp_supply.
+    pub async fn insert_events(
+        mut executor: impl AsSqlExecutor,
+        to_insert: LiquiditiesToInsert,
+    ) -> RepoResult<()> {
+        if to_insert.events.is_empty() {
+            return Ok(());
+        }
+
+        let mut kinds = Vec::with_capacity(to_insert.events.len());
+        let mut pools = Vec::with_capacity(to_insert.events.len());
+        let mut token_pairs = Vec::with_capacity(to_insert.events.len());
+        let mut users = Vec::with_capacity(to_insert.events.len());
+        let mut amount_bases = Vec::with_capacity(to_insert.events.len());
+        let mut amount_quotes = Vec::with_capacity(to_insert.events.len());
+        let mut lp_amounts = Vec::with_capacity(to_insert.events.len());
+        let mut lp_supplies = Vec::with_capacity(to_insert.events.len());
+        let mut base_reserves = Vec::with_capacity(to_insert.events.len());
+        let mut quote_reserves = Vec::with_capacity(to_insert.events.len());
+        let mut signatures = Vec::with_capacity(to_insert.events.len());
+
+        for event in to_insert.events {
+            kinds.push(event.kind);
+            pools.push(event.pool.0);
+            token_pairs.push(event.token_pair.0);
+            users.push(event.user.0);
+            amount_bases.push(event.amount_base.to_string());
+            amount_quotes.push(event.amount_quote.to_string());
+            lp_amounts.push(event.lp_amount.to_string());
+            lp_supplies.push(event.lp_supply.to_string());
+            base_reserves.push(event.base_reserves.to_string());
+            quote_reserves.push(event.quote_reserves.to_string());
+            signatures.push(event.signature);
+        }
+
+        query(
+            r#"
+insert into pumpswap.liquidity (slot, timestamp, kind, pool_id, token_pair_id, address_id, amount_base, amount_quote, lp_amount, lp_supply, base_reserves, quote_reserves, signature)
+select
+    $1,
+    $2,
+    e.kind,
+    e.pool_id,
+    e.token_pair_id,
+    e.address_id,
+    e.amount_base::numeric / power(10::numeric, b.decimals),
+    e.amount_quote::numeric / power(10::numeric, q.decimals),
+    e.lp_amount::numeric,
+    e.lp_supply::numeric,
+    e.base_reserves::numeric / power(10::numeric, b.decimals),
+    e.quote_reserves::numeric / power(10::numeric, q.decimals),
+    e.signature
+from unnest($3::text[], $4::int8[], $5::int8[], $6::int8[], $7::text[], $8::text[], $9::text[], $10::text[], $11::text[], $12::text[], $13::text[])
+    with ordinality as e(kind, pool_id, token_pair_id, address_id, amount_base, amount_quote, lp_amount, lp_supply, base_reserves, quote_reserves, signature, ordinality)
+join solana.token_pair tp on tp.id = e.token_pair_id
+join solana.token b on b.id = tp.base_id
+join solana.token q on q.id = tp.quote_id
+where b.decimals is not null
+  and q.decimals is not null
+order by e.ordinality;
+"#,
+        )
+        .bind(to_insert.slot)
+        .bind(to_insert.timestamp)
+        .bind(&kinds)
+        .bind(&pools)
+        .bind(&token_pairs)
+        .bind(&users)
+        .bind(&amount_bases)
+        .bind(&amount_quotes)
+        .bind(&lp_amounts)
+        .bind(&lp_supplies)
+        .bind(&base_reserves)
+        .bind(&quote_reserves)
+        .bind(&signatures)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
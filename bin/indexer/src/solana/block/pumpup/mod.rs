@@ -2,4 +2,83 @@
 // This file is licensed under the AGPL-3.0-or-later.
 pub(crate) use swap::index_swap;
 
+use crate::solana::block::state::State;
+use crate::solana::block::venue::VenueIndexer;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction};
+use base::model::PublicKey;
+use common::repo::Tx;
+use solana::parse::InstructionParser;
+use solana::pumpup::parse::idl_type::SwapEvent;
+use solana::pumpup::parse::{Instruction, PumpUpParser};
+use solana::pumpup::repo::{SwapToInsert, SwapsToInsert};
+
 mod swap;
+
+pub struct PumpupIndexer {
+    pub program_id: PublicKey,
+}
+
+#[async_trait]
+impl VenueIndexer for PumpupIndexer {
+    type Parsed = SwapsToInsert;
+
+    fn name(&self) -> &'static str {
+        "pumpup"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        let mut result = SwapsToInsert {
+            slot: block.slot,
+            timestamp: block.timestamp,
+            swaps: vec![],
+        };
+
+        for transaction in transactions {
+            let Ok(instructions) = PumpUpParser::parse_instructions(transaction) else {
+                continue;
+            };
+
+            for instruction in instructions {
+                match instruction {
+                    Instruction::SwapEvent(SwapEvent {
+                        mint,
+                        sol_amount,
+                        token_amount,
+                        ai_token_amount,
+                        is_buy,
+                        user,
+                        timestamp: _timestamp,
+                        pumpup_fee: _pumpup_fee,
+                        pool_real_sol_amount,
+                        pool_sol_reserves,
+                        pool_token_reserves,
+                    }) => {
+                        result.swaps.push(SwapToInsert {
+                            base: mint,
+                            amount_base: token_amount,
+                            amount_quote: sol_amount,
+                            amount_ai: ai_token_amount,
+                            is_buy,
+                            wallet: user,
+                            base_reserves: pool_token_reserves,
+                            quote_reserves: pool_sol_reserves,
+                            real_quote_reserves: pool_real_sol_amount,
+                            signature: transaction.signature.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed) {
+        index_swap(tx, state.clone(), parsed).await;
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
-use base::repo::{AddressRepo, BlockRepo, TokenBalanceRepo, TokenRepo, WalletRepo};
+use crate::solana::block::venue::VenueRegistry;
+use base::repo::{AddressRepo, BlockRepo, TokenBalanceRepo, TokenPairRepo, TokenRepo, WalletRepo};
 use sqlx::PgPool;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -20,6 +21,7 @@ pub struct StateInner {
     pub pool: PgPool,
     pub block_repo: BlockRepo,
     pub token_repo: TokenRepo,
+    pub token_pair_repo: TokenPairRepo,
     pub address_repo: AddressRepo,
     pub token_balance_repo: TokenBalanceRepo,
     pub wallet_repo: WalletRepo,
@@ -27,4 +29,5 @@ pub struct StateInner {
     pub pumpswap_swap_repo: solana::pumpswap::repo::SwapRepo,
     pub pumpup_swap_repo: solana::pumpup::repo::SwapRepo,
     pub jupiter_swap_repo: solana::jupiter::repo::SwapRepo,
+    pub venues: VenueRegistry,
 }
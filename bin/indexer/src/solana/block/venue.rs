@@ -0,0 +1,235 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::config::Config;
+use crate::solana::block::failed::parse::{FailedTrade, FailedTransaction};
+use crate::solana::block::jupiter::JupiterIndexer;
+use crate::solana::block::meteora::{MeteoraDammIndexer, MeteoraDammV1Indexer, MeteoraDlmmIndexer};
+use crate::solana::block::orca::OrcaIndexer;
+use crate::solana::block::pumpfun::PumpfunIndexer;
+use crate::solana::block::pumpswap::PumpswapIndexer;
+use crate::solana::block::pumpup::PumpupIndexer;
+use crate::solana::block::state::State;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction, TransactionStatus};
+use base::model::PublicKey;
+use common::repo::Tx;
+use common::{ConfigValue, ResolveOr};
+use std::any::Any;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Indexes the transactions of a block which call into the program of a venue
+#[async_trait]
+pub trait VenueIndexer: Send + Sync + 'static {
+    type Parsed: Send + 'static;
+
+    fn name(&self) -> &'static str;
+
+    fn program_id(&self) -> &PublicKey;
+
+    /// Parses the successful transactions calling into the program, cpu bound as it runs on rayon
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> Self::Parsed;
+
+    /// Decodes the trade a failed transaction calling into the program attempted
+    fn parse_failed(&self, _transaction: &Transaction) -> Option<FailedTrade> {
+        None
+    }
+
+    /// Persists what got parsed, within the database transaction of the block
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Self::Parsed);
+}
+
+/// Output of a venue parser, only the venue which produced it knows its type
+pub struct ParsedVenue(Box<dyn Any + Send>);
+
+#[async_trait]
+trait DynVenueIndexer: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn program_id(&self) -> &PublicKey;
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> ParsedVenue;
+
+    fn parse_failed(&self, transaction: &Transaction) -> Option<FailedTrade>;
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: ParsedVenue);
+}
+
+#[async_trait]
+impl<V: VenueIndexer> DynVenueIndexer for V {
+    fn name(&self) -> &'static str {
+        VenueIndexer::name(self)
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        VenueIndexer::program_id(self)
+    }
+
+    fn parse(&self, block: &Block, transactions: &[&Transaction]) -> ParsedVenue {
+        ParsedVenue(Box::new(VenueIndexer::parse(self, block, transactions)))
+    }
+
+    fn parse_failed(&self, transaction: &Transaction) -> Option<FailedTrade> {
+        VenueIndexer::parse_failed(self, transaction)
+    }
+
+    async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: ParsedVenue) {
+        let parsed = parsed
+            .0
+            .downcast::<V::Parsed>()
+            .expect("parsed by the same venue");
+        VenueIndexer::persist(self, tx, state, *parsed).await
+    }
+}
+
+/// Program ids of the supported venues, defaults to mainnet
+#[derive(Clone, Debug)]
+pub struct VenuePrograms {
+    pub pumpfun: PublicKey,
+    pub pumpswap: PublicKey,
+    pub pumpup: PublicKey,
+    pub jupiter: PublicKey,
+    pub meteora_dlmm: PublicKey,
+    pub meteora_damm: PublicKey,
+    pub meteora_damm_v1: PublicKey,
+    pub orca: PublicKey,
+}
+
+impl Default for VenuePrograms {
+    fn default() -> Self {
+        Self {
+            pumpfun: PublicKey::from_str("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P").unwrap(),
+            pumpswap: PublicKey::from_str("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA").unwrap(),
+            pumpup: PublicKey::from_str("PdMDrKEMaX8q7CCJb7NvUCxerBCcsFUa4LjBEynTtEd").unwrap(),
+            jupiter: PublicKey::from_str("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4").unwrap(),
+            meteora_dlmm: PublicKey::from_str("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo")
+                .unwrap(),
+            meteora_damm: PublicKey::from_str("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG")
+                .unwrap(),
+            meteora_damm_v1: PublicKey::from_str("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB")
+                .unwrap(),
+            orca: PublicKey::from_str("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc").unwrap(),
+        }
+    }
+}
+
+impl VenuePrograms {
+    pub fn load(config: &Config) -> Self {
+        let default = Self::default();
+        let program = |value: &ConfigValue, default: PublicKey| {
+            let key = value.resolve_or(String::new());
+            if key.is_empty() {
+                default
+            } else {
+                PublicKey::from_str(&key).expect("valid program id")
+            }
+        };
+
+        Self {
+            pumpfun: program(&config.venue.pumpfun, default.pumpfun),
+            pumpswap: program(&config.venue.pumpswap, default.pumpswap),
+            pumpup: program(&config.venue.pumpup, default.pumpup),
+            jupiter: program(&config.venue.jupiter, default.jupiter),
+            meteora_dlmm: program(&config.venue.meteora_dlmm, default.meteora_dlmm),
+            meteora_damm: program(&config.venue.meteora_damm, default.meteora_damm),
+            meteora_damm_v1: program(&config.venue.meteora_damm_v1, default.meteora_damm_v1),
+            orca: program(&config.venue.orca, default.orca),
+        }
+    }
+}
+
+/// Venues indexed by the indexer, they get persisted in the order of their registration
+#[derive(Clone, Default)]
+pub struct VenueRegistry {
+    venues: Vec<Arc<dyn DynVenueIndexer>>,
+}
+
+impl VenueRegistry {
+    pub fn new(programs: VenuePrograms) -> Self {
+        Self::default()
+            .register(PumpfunIndexer {
+                program_id: programs.pumpfun,
+            })
+            .register(PumpswapIndexer {
+                program_id: programs.pumpswap,
+            })
+            .register(PumpupIndexer {
+                program_id: programs.pumpup,
+            })
+            .register(JupiterIndexer {
+                program_id: programs.jupiter,
+            })
+            .register(MeteoraDlmmIndexer {
+                program_id: programs.meteora_dlmm,
+            })
+            .register(MeteoraDammIndexer {
+                program_id: programs.meteora_damm,
+            })
+            .register(MeteoraDammV1Indexer {
+                program_id: programs.meteora_damm_v1,
+            })
+            .register(OrcaIndexer {
+                program_id: programs.orca,
+            })
+    }
+
+    pub fn register(mut self, venue: impl VenueIndexer) -> Self {
+        self.venues.push(Arc::new(venue));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.venues.iter().map(|v| v.name()).collect()
+    }
+
+    /// Whether the transaction calls into the program of any registered venue
+    pub fn touched_by(&self, keys: &[PublicKey]) -> bool {
+        self.venues.iter().any(|v| keys.contains(v.program_id()))
+    }
+
+    /// Parses the block once per venue, in the order of registration
+    pub fn parse(&self, block: &Block) -> Vec<ParsedVenue> {
+        self.venues
+            .iter()
+            .map(|venue| {
+                let transactions: Vec<&Transaction> = block
+                    .transactions
+                    .iter()
+                    .filter(|t| t.status == TransactionStatus::Success)
+                    .filter(|t| t.keys.contains(venue.program_id()))
+                    .collect();
+                venue.parse(block, &transactions)
+            })
+            .collect()
+    }
+
+    /// Parses the failed transactions calling into the program of a venue. A transaction is
+    /// attributed to the venue of its first instruction calling into a registered program, a
+    /// failed route through a venue counts against the router. Retries of a wallet failing on the
+    /// same token within the block are counted once.
+    pub fn parse_failed(&self, block: &Block) -> Vec<FailedTransaction> {
+        let mut seen = HashSet::new();
+        block
+            .transactions
+            .iter()
+            .filter(|t| t.status != TransactionStatus::Success)
+            .filter_map(|t| {
+                let venue = t.instructions.iter().find_map(|instruction| {
+                    self.venues
+                        .iter()
+                        .find(|v| v.program_id() == &instruction.program_id)
+                })?;
+                FailedTransaction::new(venue.name(), t, venue.parse_failed(t))
+            })
+            .filter(|f| seen.insert((f.venue, f.signer.clone(), f.mint.clone())))
+            .collect()
+    }
+
+    pub async fn persist(&self, tx: &mut Tx<'_>, state: &State, parsed: Vec<ParsedVenue>) {
+        for (venue, parsed) in self.venues.iter().zip(parsed) {
+            venue.persist(tx, state, parsed).await;
+        }
+    }
+}
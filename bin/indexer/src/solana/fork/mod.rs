@@ -0,0 +1,222 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::fork::repo::{ForkRepo, PendingBlock};
+
+use crate::config::Config;
+use crate::solana::gap::{RpcSlotSource, SlotSource};
+use base::model::solana::Slot;
+use common::repo::pool::setup_pool;
+use common::{ResolveOr, Signal};
+use log::{info, warn};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::process::exit;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+mod repo;
+
+/// Slots it takes for a block to get finalized
+pub const FINALITY_DEPTH: i64 = 32;
+
+/// Seconds after which a block, which still is not confirmed, is considered abandoned
+const ABANDONED_AFTER: i64 = 60;
+
+/// Commitment level blocks get indexed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Commitment {
+    /// lowest latency, blocks get rolled back if their fork gets abandoned
+    Processed,
+    #[default]
+    Confirmed,
+    /// blocks get indexed once they can no longer be rolled back
+    Finalized,
+}
+
+impl FromStr for Commitment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "processed" => Ok(Commitment::Processed),
+            "confirmed" => Ok(Commitment::Confirmed),
+            "finalized" => Ok(Commitment::Finalized),
+            _ => Err(format!("unknown commitment: {s}")),
+        }
+    }
+}
+
+impl Commitment {
+    pub fn load(config: &Config) -> Self {
+        config
+            .blockstream
+            .commitment
+            .resolve_or("confirmed".to_string())
+            .parse()
+            .unwrap()
+    }
+
+    /// Slots the slot stream stays behind the tip
+    pub fn delay(&self, configured: Slot) -> Slot {
+        match self {
+            Commitment::Processed => Slot::from(0),
+            Commitment::Confirmed => configured,
+            Commitment::Finalized => Slot::from(configured.0.max(FINALITY_DEPTH)),
+        }
+    }
+}
+
+/// Outcome of comparing pending blocks with the confirmed slots of the cluster
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settlement {
+    pub confirmed: Vec<Slot>,
+    /// blocks of an abandoned fork
+    pub abandoned: Vec<Slot>,
+}
+
+/// A pending block is abandoned once the cluster confirmed a later slot without it,
+/// or if it did not get confirmed in time
+pub fn settle(pending: &[PendingBlock], confirmed: &[Slot]) -> Settlement {
+    let latest = confirmed.iter().map(|s| s.0).max();
+    let confirmed: HashSet<i64> = confirmed.iter().map(|s| s.0).collect();
+
+    let mut result = Settlement::default();
+    for block in pending {
+        if confirmed.contains(&block.slot.0) {
+            result.confirmed.push(block.slot);
+        } else if latest.is_some_and(|latest| latest > block.slot.0) || block.age > ABANDONED_AFTER
+        {
+            result.abandoned.push(block.slot);
+        }
+    }
+    result
+}
+
+/// Confirms or rolls back blocks indexed at processed commitment
+pub struct ForkWatcher<S: SlotSource> {
+    pub pool: PgPool,
+    pub source: S,
+}
+
+impl<S: SlotSource> ForkWatcher<S> {
+    pub async fn watch(&self) -> Result<Settlement, String> {
+        let pending = ForkRepo::list_pending(&self.pool)
+            .await
+            .map_err(|err| format!("{err:?}"))?;
+
+        let (Some(first), Some(last)) = (pending.first(), pending.last()) else {
+            return Ok(Settlement::default());
+        };
+
+        // looking a bit further to see whether the cluster moved on
+        let confirmed = self
+            .source
+            .confirmed_slots(first.slot, Slot::from(last.slot.0 + FINALITY_DEPTH))
+            .await?;
+
+        let settlement = settle(&pending, &confirmed);
+
+        let mut tx = self.pool.begin().await.map_err(|err| err.to_string())?;
+        ForkRepo::confirmed(&mut *tx, &settlement.confirmed)
+            .await
+            .map_err(|err| format!("{err:?}"))?;
+        for slot in &settlement.abandoned {
+            let rows = ForkRepo::rollback(&mut *tx, *slot)
+                .await
+                .map_err(|err| format!("{err:?}"))?;
+            warn!("rolled back {rows} rows of abandoned slot {}", slot.0);
+        }
+        tx.commit().await.map_err(|err| err.to_string())?;
+
+        Ok(settlement)
+    }
+}
+
+pub fn watch_forks(config: Config, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        if Commitment::load(&config) != Commitment::Processed {
+            info!("not active");
+            return;
+        }
+
+        info!("active");
+        let watcher = ForkWatcher {
+            pool: setup_pool(config.postgres.clone()).await,
+            source: RpcSlotSource::new(config.rpc.url.resolve()),
+        };
+
+        let mut interval = interval(Duration::from_millis(500));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    if let Err(err) = watcher.watch().await {
+                        warn!("failed to watch forks: {err}");
+                    }
+                }
+                _ = signal.recv() => {
+                    exit(-1);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::fork::{settle, Commitment, PendingBlock};
+    use base::model::solana::Slot;
+
+    fn pending(slot: i64, age: i64) -> PendingBlock {
+        PendingBlock {
+            slot: Slot::from(slot),
+            age,
+        }
+    }
+
+    fn slots(slots: &[i64]) -> Vec<Slot> {
+        slots.iter().map(|s| Slot::from(*s)).collect()
+    }
+
+    #[test]
+    fn test_commitment() {
+        assert_eq!("processed".parse(), Ok(Commitment::Processed));
+        assert_eq!("Confirmed".parse(), Ok(Commitment::Confirmed));
+        assert_eq!("finalized".parse(), Ok(Commitment::Finalized));
+        assert!("final".parse::<Commitment>().is_err());
+    }
+
+    #[test]
+    fn test_delay() {
+        assert_eq!(Commitment::Processed.delay(Slot::from(5)), Slot::from(0));
+        assert_eq!(Commitment::Confirmed.delay(Slot::from(5)), Slot::from(5));
+        assert_eq!(Commitment::Finalized.delay(Slot::from(5)), Slot::from(32));
+        assert_eq!(Commitment::Finalized.delay(Slot::from(50)), Slot::from(50));
+    }
+
+    #[test]
+    fn test_settle() {
+        let result = settle(
+            &[
+                pending(10, 1),
+                pending(11, 1),
+                pending(12, 1),
+                pending(14, 1),
+            ],
+            &slots(&[10, 12, 13]),
+        );
+        assert_eq!(result.confirmed, slots(&[10, 12]));
+        // 13 got confirmed without 11, 14 might still get confirmed
+        assert_eq!(result.abandoned, slots(&[11]));
+    }
+
+    #[test]
+    fn test_settle_timeout() {
+        let result = settle(&[pending(10, 61), pending(11, 5)], &[]);
+        assert!(result.confirmed.is_empty());
+        assert_eq!(result.abandoned, slots(&[10]));
+    }
+}
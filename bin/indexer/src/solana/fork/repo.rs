@@ -0,0 +1,115 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingBlock {
+    pub slot: Slot,
+    /// seconds since the block got indexed
+    pub age: i64,
+}
+
+pub struct ForkRepo {}
+
+impl ForkRepo {
+    /// Tags a block indexed at processed commitment
+    pub async fn pending(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query("insert into solana.block_pending (slot) values ($1) on conflict (slot) do nothing;")
+            .bind(slot.into())
+            .execute(executor.as_executor())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list_pending(mut executor: impl AsSqlExecutor) -> RepoResult<Vec<PendingBlock>> {
+        Ok(query(
+            r#"
+select slot, extract(epoch from now() - indexed_at)::int8 as age
+from solana.block_pending
+order by slot;
+"#,
+        )
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| PendingBlock {
+            slot: r.get::<Slot, _>("slot"),
+            age: r.get::<i64, _>("age"),
+        })
+        .collect())
+    }
+
+    pub async fn confirmed(mut executor: impl AsSqlExecutor, slots: &[Slot]) -> RepoResult<()> {
+        query(
+            r#"
+with confirmed as (delete from solana.block_pending where slot = any($1))
+delete from solana.token_revision where slot = any($1);
+"#,
+        )
+        .bind(slots.iter().map(|s| s.0).collect::<Vec<_>>())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Drops what a rollback of the block would restore, for blocks which never were pending
+    pub async fn settled(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query("delete from solana.token_revision where slot = $1;")
+            .bind(slot.into())
+            .execute(executor.as_executor())
+            .await?;
+        Ok(())
+    }
+
+    /// Last slot before the first pending block, state derived from several slots must not move
+    /// past it as pending blocks might still get rolled back. None if no block is pending.
+    pub async fn settled_slot(mut executor: impl AsSqlExecutor) -> RepoResult<Option<Slot>> {
+        Ok(
+            query("select min(slot) - 1 as slot from solana.block_pending;")
+                .fetch_one(executor.as_executor())
+                .await?
+                .get::<Option<Slot>, _>("slot"),
+        )
+    }
+
+    /// Removes everything indexed for the slot and records the rollback, the aggregator
+    /// recalculates the candles from the block time on. Returns the number of removed rows.
+    pub async fn rollback(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<i64> {
+        let row = query(
+            r#"
+with block as (select timestamp from solana.block where slot = $1)
+insert into solana.block_rollback (slot, timestamp, rows)
+values ($1, (select timestamp from block), solana.rollback_slot($1))
+returning id, rows;
+"#,
+        )
+        .bind(slot.into())
+        .fetch_one(executor.as_executor())
+        .await?;
+
+        query(
+            r#"
+insert into solana.aggregate_range (from_timestamp, to_timestamp)
+select timestamp, timestamp from solana.block_rollback where id = $1 and timestamp is not null;
+"#,
+        )
+        .bind(row.get::<i64, _>("id"))
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(row.get::<i64, _>("rows"))
+    }
+}
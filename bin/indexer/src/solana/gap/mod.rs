@@ -0,0 +1,351 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::gap::repo::{GapRepo, SlotGap};
+
+use crate::config::Config;
+use crate::solana::backfill::{Backfiller, BlockSource, RpcBlockSource};
+use crate::solana::block::{setup_state, Cursor};
+use crate::solana::indexer::IndexerRepo;
+use crate::solana::replay::BlockCapture;
+use async_trait::async_trait;
+use base::model::solana::Slot;
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::{ResolveOr, Signal};
+use log::{info, warn};
+use reqwest::Client;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::process::exit;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+mod repo;
+
+/// Slots behind the live indexer which are not audited yet, as they might still be in flight
+const SAFETY_MARGIN: i64 = 150;
+/// Repairs of a gap before it gets given up on
+const MAX_ATTEMPTS: i32 = 3;
+
+/// Source of the slots the cluster confirmed a block for
+#[async_trait]
+pub trait SlotSource: Send + Sync {
+    /// Confirmed slots within [from, to], in ascending order
+    async fn confirmed_slots(&self, from: Slot, to: Slot) -> Result<Vec<Slot>, String>;
+}
+
+/// Loads confirmed slots with the getBlocks json rpc method
+pub struct RpcSlotSource {
+    client: Client,
+    url: String,
+}
+
+impl RpcSlotSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap(),
+            url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Vec<i64>>,
+    error: Option<serde_json::Value>,
+}
+
+#[async_trait]
+impl SlotSource for RpcSlotSource {
+    async fn confirmed_slots(&self, from: Slot, to: Slot) -> Result<Vec<Slot>, String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getBlocks",
+                "params": [from.0, to.0, {"commitment": "confirmed"}]
+            }))
+            .send()
+            .await
+            .map_err(|err| err.to_string())?
+            .json::<RpcResponse>()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        match response {
+            RpcResponse {
+                result: Some(slots),
+                ..
+            } => Ok(slots.into_iter().map(Slot::from).collect()),
+            RpcResponse { error, .. } => Err(format!("getBlocks failed: {error:?}")),
+        }
+    }
+}
+
+/// Outcome of comparing the indexed slots of a window with the confirmed ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    pub confirmed: usize,
+    /// slots without a block, the leader did not produce one
+    pub skipped: usize,
+    /// contiguous ranges of confirmed slots which did not get indexed, a skipped slot ends a range
+    pub missing: Vec<(i64, i64)>,
+}
+
+impl Audit {
+    pub fn missing_count(&self) -> usize {
+        self.missing
+            .iter()
+            .map(|(from, to)| (to - from + 1) as usize)
+            .sum()
+    }
+}
+
+/// Classifies every slot within [from, to] into indexed, skipped and missing
+pub fn audit(from: i64, to: i64, confirmed: &[i64], indexed: &[i64]) -> Audit {
+    let indexed: HashSet<i64> = indexed.iter().copied().collect();
+
+    let confirmed: Vec<i64> = confirmed
+        .iter()
+        .copied()
+        .filter(|slot| *slot >= from && *slot <= to)
+        .collect();
+
+    let mut missing: Vec<(i64, i64)> = Vec::new();
+    for slot in confirmed.iter().filter(|slot| !indexed.contains(slot)) {
+        match missing.last_mut() {
+            Some((_, to)) if *to + 1 == *slot => *to = *slot,
+            _ => missing.push((*slot, *slot)),
+        }
+    }
+
+    Audit {
+        confirmed: confirmed.len(),
+        skipped: (to - from + 1) as usize - confirmed.len(),
+        missing,
+    }
+}
+
+/// Audits windows of slots behind the live indexer, gaps get queued for repair
+pub struct Auditor<S: SlotSource> {
+    pub pool: PgPool,
+    pub source: S,
+    /// slots audited per round
+    pub window: i64,
+}
+
+impl<S: SlotSource> Auditor<S> {
+    /// Audits the next window, returns none if there was nothing to audit
+    pub async fn audit(&self) -> Result<Option<Audit>, String> {
+        let indexer_slot = repo_result(IndexerRepo::get_solana_indexer_slot(&self.pool).await)?
+            .slot
+            .0;
+
+        let cursor = match repo_result(IndexerRepo::get_slot_audit_slot(&self.pool).await)? {
+            Some(slot) => slot.0,
+            None => match repo_result(GapRepo::first_indexed(&self.pool).await)? {
+                Some(slot) => slot.0 - 1,
+                None => return Ok(None),
+            },
+        };
+
+        let from = cursor + 1;
+        let to = (cursor + self.window).min(indexer_slot - SAFETY_MARGIN);
+        if to < from {
+            return Ok(None);
+        }
+
+        let confirmed: Vec<i64> = self
+            .source
+            .confirmed_slots(Slot::from(from), Slot::from(to))
+            .await?
+            .into_iter()
+            .map(|s| s.0)
+            .collect();
+
+        let indexed: Vec<i64> = repo_result(GapRepo::list_indexed(&self.pool, from, to).await)?
+            .into_iter()
+            .map(|s| s.0)
+            .collect();
+
+        let audit = audit(from, to, &confirmed, &indexed);
+
+        let mut tx = repo_result(self.pool.begin().await.map_err(Into::into))?;
+        repo_result(
+            GapRepo::audited(
+                &mut *tx,
+                from,
+                to,
+                audit.confirmed as i32,
+                audit.skipped as i32,
+                audit.missing_count() as i32,
+            )
+            .await,
+        )?;
+        for (from, to) in &audit.missing {
+            repo_result(GapRepo::queue(&mut *tx, *from, *to).await)?;
+        }
+        repo_result(IndexerRepo::set_slot_audit_slot(&mut *tx, to).await)?;
+        repo_result(tx.commit().await.map_err(Into::into))?;
+
+        if !audit.missing.is_empty() {
+            warn!(
+                "{} slots missing between {from} and {to}: {:?}",
+                audit.missing_count(),
+                audit.missing
+            );
+        }
+
+        Ok(Some(audit))
+    }
+}
+
+/// Re-indexes queued gaps, without creating a backfill for them
+pub struct Repairer<B: BlockSource> {
+    pub backfiller: Backfiller<B>,
+    /// gaps repaired per round
+    pub batch: i64,
+}
+
+impl<B: BlockSource> Repairer<B> {
+    /// Repairs the queued gaps, returns the number of gaps attempted
+    pub async fn repair(&self) -> RepoResult<usize> {
+        let pool = &self.backfiller.state.pool;
+        let gaps = GapRepo::list_queued(pool, self.batch).await?;
+
+        for gap in &gaps {
+            let give_up = gap.attempts + 1 >= MAX_ATTEMPTS;
+
+            let after = Slot::from(gap.from.0 - 1);
+            match self.backfiller.index(after, gap.to, Cursor::Repair).await {
+                Ok(_) => {
+                    let indexed = GapRepo::list_indexed(pool, gap.from, gap.to).await?;
+                    if indexed.len() as i64 == gap.to.0 - gap.from.0 + 1 {
+                        info!("repaired slots {} to {}", gap.from.0, gap.to.0);
+                        GapRepo::repaired(pool, gap.id).await?;
+                    } else {
+                        // the block source claims the slots got skipped, retrying will not help
+                        GapRepo::failed(
+                            pool,
+                            gap.id,
+                            format!(
+                                "{} slots without a block",
+                                gap.to.0 - gap.from.0 + 1 - indexed.len() as i64
+                            ),
+                            true,
+                        )
+                        .await?;
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "failed to repair slots {} to {}: {err:?}",
+                        gap.from.0, gap.to.0
+                    );
+                    GapRepo::failed(pool, gap.id, format!("{err:?}"), give_up).await?;
+                }
+            }
+        }
+
+        Ok(gaps.len())
+    }
+}
+
+pub fn repair_slot_gaps(config: Config, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        let cfg = config.gap.clone();
+        if !cfg.active.resolve_or(false) {
+            info!("not active");
+            return;
+        }
+
+        info!("active");
+        let pool = setup_pool(config.postgres.clone()).await;
+        let url = cfg.url.resolve_or(config.rpc.url.resolve());
+
+        let auditor = Auditor {
+            pool: pool.clone(),
+            source: RpcSlotSource::new(url.clone()),
+            window: cfg.window.resolve_or(1_000usize) as i64,
+        };
+
+        let repairer = Repairer {
+            backfiller: Backfiller {
+                state: setup_state(&config, pool),
+                source: RpcBlockSource::new(url).with_capture(BlockCapture::load(&config)),
+                concurrency: 4,
+            },
+            batch: 10,
+        };
+
+        let mut interval = interval(Duration::from_secs(10));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    if let Err(err) = auditor.audit().await {
+                        warn!("failed to audit slots: {err}");
+                    }
+                    if let Err(err) = repairer.repair().await {
+                        warn!("failed to repair slots: {err:?}");
+                    }
+                }
+                _ = signal.recv() => {
+                    exit(-1);
+                }
+            }
+        }
+    })
+}
+
+fn repo_result<T>(result: RepoResult<T>) -> Result<T, String> {
+    result.map_err(|err| format!("{err:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::gap::{audit, Audit};
+
+    #[test]
+    fn test_nothing_missing() {
+        assert_eq!(
+            audit(10, 15, &[10, 11, 13, 15], &[10, 11, 13, 15]),
+            Audit {
+                confirmed: 4,
+                skipped: 2,
+                missing: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_ranges() {
+        let result = audit(10, 20, &[10, 11, 12, 14, 15, 17, 18, 20], &[10, 15, 20]);
+        assert_eq!(result.confirmed, 8);
+        assert_eq!(result.skipped, 3);
+        assert_eq!(result.missing, vec![(11, 12), (14, 14), (17, 18)]);
+        assert_eq!(result.missing_count(), 5);
+    }
+
+    #[test]
+    fn test_nothing_indexed() {
+        let result = audit(1, 5, &[1, 2, 3, 4, 5], &[]);
+        assert_eq!(result.skipped, 0);
+        assert_eq!(result.missing, vec![(1, 5)]);
+    }
+
+    #[test]
+    fn test_confirmed_outside_window_ignored() {
+        let result = audit(5, 6, &[4, 5, 6, 7], &[5]);
+        assert_eq!(result.confirmed, 2);
+        assert_eq!(result.missing, vec![(6, 6)]);
+    }
+}
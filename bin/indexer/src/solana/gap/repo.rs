@@ -0,0 +1,155 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotGap {
+    pub id: i64,
+    pub from: Slot,
+    pub to: Slot,
+    pub attempts: i32,
+}
+
+pub struct GapRepo {}
+
+impl GapRepo {
+    /// Slots within [from, to] which got a block indexed
+    pub async fn list_indexed(
+        mut executor: impl AsSqlExecutor,
+        from: impl Into<Slot> + Send,
+        to: impl Into<Slot> + Send,
+    ) -> RepoResult<Vec<Slot>> {
+        Ok(
+            query("select slot from solana.block where slot >= $1 and slot <= $2 order by slot;")
+                .bind(from.into())
+                .bind(to.into())
+                .fetch_all(executor.as_executor())
+                .await?
+                .into_iter()
+                .map(|r| r.get::<Slot, _>("slot"))
+                .collect(),
+        )
+    }
+
+    /// First slot which got a block indexed
+    pub async fn first_indexed(mut executor: impl AsSqlExecutor) -> RepoResult<Option<Slot>> {
+        Ok(query("select min(slot) as slot from solana.block;")
+            .fetch_one(executor.as_executor())
+            .await?
+            .get::<Option<Slot>, _>("slot"))
+    }
+
+    pub async fn audited(
+        mut executor: impl AsSqlExecutor,
+        from: impl Into<Slot> + Send,
+        to: impl Into<Slot> + Send,
+        confirmed: i32,
+        skipped: i32,
+        missing: i32,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.slot_audit (from_slot, to_slot, confirmed, skipped, missing)
+values ($1, $2, $3, $4, $5);
+"#,
+        )
+        .bind(from.into())
+        .bind(to.into())
+        .bind(confirmed)
+        .bind(skipped)
+        .bind(missing)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn queue(
+        mut executor: impl AsSqlExecutor,
+        from: impl Into<Slot> + Send,
+        to: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.slot_gap (from_slot, to_slot) values ($1, $2)
+on conflict (from_slot, to_slot) do nothing;
+"#,
+        )
+        .bind(from.into())
+        .bind(to.into())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_queued(
+        mut executor: impl AsSqlExecutor,
+        limit: i64,
+    ) -> RepoResult<Vec<SlotGap>> {
+        Ok(query(
+            r#"
+select id, from_slot, to_slot, attempts
+from solana.slot_gap
+where status = 'QUEUED'
+order by from_slot
+limit $1;
+"#,
+        )
+        .bind(limit)
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| SlotGap {
+            id: r.get::<i64, _>("id"),
+            from: r.get::<Slot, _>("from_slot"),
+            to: r.get::<Slot, _>("to_slot"),
+            attempts: r.get::<i32, _>("attempts"),
+        })
+        .collect())
+    }
+
+    pub async fn repaired(mut executor: impl AsSqlExecutor, gap: i64) -> RepoResult<()> {
+        query(
+            r#"
+update solana.slot_gap set
+    status = 'REPAIRED',
+    attempts = attempts + 1,
+    last_error = null,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(gap)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed repair, the gap stays queued unless given up
+    pub async fn failed(
+        mut executor: impl AsSqlExecutor,
+        gap: i64,
+        error: String,
+        give_up: bool,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update solana.slot_gap set
+    status = case when $3 then 'FAILED' else 'QUEUED' end,
+    attempts = attempts + 1,
+    last_error = $2,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(gap)
+        .bind(error)
+        .bind(give_up)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::replay::BlockCapture;
+use base::model::solana::{Block, Slot};
+use solana::convert::convert_block;
+use solana_transaction_status::{
+    BlockEncodingOptions, ConfirmedBlock, TransactionDetails, UiConfirmedBlock,
+    UiTransactionEncoding,
+};
+use yellowstone_grpc_proto::convert_from::create_tx_with_meta;
+use yellowstone_grpc_proto::prelude::SubscribeUpdateBlock;
+
+/// Converts a geyser block update into the same model the indexer gets from rpc
+pub async fn convert_update(
+    update: SubscribeUpdateBlock,
+    capture: Option<&BlockCapture>,
+) -> Result<Option<Block>, String> {
+    let slot = Slot::from(update.slot as i64);
+    let block = to_ui_block(update)?;
+
+    if let Some(capture) = capture {
+        capture.write_in_background(slot, block.clone());
+    }
+
+    convert_block(slot, block)
+        .await
+        .map_err(|err| format!("{err:?}"))
+}
+
+/// Encodes the update the way getBlock does with base58 encoding and full transaction details
+pub fn to_ui_block(update: SubscribeUpdateBlock) -> Result<UiConfirmedBlock, String> {
+    let mut transactions = update.transactions;
+    // transactions are not guaranteed to arrive in block order
+    transactions.sort_by_key(|tx| tx.index);
+
+    let transactions = transactions
+        .into_iter()
+        .map(|tx| create_tx_with_meta(tx).map_err(|err| err.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    ConfirmedBlock {
+        previous_blockhash: update.parent_blockhash,
+        blockhash: update.blockhash,
+        parent_slot: update.parent_slot,
+        transactions,
+        rewards: vec![],
+        num_partitions: None,
+        block_time: update.block_time.map(|t| t.timestamp),
+        block_height: update.block_height.map(|h| h.block_height),
+    }
+    .encode_with_options(
+        UiTransactionEncoding::Base58,
+        BlockEncodingOptions {
+            transaction_details: TransactionDetails::Full,
+            show_rewards: false,
+            max_supported_transaction_version: Some(0),
+        },
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::geyser::convert::to_ui_block;
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, Message, MessageHeader, SubscribeUpdateBlock,
+        SubscribeUpdateTransactionInfo, Transaction, TransactionStatusMeta, UnixTimestamp,
+    };
+
+    fn transaction(index: u64, signature: u8) -> SubscribeUpdateTransactionInfo {
+        SubscribeUpdateTransactionInfo {
+            signature: vec![signature; 64],
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures: vec![vec![signature; 64]],
+                message: Some(Message {
+                    header: Some(MessageHeader {
+                        num_required_signatures: 1,
+                        num_readonly_signed_accounts: 0,
+                        num_readonly_unsigned_accounts: 1,
+                    }),
+                    account_keys: vec![vec![signature; 32], vec![0; 32]],
+                    recent_blockhash: vec![1; 32],
+                    instructions: vec![CompiledInstruction {
+                        program_id_index: 1,
+                        accounts: vec![0],
+                        data: vec![],
+                    }],
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                fee: 5000,
+                pre_balances: vec![10_000, 1],
+                post_balances: vec![5_000, 1],
+                ..Default::default()
+            }),
+            index,
+        }
+    }
+
+    #[test]
+    fn test_to_ui_block() {
+        let block = to_ui_block(SubscribeUpdateBlock {
+            slot: 326027760,
+            blockhash: "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm".to_string(),
+            parent_slot: 326027759,
+            parent_blockhash: "HUPrBhHv9TQC18vCTSRnjoi25fdep3twfAb6Shra761".to_string(),
+            block_time: Some(UnixTimestamp {
+                timestamp: 1742500000,
+            }),
+            transactions: vec![transaction(1, 2), transaction(0, 1)],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            block.blockhash,
+            "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm"
+        );
+        assert_eq!(
+            block.previous_blockhash,
+            "HUPrBhHv9TQC18vCTSRnjoi25fdep3twfAb6Shra761"
+        );
+        assert_eq!(block.parent_slot, 326027759);
+        assert_eq!(block.block_time, Some(1742500000));
+
+        let transactions = block.transactions.unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        // sorted by their index within the block
+        let signatures: Vec<u8> = transactions
+            .iter()
+            .map(|tx| tx.transaction.decode().unwrap().signatures[0].as_ref()[0])
+            .collect();
+        assert_eq!(signatures, vec![1, 2]);
+
+        let meta = transactions[0].meta.as_ref().unwrap();
+        assert_eq!(meta.fee, 5000);
+        assert_eq!(meta.post_balances, vec![5_000, 1]);
+    }
+
+    #[test]
+    fn test_to_ui_block_invalid_transaction() {
+        let mut tx = transaction(0, 1);
+        tx.transaction = None;
+
+        let result = to_ui_block(SubscribeUpdateBlock {
+            slot: 326027760,
+            transactions: vec![tx],
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::geyser::convert::{convert_update, to_ui_block};
+
+use crate::config::Config;
+use crate::solana::fork::Commitment;
+use crate::solana::indexer::IndexerRepo;
+use crate::solana::replay::BlockCapture;
+use async_trait::async_trait;
+use base::model::solana::Block;
+use common::{ResolveOr, Signal};
+use futures::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use solana::stream::BlockStream;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::{channel, Sender};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio::{select, spawn};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterBlocks, SubscribeRequestPing,
+};
+
+mod convert;
+
+/// Time to wait before reconnecting after the subscription failed
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Blocks can easily exceed the default message size limit of tonic
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct GeyserBlockStreamConfig {
+    pub url: String,
+    pub token: Option<String>,
+    pub commitment: Commitment,
+    /// writes every received block to disk
+    pub capture: Option<BlockCapture>,
+}
+
+impl GeyserBlockStreamConfig {
+    pub fn load(config: &Config) -> Self {
+        let token = config.geyser.token.resolve_or(String::new());
+        Self {
+            url: config.geyser.url.resolve(),
+            token: Some(token).filter(|t| !t.is_empty()),
+            commitment: Commitment::load(config),
+            capture: BlockCapture::load(config),
+        }
+    }
+}
+
+/// Streams blocks from a yellowstone geyser grpc block subscription.
+/// Unlike the rpc block stream there is no polling, blocks are pushed as soon as the node has them.
+pub struct GeyserBlockStream {
+    config: GeyserBlockStreamConfig,
+    pool: PgPool,
+}
+
+/// Progress of a single subscription
+#[derive(Default)]
+struct Subscription {
+    connected: bool,
+    received: usize,
+}
+
+impl GeyserBlockStream {
+    pub fn new(config: GeyserBlockStreamConfig, pool: PgPool) -> Self {
+        Self { config, pool }
+    }
+
+    /// Slot after the last indexed one, none if nothing got indexed yet
+    async fn resume_slot(&self) -> Option<u64> {
+        IndexerRepo::get_solana_indexer_slot(&self.pool)
+            .await
+            .ok()
+            .map(|indexer| indexer.slot.0 as u64 + 1)
+    }
+
+    async fn subscribe(
+        &self,
+        blocks: &Sender<Block>,
+        from_slot: Option<u64>,
+        subscription: &mut Subscription,
+    ) -> Result<(), String> {
+        let mut client = GeyserGrpcClient::build_from_shared(self.config.url.clone())
+            .map_err(|err| err.to_string())?
+            .x_token(self.config.token.clone())
+            .map_err(|err| err.to_string())?
+            .connect_timeout(Duration::from_secs(10))
+            .max_decoding_message_size(MAX_MESSAGE_SIZE)
+            .connect()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let (mut sink, mut updates) = client
+            .subscribe_with_request(Some(request(self.config.commitment, from_slot)))
+            .await
+            .map_err(|err| err.to_string())?;
+
+        subscription.connected = true;
+        info!("subscribed to {} from {from_slot:?}", self.config.url);
+
+        while let Some(update) = updates.next().await {
+            let update = update.map_err(|status| status.to_string())?;
+            match update.update_oneof {
+                Some(UpdateOneof::Block(block)) => {
+                    subscription.received += 1;
+                    let slot = block.slot;
+                    match convert_update(block, self.config.capture.as_ref()).await {
+                        Ok(Some(block)) => {
+                            if blocks.send(block).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                        Ok(None) => debug!("skips block {slot}"),
+                        Err(err) => warn!("failed to convert block {slot}: {err}"),
+                    }
+                }
+                // keeps load balancers from closing an idle connection
+                Some(UpdateOneof::Ping(_)) => sink
+                    .send(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(|err| err.to_string())?,
+                _ => {}
+            }
+        }
+
+        Err("subscription closed".to_string())
+    }
+}
+
+#[async_trait]
+impl BlockStream for GeyserBlockStream {
+    async fn stream(self, mut signal: Signal) -> (Receiver<Block>, JoinHandle<()>) {
+        let (tx, rx) = channel(32);
+
+        let handle = spawn(async move {
+            let mut resume = true;
+            loop {
+                // every (re)connect continues right after the last indexed slot
+                let from_slot = if resume {
+                    self.resume_slot().await
+                } else {
+                    None
+                };
+
+                let mut subscription = Subscription::default();
+                select! {
+                    result = self.subscribe(&tx, from_slot, &mut subscription) => {
+                        if let Err(err) = result {
+                            warn!("geyser subscription failed: {err}");
+                        }
+                        if tx.is_closed() {
+                            return;
+                        }
+
+                        // the node does not keep the history to resume from, continues at the tip.
+                        // slots missed in between get picked up by the slot gap audit
+                        let rejected = from_slot.is_some()
+                            && subscription.connected
+                            && subscription.received == 0;
+                        if rejected {
+                            warn!("geyser can not resume from {from_slot:?}, continues at the tip");
+                        }
+                        resume = !rejected;
+                        sleep(RECONNECT_DELAY).await;
+                    }
+                    _ = signal.recv() => {
+                        return;
+                    }
+                }
+            }
+        });
+
+        (rx, handle)
+    }
+}
+
+/// Subscribes to full blocks including all transactions, starting with the given slot if any
+pub fn request(commitment: Commitment, from_slot: Option<u64>) -> SubscribeRequest {
+    let commitment = match commitment {
+        Commitment::Processed => CommitmentLevel::Processed,
+        Commitment::Confirmed => CommitmentLevel::Confirmed,
+        Commitment::Finalized => CommitmentLevel::Finalized,
+    };
+
+    SubscribeRequest {
+        blocks: HashMap::from([(
+            "indexer".to_string(),
+            SubscribeRequestFilterBlocks {
+                include_transactions: Some(true),
+                include_accounts: Some(false),
+                include_entries: Some(false),
+                ..Default::default()
+            },
+        )]),
+        commitment: Some(commitment as i32),
+        from_slot,
+        ..Default::default()
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
-use crate::solana::indexer::IndexerRepo;
+use crate::solana::indexer::{
+    IndexerRepo, LIVE_CURSOR, SLOT_AUDIT_CURSOR, TOKEN_HOLDER_CURSOR, WALLET_SWAP_CURSOR,
+};
 use base::model::solana::{Indexer, Slot};
 use common::model::UpdatedAt;
 use common::repo::RepoResult;
@@ -11,7 +13,8 @@ use sqlx::{query, Row};
 impl IndexerRepo {
 
     pub async fn get_solana_indexer_slot(mut executor: impl AsSqlExecutor) -> RepoResult<Indexer> {
-        Ok(query("select * from solana.indexer where id = 1;")
+        Ok(query("select * from solana.indexer where id = $1;")
+            .bind(LIVE_CURSOR)
             .fetch_one(executor.as_executor())
             .await
             .map(|r| Indexer {
@@ -23,7 +26,28 @@ impl IndexerRepo {
     pub async fn get_wallet_swap_slot(
         mut executor: impl AsSqlExecutor,
     ) -> RepoResult<Option<Slot>> {
-        Ok(query("select * from solana.indexer where id = 2;")
+        Ok(query("select * from solana.indexer where id = $1;")
+            .bind(WALLET_SWAP_CURSOR)
+            .fetch_optional(executor.as_executor())
+            .await?
+            .map(|r| r.get::<Slot, _>("slot")))
+    }
+
+    pub async fn get_token_holder_slot(
+        mut executor: impl AsSqlExecutor,
+    ) -> RepoResult<Option<Slot>> {
+        Ok(query("select * from solana.indexer where id = $1;")
+            .bind(TOKEN_HOLDER_CURSOR)
+            .fetch_optional(executor.as_executor())
+            .await?
+            .map(|r| r.get::<Slot, _>("slot")))
+    }
+
+    pub async fn get_slot_audit_slot(
+        mut executor: impl AsSqlExecutor,
+    ) -> RepoResult<Option<Slot>> {
+        Ok(query("select * from solana.indexer where id = $1;")
+            .bind(SLOT_AUDIT_CURSOR)
             .fetch_optional(executor.as_executor())
             .await?
             .map(|r| r.get::<Slot, _>("slot")))
@@ -6,3 +6,12 @@ mod set;
 
 pub struct IndexerRepo {}
 
+/// Cursor of the live indexer within solana.indexer, the last slot indexed at the tip
+pub const LIVE_CURSOR: i64 = 1;
+/// Cursor of the wallet swap refresher within solana.indexer
+pub const WALLET_SWAP_CURSOR: i64 = 2;
+/// Cursor of the token holder snapshot within solana.indexer
+pub const TOKEN_HOLDER_CURSOR: i64 = 3;
+/// Cursor of the slot gap audit within solana.indexer
+pub const SLOT_AUDIT_CURSOR: i64 = 4;
+
@@ -1,23 +1,27 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
-use crate::solana::indexer::IndexerRepo;
+use crate::solana::indexer::{
+    IndexerRepo, LIVE_CURSOR, SLOT_AUDIT_CURSOR, TOKEN_HOLDER_CURSOR, WALLET_SWAP_CURSOR,
+};
 use base::model::solana::Slot;
 use common::repo::RepoResult;
 use common::sql::AsSqlExecutor;
 use sqlx::query;
 
 impl IndexerRepo {
+    /// Advances the live cursor, a block written after a later slot does not move it backwards
     pub async fn set_solana_indexer_slot(
         mut executor: impl AsSqlExecutor,
         slot: impl Into<Slot> + Send,
     ) -> RepoResult<()> {
         query(
             r#"
-insert into solana.indexer (id, slot, updated_at) values (1, $1, now())
-on conflict (id) do update set slot = $1, updated_at = now();
+insert into solana.indexer (id, slot, updated_at) values ($1, $2, now())
+on conflict (id) do update set slot = greatest(solana.indexer.slot, $2), updated_at = now();
 "#,
         )
+        .bind(LIVE_CURSOR)
         .bind(slot.into())
         .execute(executor.as_executor())
         .await?;
@@ -30,10 +34,45 @@ on conflict (id) do update set slot = $1, updated_at = now();
     ) -> RepoResult<()> {
         query(
             r#"
-insert into solana.indexer (id, slot, updated_at) values (2, $1, now())
-on conflict (id) do update set slot = $1, updated_at = now();
+insert into solana.indexer (id, slot, updated_at) values ($1, $2, now())
+on conflict (id) do update set slot = $2, updated_at = now();
 "#,
         )
+        .bind(WALLET_SWAP_CURSOR)
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_token_holder_slot(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.indexer (id, slot, updated_at) values ($1, $2, now())
+on conflict (id) do update set slot = $2, updated_at = now();
+"#,
+        )
+        .bind(TOKEN_HOLDER_CURSOR)
+        .bind(slot.into())
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_slot_audit_slot(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.indexer (id, slot, updated_at) values ($1, $2, now())
+on conflict (id) do update set slot = $2, updated_at = now();
+"#,
+        )
+        .bind(SLOT_AUDIT_CURSOR)
         .bind(slot.into())
         .execute(executor.as_executor())
         .await?;
@@ -0,0 +1,341 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::host::check_public;
+use async_trait::async_trait;
+use log::debug;
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::{Client, ClientBuilder, Response};
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use tokio::time::sleep;
+
+pub const DEFAULT_GATEWAYS: &str = "https://ipfs.io,https://dweb.link,https://gateway.pinata.cloud";
+
+/// Metadata is a few hundred bytes, anything way larger is not worth reading
+pub const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Gateways redirect to their subdomain urls, every hop gets checked like the first url
+const MAX_REDIRECTS: usize = 3;
+
+/// Off chain metadata of a token, as linked by the uri of its on chain metadata
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TokenMetadata {
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub website: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+}
+
+impl TokenMetadata {
+    /// Launchpads send empty strings for links the creator did not provide
+    pub fn normalized(self) -> Self {
+        fn non_empty(value: Option<String>) -> Option<String> {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        }
+
+        Self {
+            description: non_empty(self.description),
+            image: non_empty(self.image),
+            website: non_empty(self.website),
+            twitter: non_empty(self.twitter),
+            telegram: non_empty(self.telegram),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+    Http(String),
+    Status(u16),
+    Invalid(String),
+    /// the host is not public
+    Rejected(String),
+    TooLarge,
+    UnsupportedUri,
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Http(msg) => f.write_fmt(format_args!("request failed: {msg}")),
+            FetchError::Status(status) => f.write_fmt(format_args!("unexpected status: {status}")),
+            FetchError::Invalid(msg) => f.write_fmt(format_args!("invalid metadata: {msg}")),
+            FetchError::Rejected(msg) => f.write_fmt(format_args!("rejected: {msg}")),
+            FetchError::TooLarge => {
+                f.write_fmt(format_args!("metadata exceeds {MAX_BODY_BYTES} bytes"))
+            }
+            FetchError::UnsupportedUri => f.write_str("unsupported uri"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl FetchError {
+    /// Whether another attempt can not succeed either
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            FetchError::Rejected(_) | FetchError::TooLarge | FetchError::UnsupportedUri
+        )
+    }
+}
+
+pub type FetchResult<T> = Result<T, FetchError>;
+
+#[async_trait]
+pub trait MetadataFetcher: Send + Sync {
+    async fn fetch(&self, uri: &str) -> FetchResult<TokenMetadata>;
+}
+
+/// Fetches metadata over http, ipfs content gets requested from each gateway in order.
+/// Hosts which do not resolve to public addresses are rejected, as the uri is chosen by
+/// whoever created the token.
+pub struct HttpFetcher {
+    client: Client,
+    timeout: Duration,
+    gateways: Vec<String>,
+    retries: usize,
+    allow_private_hosts: bool,
+}
+
+impl HttpFetcher {
+    pub fn new(gateways: Vec<String>, timeout: Duration, retries: usize) -> Self {
+        Self {
+            client: client_builder(timeout).build().unwrap(),
+            timeout,
+            gateways,
+            retries,
+            allow_private_hosts: false,
+        }
+    }
+
+    /// Allows hosts within private networks, for gateways running next to the indexer
+    pub fn allow_private_hosts(mut self) -> Self {
+        self.allow_private_hosts = true;
+        self
+    }
+
+    async fn get(&self, url: &str) -> FetchResult<TokenMetadata> {
+        let mut url = url.to_string();
+
+        for _ in 0..=MAX_REDIRECTS {
+            // the request connects to the addresses which passed the check only
+            let client = if self.allow_private_hosts {
+                self.client.clone()
+            } else {
+                check_public(&url)
+                    .await
+                    .map_err(FetchError::Rejected)?
+                    .client(client_builder(self.timeout))
+                    .map_err(|err| FetchError::Http(err.to_string()))?
+            };
+
+            let mut response = client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|err| FetchError::Http(err.to_string()))?;
+
+            let status = response.status();
+            if status.is_redirection() {
+                url = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .and_then(|location| response.url().join(location).ok())
+                    .ok_or(FetchError::Status(status.as_u16()))?
+                    .to_string();
+                continue;
+            }
+
+            if !status.is_success() {
+                return Err(FetchError::Status(status.as_u16()));
+            }
+
+            let body = read_body(&mut response).await?;
+            return serde_json::from_slice::<TokenMetadata>(&body)
+                .map(TokenMetadata::normalized)
+                .map_err(|err| FetchError::Invalid(err.to_string()));
+        }
+
+        Err(FetchError::Http("too many redirects".to_string()))
+    }
+}
+
+fn client_builder(timeout: Duration) -> ClientBuilder {
+    // redirects get followed by hand, every hop is checked like the first url
+    Client::builder().timeout(timeout).redirect(Policy::none())
+}
+
+/// Reads the body up to MAX_BODY_BYTES, without trusting the announced content length
+async fn read_body(response: &mut Response) -> FetchResult<Vec<u8>> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_BODY_BYTES as u64)
+    {
+        return Err(FetchError::TooLarge);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| FetchError::Http(err.to_string()))?
+    {
+        if body.len() + chunk.len() > MAX_BODY_BYTES {
+            return Err(FetchError::TooLarge);
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+#[async_trait]
+impl MetadataFetcher for HttpFetcher {
+    async fn fetch(&self, uri: &str) -> FetchResult<TokenMetadata> {
+        let urls = resolve(uri, &self.gateways);
+        if urls.is_empty() {
+            return Err(FetchError::UnsupportedUri);
+        }
+
+        let mut last_error = FetchError::UnsupportedUri;
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                sleep(Duration::from_millis(250 * attempt as u64)).await;
+            }
+
+            for url in &urls {
+                match self.get(url).await {
+                    Ok(metadata) => return Ok(metadata),
+                    // the content is there, asking another gateway would not change a thing
+                    Err(err @ (FetchError::Invalid(_) | FetchError::TooLarge)) => return Err(err),
+                    Err(err) => {
+                        debug!("failed to fetch {url}: {err}");
+                        last_error = err;
+                    }
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+/// Urls to request the metadata from. Ipfs content gets requested from every gateway,
+/// no matter which gateway the uri points to.
+pub fn resolve(uri: &str, gateways: &[String]) -> Vec<String> {
+    let uri = uri.trim();
+
+    let path = if let Some(path) = uri.strip_prefix("ipfs://") {
+        Some(path.trim_start_matches("ipfs/"))
+    } else if uri.starts_with("http://") || uri.starts_with("https://") {
+        uri.find("/ipfs/").map(|idx| &uri[idx + "/ipfs/".len()..])
+    } else {
+        return vec![];
+    };
+
+    match path {
+        Some(path) if !path.is_empty() => gateways
+            .iter()
+            .map(|gateway| format!("{}/ipfs/{}", gateway.trim_end_matches('/'), path))
+            .collect(),
+        Some(_) => vec![],
+        None => vec![uri.to_string()],
+    }
+}
+
+/// Parses a comma separated list of gateways
+pub fn gateways(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|g| g.trim().trim_end_matches('/').to_string())
+        .filter(|g| !g.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::metadata::fetch::{gateways, resolve, TokenMetadata};
+
+    fn gws() -> Vec<String> {
+        gateways("https://ipfs.io, https://dweb.link/")
+    }
+
+    #[test]
+    fn test_gateways() {
+        assert_eq!(gws(), vec!["https://ipfs.io", "https://dweb.link"]);
+        assert!(gateways("").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_gateway_url() {
+        assert_eq!(
+            resolve(
+                "https://ipfs.io/ipfs/QmaB87CEP1pLv2A9ywboqNUMf2GH8j1Cbv8af2F81KTDEh",
+                &gws()
+            ),
+            vec![
+                "https://ipfs.io/ipfs/QmaB87CEP1pLv2A9ywboqNUMf2GH8j1Cbv8af2F81KTDEh",
+                "https://dweb.link/ipfs/QmaB87CEP1pLv2A9ywboqNUMf2GH8j1Cbv8af2F81KTDEh",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_ipfs_scheme() {
+        assert_eq!(
+            resolve("ipfs://QmTest/metadata.json", &gws()),
+            vec![
+                "https://ipfs.io/ipfs/QmTest/metadata.json",
+                "https://dweb.link/ipfs/QmTest/metadata.json",
+            ]
+        );
+        assert_eq!(
+            resolve("ipfs://ipfs/QmTest", &gws()),
+            vec![
+                "https://ipfs.io/ipfs/QmTest",
+                "https://dweb.link/ipfs/QmTest"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_plain_url() {
+        assert_eq!(
+            resolve("https://static.jup.ag/jup/metadata.json", &gws()),
+            vec!["https://static.jup.ag/jup/metadata.json"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_unsupported() {
+        assert!(resolve("ar://something", &gws()).is_empty());
+        assert!(resolve("ipfs://", &gws()).is_empty());
+        assert!(resolve("", &gws()).is_empty());
+    }
+
+    #[test]
+    fn test_normalized() {
+        let metadata = TokenMetadata {
+            description: Some("a token".to_string()),
+            image: None,
+            website: Some("".to_string()),
+            twitter: Some(" https://x.com/nyan ".to_string()),
+            telegram: Some(" ".to_string()),
+        }
+        .normalized();
+
+        assert_eq!(metadata.description, Some("a token".to_string()));
+        assert_eq!(metadata.website, None);
+        assert_eq!(metadata.twitter, Some("https://x.com/nyan".to_string()));
+        assert_eq!(metadata.telegram, None);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::metadata::fetch::{
+    gateways, resolve, FetchError, FetchResult, HttpFetcher, MetadataFetcher, TokenMetadata,
+    DEFAULT_GATEWAYS, MAX_BODY_BYTES,
+};
+pub use crate::solana::metadata::repo::{MetadataRepo, PendingMetadata};
+
+use crate::config::Config;
+use common::repo::pool::setup_pool;
+use common::repo::RepoResult;
+use common::{ResolveOr, Signal};
+use futures::future::join_all;
+use log::{debug, error, info};
+use sqlx::PgPool;
+use std::process::exit;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+mod fetch;
+mod repo;
+
+pub fn enrich_token_metadata(config: Config, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        let cfg = config.metadata.clone();
+        if !cfg.active.resolve_or(false) {
+            info!("not active");
+            return;
+        }
+
+        info!("active");
+        let pool = setup_pool(config.postgres.clone()).await;
+
+        let enricher = Enricher {
+            fetcher: HttpFetcher::new(
+                gateways(&cfg.gateways.resolve_or(DEFAULT_GATEWAYS.to_string())),
+                Duration::from_millis(cfg.timeout_ms.resolve_or(5_000usize) as u64),
+                cfg.retries.resolve_or(2usize),
+            ),
+            batch: cfg.batch.resolve_or(20usize) as i64,
+            max_attempts: cfg.max_attempts.resolve_or(5usize) as i32,
+        };
+
+        let mut interval = interval(Duration::from_secs(1));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    match enricher.enrich(&pool).await {
+                        Ok(0) => {}
+                        Ok(count) => debug!("enriched metadata of {count} tokens"),
+                        Err(err) => error!("failed to enrich token metadata: {err:?}"),
+                    }
+                }
+                _ = signal.recv() => {
+                    exit(-1);
+                }
+            }
+        }
+    })
+}
+
+pub struct Enricher<F: MetadataFetcher> {
+    pub fetcher: F,
+    /// tokens fetched concurrently per round
+    pub batch: i64,
+    /// attempts before a token gets given up on
+    pub max_attempts: i32,
+}
+
+impl<F: MetadataFetcher> Enricher<F> {
+    /// Fetches the metadata of the pending tokens and stores the result of each token on its own,
+    /// a token which can not be stored is logged and attempted again next round.
+    /// Returns the number of tokens attempted.
+    pub async fn enrich(&self, pool: &PgPool) -> RepoResult<usize> {
+        let pending = MetadataRepo::list_pending(pool, self.batch).await?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let results = join_all(pending.iter().map(|p| self.fetcher.fetch(&p.uri))).await;
+
+        for (pending, result) in pending.iter().zip(results) {
+            if let Err(err) = self.store(pool, pending, result).await {
+                error!(
+                    "failed to store metadata of token {:?}: {err:?}",
+                    pending.token
+                );
+            }
+        }
+
+        Ok(pending.len())
+    }
+
+    async fn store(
+        &self,
+        pool: &PgPool,
+        pending: &PendingMetadata,
+        result: FetchResult<TokenMetadata>,
+    ) -> RepoResult<()> {
+        let mut tx = pool.begin().await?;
+        match result {
+            Ok(metadata) => MetadataRepo::fetched(&mut *tx, pending.token, metadata).await?,
+            Err(err) => {
+                let attempts = pending.attempts + 1;
+                let retry_in = if attempts >= self.max_attempts || err.is_permanent() {
+                    None
+                } else {
+                    Some(backoff(attempts))
+                };
+                MetadataRepo::failed(&mut *tx, pending.token, err.to_string(), retry_in).await?
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Seconds to wait before the next attempt, doubles with every attempt up to an hour
+pub fn backoff(attempts: i32) -> i64 {
+    (60i64 << attempts.clamp(0, 6)).min(3_600)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::metadata::backoff;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), 120);
+        assert_eq!(backoff(2), 240);
+        assert_eq!(backoff(5), 1_920);
+        assert_eq!(backoff(6), 3_600);
+        assert_eq!(backoff(30), 3_600);
+    }
+}
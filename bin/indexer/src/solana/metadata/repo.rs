@@ -0,0 +1,130 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::metadata::fetch::TokenMetadata;
+use base::model::TokenId;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+
+#[derive(Debug, Clone)]
+pub struct PendingMetadata {
+    pub token: TokenId,
+    pub uri: String,
+    /// failed attempts so far
+    pub attempts: i32,
+}
+
+pub struct MetadataRepo {}
+
+impl MetadataRepo {
+    /// Tokens whose metadata was never fetched or is due for another attempt, the longest waiting
+    /// first - retries by the time they became due, new tokens by the time they got indexed
+    pub async fn list_pending(
+        mut executor: impl AsSqlExecutor,
+        limit: i64,
+    ) -> RepoResult<Vec<PendingMetadata>> {
+        Ok(query(
+            r#"
+select t.id, t.metadata, coalesce(tm.attempts, 0) as attempts
+from solana.token t
+left join solana.token_metadata tm on tm.token_id = t.id
+where t.metadata is not null
+  and (tm.token_id is null or (tm.status = 'FAILED' and tm.next_attempt_at <= now()))
+order by coalesce(tm.next_attempt_at, t.created_at), t.id
+limit $1;
+"#,
+        )
+        .bind(limit)
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| PendingMetadata {
+            token: r.get::<TokenId, _>("id"),
+            uri: r.get::<String, _>("metadata"),
+            attempts: r.get::<i32, _>("attempts"),
+        })
+        .collect())
+    }
+
+    pub async fn fetched(
+        mut executor: impl AsSqlExecutor,
+        token: TokenId,
+        metadata: TokenMetadata,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+update solana.token set
+    description = $2,
+    image = $3,
+    website = $4,
+    twitter = $5,
+    telegram = $6,
+    updated_at = now()
+where id = $1;
+"#,
+        )
+        .bind(token)
+        .bind(metadata.description)
+        .bind(metadata.image)
+        .bind(metadata.website)
+        .bind(metadata.twitter)
+        .bind(metadata.telegram)
+        .execute(executor.as_executor())
+        .await?;
+
+        query(
+            r#"
+insert into solana.token_metadata (token_id, status, attempts, next_attempt_at, last_error, updated_at)
+values ($1, 'FETCHED', 1, null, null, now())
+on conflict (token_id) do update set
+    status = 'FETCHED',
+    attempts = solana.token_metadata.attempts + 1,
+    next_attempt_at = null,
+    last_error = null,
+    updated_at = now();
+"#,
+        )
+        .bind(token)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt, the token gets retried after the given number of seconds
+    /// or never again if none
+    pub async fn failed(
+        mut executor: impl AsSqlExecutor,
+        token: TokenId,
+        error: String,
+        retry_in: Option<i64>,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+insert into solana.token_metadata (token_id, status, attempts, next_attempt_at, last_error, updated_at)
+values (
+    $1,
+    case when $3::int8 is null then 'DEAD' else 'FAILED' end,
+    1,
+    now() + make_interval(secs => $3::int8),
+    $2,
+    now()
+)
+on conflict (token_id) do update set
+    status = excluded.status,
+    attempts = solana.token_metadata.attempts + 1,
+    next_attempt_at = excluded.next_attempt_at,
+    last_error = excluded.last_error,
+    updated_at = now();
+"#,
+        )
+        .bind(token)
+        .bind(error)
+        .bind(retry_in)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+}
@@ -2,7 +2,12 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 use crate::config::Config;
+use crate::solana::backfill::backfill_blocks;
 use crate::solana::block::index_blocks;
+use crate::solana::fork::watch_forks;
+use crate::solana::gap::repair_slot_gaps;
+use crate::solana::metadata::enrich_token_metadata;
+use crate::solana::token_holder::refresh_token_holder;
 use crate::solana::wallet_swap::refresh_wallet_swap;
 use common::Signal;
 use futures::future::join_all;
@@ -10,8 +15,16 @@ use log::error;
 use tokio::runtime::Runtime;
 use tokio::task::JoinHandle;
 
+pub mod backfill;
 pub mod block;
+pub mod fork;
+pub mod gap;
+pub mod geyser;
 pub mod indexer;
+pub mod metadata;
+pub mod notify;
+pub mod replay;
+pub mod token_holder;
 mod wallet_swap;
 mod watchdog;
 
@@ -19,7 +32,12 @@ pub fn run_solana(runtime: Runtime, config: Config, signal: Signal) {
     runtime.block_on(async {
         let handles: Vec<JoinHandle<()>> = vec![
             index_blocks(config.clone(), signal.clone()),
+            watch_forks(config.clone(), signal.clone()),
+            backfill_blocks(config.clone(), signal.clone()),
+            repair_slot_gaps(config.clone(), signal.clone()),
             refresh_wallet_swap(config.clone(), signal.clone()),
+            refresh_token_holder(config.clone(), signal.clone()),
+            enrich_token_metadata(config.clone(), signal.clone()),
         ];
 
         for result in join_all(handles).await {
@@ -0,0 +1,62 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::query;
+
+/// Channel the indexer notifies on, once a block got indexed.
+/// The payload is a json object e.g. {"slot": 42, "token_pairs": [1, 2, 3]},
+/// token_pairs is null if too many token pairs were touched to fit into the payload.
+pub const CHANNEL_BLOCK_INDEXED: &str = "block_indexed";
+
+/// Channel the database notifies on, whenever a token completed its pumpfun bonding curve.
+/// Sent by the token_graduated trigger of solana.token_lifecycle, the payload has the same shape
+/// as a block notification.
+pub const CHANNEL_TOKEN_GRADUATED: &str = "token_graduated";
+
+/// Upper bound of token pairs sent with a notification, postgres limits the payload to 8000 bytes
+const MAX_TOKEN_PAIRS: i64 = 500;
+
+pub struct NotifyRepo {}
+
+impl NotifyRepo {
+    /// Notifies listeners about the token pairs swapped within the slot.
+    /// Postgres delivers the notification only once the surrounding transaction commits.
+    pub async fn block_indexed(
+        mut executor: impl AsSqlExecutor,
+        slot: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        query(
+            r#"
+with touched as (
+    select token_pair_id from pumpfun.swap where slot = $1
+    union
+    select token_pair_id from pumpswap.swap where slot = $1
+    union
+    select token_pair_id from pumpup.swap where slot = $1
+    union
+    select token_pair_id from jupiter.swap where slot = $1
+    union
+    select token_pair_id from meteora.swap where slot = $1
+    union
+    select token_pair_id from orca.swap where slot = $1
+)
+select pg_notify($2, json_build_object(
+    'slot', $1::int8,
+    'token_pairs', case
+        when (select count(*) from touched) > $3 then null
+        else coalesce((select json_agg(token_pair_id order by token_pair_id) from touched), '[]'::json)
+    end
+)::text);
+"#,
+        )
+        .bind(slot.into())
+        .bind(CHANNEL_BLOCK_INDEXED)
+        .bind(MAX_TOKEN_PAIRS)
+        .execute(executor.as_executor())
+        .await?;
+        Ok(())
+    }
+}
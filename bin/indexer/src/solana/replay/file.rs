@@ -0,0 +1,207 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use solana_transaction_status::UiConfirmedBlock;
+use std::fs::{create_dir_all, read_dir, rename, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// A captured block file, named after its slot e.g. 326027759.json or block_326027759.json.gz
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockFile {
+    pub slot: Slot,
+    pub path: PathBuf,
+}
+
+impl BlockFile {
+    pub fn from_path(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let stem = name
+            .strip_suffix(".json.gz")
+            .or_else(|| name.strip_suffix(".json"))?;
+
+        let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let slot = stem[stem.len() - digits..].parse::<i64>().ok()?;
+
+        Some(Self {
+            slot: Slot::from(slot),
+            path,
+        })
+    }
+
+    pub fn is_gzip(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext == "gz")
+    }
+
+    pub fn read(&self) -> Result<UiConfirmedBlock, String> {
+        let file =
+            File::open(&self.path).map_err(|err| format!("{}: {err}", self.path.display()))?;
+
+        let reader: Box<dyn Read> = if self.is_gzip() {
+            Box::new(GzDecoder::new(BufReader::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+
+        serde_json::from_reader(reader).map_err(|err| format!("{}: {err}", self.path.display()))
+    }
+}
+
+/// Lists the block files of a directory in slot order, or the file itself if path is a file
+pub fn list_block_files(path: &Path) -> Result<Vec<BlockFile>, String> {
+    if path.is_file() {
+        return BlockFile::from_path(path.to_path_buf())
+            .map(|file| vec![file])
+            .ok_or(format!("{} is not a block file", path.display()));
+    }
+
+    let mut result: Vec<BlockFile> = read_dir(path)
+        .map_err(|err| format!("{}: {err}", path.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(BlockFile::from_path)
+        .collect();
+
+    result.sort_by_key(|file| file.slot.0);
+    Ok(result)
+}
+
+/// Writes fetched blocks to disk, so that they can be replayed later on
+#[derive(Debug, Clone)]
+pub struct BlockCapture {
+    pub dir: PathBuf,
+    pub gzip: bool,
+}
+
+impl BlockCapture {
+    pub fn write(&self, slot: Slot, block: &UiConfirmedBlock) -> Result<BlockFile, String> {
+        create_dir_all(&self.dir).map_err(|err| format!("{}: {err}", self.dir.display()))?;
+
+        let name = if self.gzip {
+            format!("{}.json.gz", slot.0)
+        } else {
+            format!("{}.json", slot.0)
+        };
+        let path = self.dir.join(name);
+
+        // replays never pick up a partially written file
+        let tmp = self.dir.join(format!(".{}.tmp", slot.0));
+        let file = File::create(&tmp).map_err(|err| format!("{}: {err}", tmp.display()))?;
+
+        let result = if self.gzip {
+            let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+            serde_json::to_writer(&mut encoder, block)
+                .map_err(|err| err.to_string())
+                .and_then(|_| encoder.finish().map_err(|err| err.to_string()))
+                .and_then(|mut writer| writer.flush().map_err(|err| err.to_string()))
+        } else {
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer(&mut writer, block)
+                .map_err(|err| err.to_string())
+                .and_then(|_| writer.flush().map_err(|err| err.to_string()))
+        };
+        result.map_err(|err| format!("{}: {err}", tmp.display()))?;
+
+        rename(&tmp, &path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Ok(BlockFile { slot, path })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::replay::file::{list_block_files, BlockCapture, BlockFile};
+    use base::model::solana::Slot;
+    use solana_transaction_status::UiConfirmedBlock;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::path::PathBuf;
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("indexer-replay-{name}-{}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn block() -> UiConfirmedBlock {
+        serde_json::from_str(
+            r#"{
+    "previousBlockhash": "HUPrBhHv9TQC18vCTSRnjoi25fdep3twfAb6Shra761",
+    "blockhash": "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm",
+    "parentSlot": 326027758,
+    "transactions": [],
+    "blockTime": 1742500000,
+    "blockHeight": 304318470
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_block_file() {
+        let file = BlockFile::from_path(PathBuf::from("/tmp/326027759.json")).unwrap();
+        assert_eq!(file.slot, Slot::from(326027759));
+        assert!(!file.is_gzip());
+
+        let file = BlockFile::from_path(PathBuf::from("block_326027759.json.gz")).unwrap();
+        assert_eq!(file.slot, Slot::from(326027759));
+        assert!(file.is_gzip());
+
+        assert_eq!(BlockFile::from_path(PathBuf::from("block.json")), None);
+        assert_eq!(BlockFile::from_path(PathBuf::from("326027759.txt")), None);
+    }
+
+    #[test]
+    fn test_list_block_files() {
+        let dir = dir("list");
+        write(dir.join("326027761.json"), "{}").unwrap();
+        write(dir.join("326027759.json.gz"), "").unwrap();
+        write(dir.join("block_326027760.json"), "{}").unwrap();
+        write(dir.join("readme.md"), "").unwrap();
+
+        let slots: Vec<i64> = list_block_files(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.slot.0)
+            .collect();
+        assert_eq!(slots, vec![326027759, 326027760, 326027761]);
+
+        let single = list_block_files(&dir.join("326027761.json")).unwrap();
+        assert_eq!(single.len(), 1);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_capture_and_read() {
+        let dir = dir("capture");
+
+        for gzip in [true, false] {
+            let capture = BlockCapture {
+                dir: dir.clone(),
+                gzip,
+            };
+            let file = capture.write(Slot::from(326027759), &block()).unwrap();
+            assert_eq!(file.is_gzip(), gzip);
+
+            let read = file.read().unwrap();
+            assert_eq!(
+                read.blockhash,
+                "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm"
+            );
+            assert_eq!(read.parent_slot, 326027758);
+            assert_eq!(read.block_time, Some(1742500000));
+        }
+
+        // no temporary files remain
+        assert_eq!(list_block_files(&dir).unwrap().len(), 2);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        remove_dir_all(dir).unwrap();
+    }
+}
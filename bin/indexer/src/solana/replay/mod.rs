@@ -0,0 +1,272 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::replay::file::{list_block_files, BlockCapture, BlockFile};
+
+use crate::config::Config;
+use crate::solana::backfill::RpcBlockSource;
+use async_trait::async_trait;
+use base::model::solana::{Block, Slot};
+use common::{ResolveOr, Signal};
+use log::{info, warn};
+use solana::convert::convert_block;
+use solana::stream::BlockStream;
+use solana_transaction_status::UiConfirmedBlock;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{channel, Receiver};
+use tokio::task::{spawn_blocking, JoinHandle};
+use tokio::time::sleep;
+use tokio::{select, spawn};
+
+mod file;
+
+/// Upper bound of the wait between two blocks when replaying in realtime
+const MAX_REALTIME_WAIT: Duration = Duration::from_secs(10);
+
+/// How fast captured blocks get replayed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Pace {
+    /// as fast as the indexer consumes them
+    #[default]
+    None,
+    /// waits the time between the block times of two consecutive blocks
+    Realtime,
+    /// waits a fixed time between two blocks
+    Fixed(Duration),
+}
+
+impl FromStr for Pace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "" | "none" => Ok(Pace::None),
+            "realtime" => Ok(Pace::Realtime),
+            ms => ms
+                .parse::<u64>()
+                .map(|ms| Pace::Fixed(Duration::from_millis(ms)))
+                .map_err(|_| format!("unknown pace: {s}")),
+        }
+    }
+}
+
+impl Pace {
+    fn wait(&self, previous: Option<i64>, current: Option<i64>) -> Duration {
+        match self {
+            Pace::None => Duration::ZERO,
+            Pace::Fixed(duration) => *duration,
+            Pace::Realtime => match (previous, current) {
+                (Some(previous), Some(current)) if current > previous => {
+                    Duration::from_secs((current - previous) as u64).min(MAX_REALTIME_WAIT)
+                }
+                _ => Duration::ZERO,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReplayBlockStreamConfig {
+    pub path: PathBuf,
+    pub pace: Pace,
+}
+
+impl ReplayBlockStreamConfig {
+    pub fn load(config: &Config) -> Self {
+        Self {
+            path: PathBuf::from(config.replay.path.resolve()),
+            pace: config
+                .replay
+                .pace
+                .resolve_or("none".to_string())
+                .parse()
+                .unwrap(),
+        }
+    }
+}
+
+impl BlockCapture {
+    /// Capture configured by the replay section, none if capturing is not enabled
+    pub fn load(config: &Config) -> Option<Self> {
+        let dir = config.replay.capture.resolve_or(String::new());
+        if dir.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            dir: PathBuf::from(dir),
+            gzip: config.replay.gzip.resolve_or(true),
+        })
+    }
+
+    /// Writes the block on the blocking pool, so that streaming never waits for the disk
+    pub fn write_in_background(&self, slot: Slot, block: UiConfirmedBlock) {
+        let capture = self.clone();
+        spawn_blocking(move || {
+            if let Err(err) = capture.write(slot, &block) {
+                warn!("failed to capture block {slot:?}: {err}");
+            }
+        });
+    }
+}
+
+/// Attempts to load a streamed block for its capture, the node might not have confirmed it yet
+const CAPTURE_ATTEMPTS: usize = 3;
+
+/// Captures the blocks of a stream which hands out converted blocks only, e.g. the rpc block
+/// stream, by loading every streamed block once more with getBlock in the background.
+/// Doubles the rpc load, so it is only meant for capturing.
+pub struct CaptureBlockStream<S> {
+    inner: S,
+    source: Arc<RpcBlockSource>,
+}
+
+impl<S> CaptureBlockStream<S> {
+    /// The source must have a capture configured
+    pub fn new(inner: S, source: RpcBlockSource) -> Self {
+        Self {
+            inner,
+            source: Arc::new(source),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: BlockStream + Send + 'static> BlockStream for CaptureBlockStream<S> {
+    async fn stream(self, signal: Signal) -> (Receiver<Block>, JoinHandle<()>) {
+        let (mut blocks, inner) = self.inner.stream(signal).await;
+        let (tx, rx) = channel(8);
+        let source = self.source;
+
+        let handle = spawn(async move {
+            while let Some(block) = blocks.recv().await {
+                let source = source.clone();
+                let slot = block.slot;
+                spawn(async move {
+                    for attempt in 1..=CAPTURE_ATTEMPTS {
+                        match source.capture(slot).await {
+                            Ok(_) => return,
+                            Err(err) if attempt == CAPTURE_ATTEMPTS => {
+                                warn!("failed to capture block {slot:?}: {err}")
+                            }
+                            Err(_) => sleep(Duration::from_secs(1)).await,
+                        }
+                    }
+                });
+
+                if tx.send(block).await.is_err() {
+                    break;
+                }
+            }
+            let _ = inner.await;
+        });
+
+        (rx, handle)
+    }
+}
+
+/// Replays captured blocks from a file or directory in slot order, e.g. to reproduce a bug locally.
+/// The stream ends once all blocks got replayed.
+pub struct ReplayBlockStream {
+    config: ReplayBlockStreamConfig,
+}
+
+impl ReplayBlockStream {
+    pub fn new(config: ReplayBlockStreamConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl BlockStream for ReplayBlockStream {
+    async fn stream(self, mut signal: Signal) -> (Receiver<Block>, JoinHandle<()>) {
+        let (tx, rx) = channel(8);
+
+        let handle = spawn(async move {
+            let files = match list_block_files(&self.config.path) {
+                Ok(files) => files,
+                Err(err) => {
+                    warn!("unable to list blocks: {err}");
+                    return;
+                }
+            };
+            info!("replays {} blocks", files.len());
+
+            let mut previous_time = None;
+            for file in files {
+                let slot = file.slot;
+                let block = match spawn_blocking(move || file.read())
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|result| result)
+                {
+                    Ok(block) => block,
+                    Err(err) => {
+                        warn!("skips block {slot:?}: {err}");
+                        continue;
+                    }
+                };
+
+                let block_time = block.block_time;
+                select! {
+                    _ = sleep(self.config.pace.wait(previous_time, block_time)) => {}
+                    _ = signal.recv() => {
+                        return;
+                    }
+                }
+                previous_time = block_time.or(previous_time);
+
+                match convert_block(slot, block).await {
+                    Ok(Some(block)) => {
+                        if tx.send(block).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => info!("skips block {slot:?}"),
+                    Err(err) => warn!("failed to convert block {slot:?}: {err:?}"),
+                }
+            }
+
+            info!("replay done");
+        });
+
+        (rx, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solana::replay::Pace;
+    use std::time::Duration;
+
+    #[test]
+    fn test_pace() {
+        assert_eq!("".parse(), Ok(Pace::None));
+        assert_eq!("none".parse(), Ok(Pace::None));
+        assert_eq!("Realtime".parse(), Ok(Pace::Realtime));
+        assert_eq!("250".parse(), Ok(Pace::Fixed(Duration::from_millis(250))));
+        assert!("fast".parse::<Pace>().is_err());
+    }
+
+    #[test]
+    fn test_wait() {
+        assert_eq!(Pace::None.wait(Some(1), Some(5)), Duration::ZERO);
+        assert_eq!(
+            Pace::Fixed(Duration::from_millis(250)).wait(None, None),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            Pace::Realtime.wait(Some(1), Some(3)),
+            Duration::from_secs(2)
+        );
+        assert_eq!(Pace::Realtime.wait(None, Some(3)), Duration::ZERO);
+        assert_eq!(Pace::Realtime.wait(Some(3), Some(1)), Duration::ZERO);
+        assert_eq!(
+            Pace::Realtime.wait(Some(0), Some(60)),
+            Duration::from_secs(10)
+        );
+    }
+}
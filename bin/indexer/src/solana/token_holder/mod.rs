@@ -0,0 +1,81 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+pub use crate::solana::token_holder::repo::{
+    HolderBalanceRepo, HolderBalanceToInsert, TokenHolderRepo,
+};
+
+use crate::config::Config;
+use crate::solana::fork::ForkRepo;
+use crate::solana::indexer::IndexerRepo;
+use common::repo::pool::setup_pool;
+use common::Signal;
+use log::info;
+use sqlx::PgPool;
+use std::process::exit;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio::{select, spawn};
+
+mod repo;
+
+/// max number of slots folded into the holder snapshot per refresh
+const MAX_SLOTS: i64 = 100;
+
+pub fn refresh_token_holder(config: Config, mut signal: Signal) -> JoinHandle<()> {
+    spawn(async move {
+        info!("active");
+
+        let pool = setup_pool(config.postgres.clone()).await;
+        let mut interval = interval(Duration::from_millis(100));
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    refresh(pool.clone()).await;
+                }
+                _ = signal.recv() => {
+                    exit(-1);
+                }
+            }
+        }
+    })
+}
+
+pub async fn refresh(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    // the snapshot has to start with the first recorded balance, otherwise holders would be missing
+    let slot = match IndexerRepo::get_token_holder_slot(&mut tx).await.unwrap() {
+        Some(slot) => Some(slot),
+        None => HolderBalanceRepo::get_first_slot(&mut tx).await.unwrap(),
+    };
+
+    if let Some(slot) = slot {
+        let indexer_slot = IndexerRepo::get_solana_indexer_slot(&mut tx)
+            .await
+            .unwrap()
+            .slot;
+
+        // blocks which might still get rolled back are not folded yet
+        let indexer_slot = match ForkRepo::settled_slot(&mut tx).await.unwrap() {
+            Some(settled) if settled < indexer_slot => settled,
+            _ => indexer_slot,
+        };
+
+        // can not refresh for blocks which have not been indexed yet
+        if slot < indexer_slot {
+            let to = if indexer_slot.0 - slot.0 > MAX_SLOTS {
+                (slot.0 + MAX_SLOTS).into()
+            } else {
+                indexer_slot
+            };
+
+            TokenHolderRepo::refresh(&mut tx, slot, to).await.unwrap();
+            IndexerRepo::set_token_holder_slot(&mut tx, to)
+                .await
+                .unwrap();
+        }
+    }
+    tx.commit().await.unwrap();
+}
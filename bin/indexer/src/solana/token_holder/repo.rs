@@ -0,0 +1,177 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::model::solana::Slot;
+use base::model::{AddressId, Mint, TokenId};
+use common::repo::RepoResult;
+use common::sql::AsSqlExecutor;
+use sqlx::{query, Row};
+use std::collections::HashMap;
+
+pub struct HolderBalanceToInsert {
+    pub token: TokenId,
+    pub address: AddressId,
+    pub balance: String,
+}
+
+pub struct HolderBalanceRepo {}
+
+impl HolderBalanceRepo {
+    /// Tracked tokens for the given mints - tokens which are the base of a pair but never its quote.
+    /// Mints which are not known, and quote mints like WSOL or USDC, are left out
+    pub async fn list_known_tokens(
+        mut executor: impl AsSqlExecutor,
+        mints: Vec<Mint>,
+    ) -> RepoResult<HashMap<Mint, TokenId>> {
+        Ok(query(
+            r#"
+select t.id, t.mint
+from solana.token t
+where t.mint = any($1)
+  and exists (select 1 from solana.token_pair tp where tp.base_id = t.id)
+  and not exists (select 1 from solana.token_pair tp where tp.quote_id = t.id);
+"#,
+        )
+        .bind(mints.iter().map(|m| m.to_string()).collect::<Vec<_>>())
+        .fetch_all(executor.as_executor())
+        .await?
+        .into_iter()
+        .map(|r| {
+            (
+                Mint::from(r.get::<String, _>("mint")),
+                r.get::<TokenId, _>("id"),
+            )
+        })
+        .collect())
+    }
+
+    pub async fn insert(
+        mut executor: impl AsSqlExecutor,
+        slot: Slot,
+        balances: Vec<HolderBalanceToInsert>,
+    ) -> RepoResult<()> {
+        if balances.is_empty() {
+            return Ok(());
+        }
+
+        let mut tokens = Vec::with_capacity(balances.len());
+        let mut addresses = Vec::with_capacity(balances.len());
+        let mut amounts = Vec::with_capacity(balances.len());
+
+        for balance in balances {
+            tokens.push(balance.token.0);
+            addresses.push(balance.address.0);
+            amounts.push(balance.balance);
+        }
+
+        query(
+            r#"
+insert into solana.token_holder_balance (slot, token_id, address_id, balance)
+select $1, b.token_id, b.address_id, b.balance::numeric
+from unnest($2::int8[], $3::int8[], $4::text[]) as b(token_id, address_id, balance)
+on conflict (slot, token_id, address_id) do update set balance = excluded.balance;
+"#,
+        )
+        .bind(slot)
+        .bind(&tokens)
+        .bind(&addresses)
+        .bind(&amounts)
+        .execute(executor.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Slot right before the first recorded holder balance
+    pub async fn get_first_slot(mut executor: impl AsSqlExecutor) -> RepoResult<Option<Slot>> {
+        Ok(
+            query("select min(slot) - 1 as slot from solana.token_holder_balance;")
+                .fetch_one(executor.as_executor())
+                .await?
+                .get::<Option<Slot>, _>("slot"),
+        )
+    }
+}
+
+pub struct TokenHolderRepo {}
+
+impl TokenHolderRepo {
+    /// Folds all holder balance changes of the slots (from, to] into the current holder balances
+    /// and refreshes the holder summary of every touched token.
+    /// The folded changes are settled, so they get pruned afterwards.
+    pub async fn refresh(
+        mut executor: impl AsSqlExecutor,
+        from: impl Into<Slot> + Send,
+        to: impl Into<Slot> + Send,
+    ) -> RepoResult<()> {
+        let from = from.into();
+        let to = to.into();
+
+        query(
+            r#"
+insert into solana.token_holder (token_id, address_id, balance, slot, updated_at)
+select distinct on (token_id, address_id) token_id, address_id, balance, slot, now()
+from solana.token_holder_balance
+where slot > $1 and slot <= $2
+order by token_id, address_id, slot desc
+on conflict (token_id, address_id) do update set
+    balance = excluded.balance,
+    slot = excluded.slot,
+    updated_at = now()
+where solana.token_holder.slot <= excluded.slot;
+"#,
+        )
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        query(
+            r#"
+with touched as (
+    select distinct token_id from solana.token_holder_balance where slot > $1 and slot <= $2
+),
+ranked as (
+    select
+        h.token_id,
+        h.address_id,
+        h.balance,
+        row_number() over (partition by h.token_id order by h.balance desc) as rank
+    from solana.token_holder h
+    join touched using (token_id)
+    where h.balance > 0
+)
+insert into solana.token_holder_summary (token_id, holder_count, top_holders_balance, creator_balance, slot, updated_at)
+select
+    touched.token_id,
+    count(r.address_id),
+    coalesce(sum(r.balance) filter (where r.rank <= 10), 0),
+    coalesce(max(r.balance) filter (where r.address_id = t.creator_id), 0),
+    $2,
+    now()
+from touched
+join solana.token t on t.id = touched.token_id
+left join ranked r on r.token_id = touched.token_id
+group by touched.token_id
+on conflict (token_id) do update set
+    holder_count = excluded.holder_count,
+    top_holders_balance = excluded.top_holders_balance,
+    creator_balance = excluded.creator_balance,
+    slot = excluded.slot,
+    updated_at = now();
+"#,
+        )
+        .bind(from)
+        .bind(to)
+        .execute(executor.as_executor())
+        .await?;
+
+        query("delete from solana.token_holder_balance where slot > $1 and slot <= $2;")
+            .bind(from)
+            .bind(to)
+            .execute(executor.as_executor())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 // This file is licensed under the AGPL-3.0-or-later.
 
 use crate::config::Config;
+use crate::solana::fork::ForkRepo;
 use crate::solana::indexer::IndexerRepo;
 use base::repo::WalletSwapRepo;
 use common::repo::pool::setup_pool;
@@ -51,9 +52,10 @@ async fn refresh(pool: PgPool) {
             .await
             .unwrap()
             .slot;
+        let settled_slot = ForkRepo::settled_slot(&mut tx).await.unwrap();
 
-        // can not refresh for blocks which have not been indexed yet
-        if next_slot <= indexer_slot {
+        // can not refresh for blocks which have not been indexed yet or might still get rolled back
+        if next_slot <= indexer_slot && settled_slot.is_none_or(|settled| next_slot <= settled) {
             WalletSwapRepo::refresh(&mut tx, next_slot).await.unwrap();
 
             IndexerRepo::set_wallet_swap_slot(&mut tx, next_slot)
@@ -0,0 +1,154 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use async_trait::async_trait;
+use base::assert_sql;
+use base::model::solana::{Block, Slot};
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::backfill::{BackfillError, Backfiller, BlockSource};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Serves a single block, every other slot got skipped
+pub(crate) struct FixtureSource {
+    pub(crate) block: Block,
+    pub(crate) failures: AtomicUsize,
+}
+
+#[async_trait]
+impl BlockSource for FixtureSource {
+    async fn block(&self, slot: Slot) -> Result<Option<Block>, String> {
+        if self.failures.load(Ordering::SeqCst) > 0 {
+            self.failures.fetch_sub(1, Ordering::SeqCst);
+            return Err("rpc unavailable".to_string());
+        }
+
+        if slot == self.block.slot {
+            Ok(Some(self.block.clone()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_backfill() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        let backfiller = Backfiller {
+            state: setup(pool.clone()),
+            source: FixtureSource {
+                block,
+                failures: AtomicUsize::new(0),
+            },
+            concurrency: 2,
+        };
+
+        let backfill = backfiller
+            .run(Slot::from(326027757), Slot::from(326027761))
+            .await
+            .unwrap();
+        assert!(backfill.done);
+        assert_eq!(backfill.slot, Slot::from(326027761));
+
+        assert_sql!(&pool, "(select count(*) from solana.block) = 1");
+        assert_sql!(&pool, "(select slot from solana.block) = 326027759");
+        assert_sql!(&pool, "(select status from solana.backfill) = 'DONE'");
+        assert_sql!(&pool, "(select slot from solana.backfill) = 326027761");
+
+        // the candles of the block time get recalculated by the aggregator
+        assert_sql!(&pool, "(select count(*) from solana.aggregate_range) = 1");
+        assert_sql!(
+            &pool,
+            "(select from_timestamp from solana.aggregate_range) = (select timestamp from solana.block)"
+        );
+
+        // the live cursor is left alone
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.indexer where id = 1) = 0"
+        );
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_backfill_rerun_is_idempotent() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        let backfiller = Backfiller {
+            state: setup(pool.clone()),
+            source: FixtureSource {
+                block,
+                failures: AtomicUsize::new(0),
+            },
+            concurrency: 4,
+        };
+
+        backfiller
+            .run(Slot::from(326027758), Slot::from(326027760))
+            .await
+            .unwrap();
+        let balances: i64 = sqlx::query_scalar("select count(*) from solana.token_balance")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        // the same range again and an overlapping one
+        backfiller
+            .run(Slot::from(326027758), Slot::from(326027760))
+            .await
+            .unwrap();
+        backfiller
+            .run(Slot::from(326027700), Slot::from(326027800))
+            .await
+            .unwrap();
+
+        assert_sql!(&pool, "(select count(*) from solana.block) = 1");
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.backfill where status = 'DONE') = 2"
+        );
+        let rerun_balances: i64 = sqlx::query_scalar("select count(*) from solana.token_balance")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rerun_balances, balances);
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_backfill_resumes_after_failure() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        let backfiller = Backfiller {
+            state: setup(pool.clone()),
+            source: FixtureSource {
+                block,
+                // more than the attempts of a single run
+                failures: AtomicUsize::new(4),
+            },
+            concurrency: 1,
+        };
+
+        let result = backfiller
+            .run(Slot::from(326027757), Slot::from(326027760))
+            .await;
+        assert!(matches!(result, Err(BackfillError::Source { .. })));
+        assert_sql!(&pool, "(select status from solana.backfill) = 'RUNNING'");
+        assert_sql!(&pool, "(select count(*) from solana.block) = 0");
+
+        let backfill = backfiller
+            .run(Slot::from(326027757), Slot::from(326027760))
+            .await
+            .unwrap();
+        assert!(backfill.done);
+        assert_sql!(&pool, "(select count(*) from solana.block) = 1");
+    })
+    .await
+}
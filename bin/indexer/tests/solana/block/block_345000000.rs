@@ -0,0 +1,79 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::index_block;
+use solana::convert::convert_block;
+use sqlx::Executor;
+
+// synthesized block with a meteora dlmm swap and a meteora damm swap
+// 4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu - dlmm, buys 1500 token x for 2 sol
+// 2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL - damm, sells 3 token b for 0.007 sol
+#[test_log::test(sqlx::test)]
+async fn test_ok() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = serde_json::from_str(include_str!("./block_345000000.json")).unwrap();
+        let block = convert_block(Slot::from(345000000), block)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        tx.execute(
+            r#"
+insert into solana.address (id, address, created_at, updated_at)
+values  (0, '0000000000000000000000000000000000000000000', '2025-05-25 08:00:00.000000 +00:00', '2025-05-25 08:00:00.000000 +00:00');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, description, image, website, creator_id, block_id, block_time, created_at, updated_at)
+values  (1, 0, 'So11111111111111111111111111111111111111112', 'Wrapped SOL', 'WSOL', 9, null, null, null, null, null, null, null, null, '2025-05-25 08:00:00.000000 +00:00', '2025-05-25 08:00:00.000000 +00:00'),
+        (1000, 0, '4hshgkLhUqWe9FSnmGJ18ohtR3n8JTBhnP9Dqpxemq2K', 'Meteora X', 'MX', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-25 08:00:00.000000 +00:00', '2025-05-25 08:00:00.000000 +00:00'),
+        (1001, 0, 'AifaAxwucty36oVYn4zBqQs7ohxkw6jbm1mXNUfDBjJF', 'Meteora B', 'MB', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-25 08:00:00.000000 +00:00', '2025-05-25 08:00:00.000000 +00:00');
+"#,
+        )
+        .await
+        .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let state = setup(pool.clone());
+        index_block(state, block).await;
+
+        assert_sql!(&pool, "(select count(*) from meteora.swap) = 2");
+        assert_sql!(&pool, "(select count(*) from meteora.pool) = 2");
+
+        // dlmm
+        assert_sql!(&pool, "(select tp.base_id from meteora.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 1000");
+        assert_sql!(&pool, "(select tp.quote_id from meteora.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 1");
+        assert_sql!(&pool, "(select a.address from meteora.swap s join solana.address a on a.id = s.pool_id where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 'GLv6SYkMp6Hknb5kxhALDG5JtydaBS6CQx2pyaGjATyh'");
+        assert_sql!(&pool, "(select a.address from meteora.swap s join solana.address a on a.id = s.address_id where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 'EPfn1K3htNXSaC2ennFTcth3DMtdc64HwFdRZmWx4NJa'");
+        assert_sql!(&pool, "(select p.program from meteora.swap s join meteora.pool p on p.id = s.pool_id where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 'dlmm'");
+        assert_sql!(&pool, "(select slot from meteora.swap where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = 345000000");
+        assert_sql!(&pool, "(select amount_base from meteora.swap where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = '1500.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from meteora.swap where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = '2.000000000000'");
+        assert_sql!(&pool, "(select price from meteora.swap where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = '0.001333333333'");
+        assert_sql!(&pool, "(select is_buy from meteora.swap where signature = '4tYK28ACd64AZ6zULz5naa881hpDmx5ERCSw7T2qtGY53m1CfyhEX6SMzxvJr5awuQWojebu9nFVxdwqAvGiSNJu') = true");
+
+        // damm
+        assert_sql!(&pool, "(select tp.base_id from meteora.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = 1001");
+        assert_sql!(&pool, "(select a.address from meteora.swap s join solana.address a on a.id = s.pool_id where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = 'CGi7mrmpsr8GiudBNHCTv5hbVmnRkRW9Eqvv35hwsa92'");
+        assert_sql!(&pool, "(select a.address from meteora.swap s join solana.address a on a.id = s.address_id where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = '3Ey6LVwv3B3taMMDLE2715Gf6eG28reEErZ3F2iS5dXM'");
+        assert_sql!(&pool, "(select p.program from meteora.swap s join meteora.pool p on p.id = s.pool_id where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = 'damm'");
+        assert_sql!(&pool, "(select amount_base from meteora.swap where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = '3.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from meteora.swap where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = '0.007000000000'");
+        assert_sql!(&pool, "(select price from meteora.swap where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = '0.002333333333'");
+        assert_sql!(&pool, "(select is_buy from meteora.swap where signature = '2Cr3xBtF1p38MKHBuZLS1Di1WvNVVmMLM4UWGbrN3a7eEyuoTxQsmyJ1itXvdUeJRhjt2nXNteZKyXpnxVE7qeTL') = false");
+
+        // current price follows the last swap of each token pair
+        assert_sql!(&pool, "(select count(*) from meteora.current) = 2");
+        assert_sql!(&pool, "(select c.price from meteora.current c join solana.token_pair tp on tp.id = c.id where tp.base_id = 1001) = '0.002333333333'");
+
+        // rolling back the slot leaves no swap to restore the current price from
+        pool.execute("select solana.rollback_slot(345000000)").await.unwrap();
+        assert_sql!(&pool, "(select count(*) from meteora.swap) = 0");
+        assert_sql!(&pool, "(select count(*) from meteora.current) = 0");
+    })
+    .await
+}
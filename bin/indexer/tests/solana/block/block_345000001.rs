@@ -0,0 +1,90 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::index_block;
+use solana::convert::convert_block;
+use sqlx::Executor;
+
+// synthesized block with two orca whirlpool swaps
+// CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m - token is mint a, buys 8000 token for 2 sol, crosses tick arrays
+// 3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F - sol is mint a, sells 100 token for 0.00625 sol
+#[test_log::test(sqlx::test)]
+async fn test_ok() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = serde_json::from_str(include_str!("./block_345000001.json")).unwrap();
+        let block = convert_block(Slot::from(345000001), block)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        tx.execute(
+            r#"
+insert into solana.address (id, address, created_at, updated_at)
+values  (0, '0000000000000000000000000000000000000000000', '2025-05-26 08:00:00.000000 +00:00', '2025-05-26 08:00:00.000000 +00:00');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, description, image, website, creator_id, block_id, block_time, created_at, updated_at)
+values  (1, 0, 'So11111111111111111111111111111111111111112', 'Wrapped SOL', 'WSOL', 9, null, null, null, null, null, null, null, null, '2025-05-26 08:00:00.000000 +00:00', '2025-05-26 08:00:00.000000 +00:00'),
+        (1000, 0, 'MHqCh6gTunVHJRwJeEoYe83FHoLfd7E2Vz7apfSM3cL', 'Orca C', 'OC', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-26 08:00:00.000000 +00:00', '2025-05-26 08:00:00.000000 +00:00'),
+        (1001, 0, 'CU3QCvDdQVdq8gac927WDCpQ2DH7Gjdei8BBftQLjc8B', 'Orca D', 'OD', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-26 08:00:00.000000 +00:00', '2025-05-26 08:00:00.000000 +00:00');
+"#,
+        )
+        .await
+        .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let state = setup(pool.clone());
+        index_block(state, block).await;
+
+        assert_sql!(&pool, "(select count(*) from orca.swap) = 2");
+        assert_sql!(&pool, "(select count(*) from orca.pool) = 2");
+
+        // token is mint a
+        assert_sql!(&pool, "(select tp.base_id from orca.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = 1000");
+        assert_sql!(&pool, "(select tp.quote_id from orca.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = 1");
+        assert_sql!(&pool, "(select a.address from orca.swap s join solana.address a on a.id = s.pool_id where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = '8A4nPBaamXECFAR8UsUXL5tnR6HzpiGsTzyiJX7FxL3S'");
+        assert_sql!(&pool, "(select a.address from orca.swap s join solana.address a on a.id = s.address_id where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = 'HBLwfwYTRQu6q2curo1vHst7mQ2Brjf6vHS44HsPUcsL'");
+        assert_sql!(&pool, "(select slot from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = 345000001");
+        assert_sql!(&pool, "(select amount_base from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = '8000.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = '2.000000000000'");
+        assert_sql!(&pool, "(select price from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = '0.000250000000'");
+        assert_sql!(&pool, "(select sqrt_price from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = '9223372036854775808'");
+        assert_sql!(&pool, "(select tick_before from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = -15971");
+        assert_sql!(&pool, "(select tick_after from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = -13864");
+        assert_sql!(&pool, "(select is_buy from orca.swap where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = true");
+        assert_sql!(&pool, "(select p.inverted from orca.swap s join orca.pool p on p.id = s.pool_id where signature = 'CpMua48vqsvR8733d2NhGEFiz8RkH9DqAALf8tUQKu6JnGg2FcXEGPePmQVCD8d8XCFLJLNzEJjgxnbPpoCeP1m') = false");
+
+        // sol is mint a, the price gets inverted
+        assert_sql!(&pool, "(select tp.base_id from orca.swap s join solana.token_pair tp on tp.id = s.token_pair_id where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = 1001");
+        assert_sql!(&pool, "(select a.address from orca.swap s join solana.address a on a.id = s.pool_id where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4'");
+        assert_sql!(&pool, "(select a.address from orca.swap s join solana.address a on a.id = s.address_id where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = '9D1AuC6X6NZ8SzXJVWMct5K739SDbkmM3hTXqkxQggXT'");
+        assert_sql!(&pool, "(select amount_base from orca.swap where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = '100.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from orca.swap where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = '0.006250000000'");
+        assert_sql!(&pool, "(select price from orca.swap where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = '0.000062500000'");
+        assert_sql!(&pool, "(select tick_after from orca.swap where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = 27727");
+        assert_sql!(&pool, "(select is_buy from orca.swap where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = false");
+        assert_sql!(&pool, "(select p.inverted from orca.swap s join orca.pool p on p.id = s.pool_id where signature = '3MRm7Fb9hXz6Lv88JBnwQfrzvrojQVQHdrGVRHtcLdyiAgo7BVo276EE8x4F5pVcVGRoqoNAZSWmaDXr3m18Hw4F') = true");
+
+        // pool state follows the last swap
+        assert_sql!(&pool, "(select p.sqrt_price from orca.pool p join solana.address a on a.id = p.id where a.address = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4') = '73786976294838206464'");
+        assert_sql!(&pool, "(select p.tick_current_index from orca.pool p join solana.address a on a.id = p.id where a.address = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4') = 27727");
+        assert_sql!(&pool, "(select p.price from orca.pool p join solana.address a on a.id = p.id where a.address = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4') = '0.000062500000'");
+        assert_sql!(&pool, "(select p.updated_slot from orca.pool p join solana.address a on a.id = p.id where a.address = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4') = 345000001");
+
+        // current price follows the last swap of each token pair
+        assert_sql!(&pool, "(select count(*) from orca.current) = 2");
+        assert_sql!(&pool, "(select c.price from orca.current c join solana.token_pair tp on tp.id = c.id where tp.base_id = 1000) = '0.000250000000'");
+
+        // rolling back the slot leaves no swap to restore the pool state and current price from
+        pool.execute("select solana.rollback_slot(345000001)").await.unwrap();
+        assert_sql!(&pool, "(select count(*) from orca.swap) = 0");
+        assert_sql!(&pool, "(select count(*) from orca.current) = 0");
+        assert_sql!(&pool, "(select p.sqrt_price is null and p.updated_slot is null from orca.pool p join solana.address a on a.id = p.id where a.address = '8Hm1SY2BWk5tc3KqHfSv64Af1swvWE1cYNcALvyUSiW4')");
+    })
+    .await
+}
@@ -0,0 +1,76 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::index_block;
+use solana::convert::convert_block;
+use sqlx::Executor;
+
+// synthesized block with the lifecycle of a pumpfun token
+// 2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ - buy completing the bonding curve
+// 2jB2jBDKihaCdyqLA8NMVz69KXSsxyh9XSRz41B3rcLi3935w87EaE2A6svomiRD9gMhHW1WHBeHUjzAAD4wUN3Y - migration of the curve into a pumpswap pool
+#[test_log::test(sqlx::test)]
+async fn test_ok() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = serde_json::from_str(include_str!("./block_345000002.json")).unwrap();
+        let block = convert_block(Slot::from(345000002), block)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        tx.execute(
+            r#"
+insert into solana.address (id, address, created_at, updated_at)
+values  (0, '0000000000000000000000000000000000000000000', '2025-05-27 08:00:00.000000 +00:00', '2025-05-27 08:00:00.000000 +00:00');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, description, image, website, creator_id, block_id, block_time, created_at, updated_at)
+values  (1, 0, 'So11111111111111111111111111111111111111112', 'Wrapped SOL', 'WSOL', 9, null, null, null, null, null, null, null, null, '2025-05-27 08:00:00.000000 +00:00', '2025-05-27 08:00:00.000000 +00:00'),
+        (1000, 0, 'D91jYHvFVaAC8ngyf7G79n7C9Pc8bto4XqsZYQyBnEDd', 'Graduate', 'GRAD', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-27 08:00:00.000000 +00:00', '2025-05-27 08:00:00.000000 +00:00');
+
+insert into solana.token_pair (id, base_id, quote_id)
+values  (2000, 1000, 1);
+"#,
+        )
+        .await
+        .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let state = setup(pool.clone());
+        index_block(state, block).await;
+
+        assert_sql!(&pool, "(select count(*) from pumpfun.lifecycle) = 2");
+
+        // complete
+        assert_sql!(&pool, "(select kind from pumpfun.lifecycle where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ') = 'complete'");
+        assert_sql!(&pool, "(select token_pair_id from pumpfun.lifecycle where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ') = 2000");
+        assert_sql!(&pool, "(select slot from pumpfun.lifecycle where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ') = 345000002");
+        assert_sql!(&pool, "(select a.address from pumpfun.lifecycle l join solana.address a on a.id = l.bonding_curve_id where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ') = '4C2wuYMDCSk4FXdrQDWvxcTHAtXRDTni9UekXKH214yv'");
+        assert_sql!(&pool, "(select a.address from pumpfun.lifecycle l join solana.address a on a.id = l.address_id where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ') = 'D8x8cwZJsQZgKFNDVX1Qeu2CiLvjcgQeuK9Eki8GuAtQ'");
+        assert_sql!(&pool, "(select pool_id is null from pumpfun.lifecycle where signature = '2468c67iPBcy4a7nZHBz1oK15qYeeGnmTpht6D9MWTL42b1Jtjj6b1uqYxoCVp68V3wZGtQXzgVGMVtusVMk62zZ')");
+
+        // migrate
+        assert_sql!(&pool, "(select kind from pumpfun.lifecycle where signature = '2jB2jBDKihaCdyqLA8NMVz69KXSsxyh9XSRz41B3rcLi3935w87EaE2A6svomiRD9gMhHW1WHBeHUjzAAD4wUN3Y') = 'migrate'");
+        assert_sql!(&pool, "(select token_pair_id from pumpfun.lifecycle where signature = '2jB2jBDKihaCdyqLA8NMVz69KXSsxyh9XSRz41B3rcLi3935w87EaE2A6svomiRD9gMhHW1WHBeHUjzAAD4wUN3Y') = 2000");
+        assert_sql!(&pool, "(select a.address from pumpfun.lifecycle l join solana.address a on a.id = l.address_id where signature = '2jB2jBDKihaCdyqLA8NMVz69KXSsxyh9XSRz41B3rcLi3935w87EaE2A6svomiRD9gMhHW1WHBeHUjzAAD4wUN3Y') = '9mtdqfm1v3hbM8ULrjdR8igVzMUNgB6tp8UKWX4wCg1J'");
+        assert_sql!(&pool, "(select a.address from pumpfun.lifecycle l join solana.address a on a.id = l.pool_id where signature = '2jB2jBDKihaCdyqLA8NMVz69KXSsxyh9XSRz41B3rcLi3935w87EaE2A6svomiRD9gMhHW1WHBeHUjzAAD4wUN3Y') = '4xY6dLxCs8DoDe9WCQgjBQL9ynJh327cnWoESTs7oMh1'");
+
+        // the token graduated and its pumpswap pool is linked to the pumpfun token pair
+        assert_sql!(&pool, "(select count(*) from solana.token_lifecycle) = 1");
+        assert_sql!(&pool, "(select pumpfun_token_pair_id from solana.token_lifecycle where token_id = 1000) = 2000");
+        assert_sql!(&pool, "(select pumpswap_token_pair_id from solana.token_lifecycle where token_id = 1000) = 2000");
+        assert_sql!(&pool, "(select graduated_slot from solana.token_lifecycle where token_id = 1000) = 345000002");
+        assert_sql!(&pool, "(select migrated_slot from solana.token_lifecycle where token_id = 1000) = 345000002");
+        assert_sql!(&pool, "(select a.address from solana.token_lifecycle tl join solana.address a on a.id = tl.pumpswap_pool_id where token_id = 1000) = '4xY6dLxCs8DoDe9WCQgjBQL9ynJh327cnWoESTs7oMh1'");
+
+        // rolling back the slot reverts the lifecycle of the token
+        pool.execute("select solana.rollback_slot(345000002)").await.unwrap();
+        assert_sql!(&pool, "(select count(*) from pumpfun.lifecycle) = 0");
+        assert_sql!(&pool, "(select count(*) from solana.token_lifecycle) = 0");
+    })
+    .await
+}
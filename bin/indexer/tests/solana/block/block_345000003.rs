@@ -0,0 +1,81 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::index_block;
+use solana::convert::convert_block;
+use sqlx::Executor;
+
+// synthesized block with the liquidity of a pumpswap pool
+// jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ - pool created with 200k tokens and 80 sol
+// 5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j - deposit of 1000 lp tokens
+// rEvmx6UFToRKtueHxGwCgGkPNtDqP8vJAUSRhn1m6USGohsuLVcdBLLez7hB98Mf3iSdjRJSeSei1qxNtc8izxF - withdrawal of 2500 lp tokens by the creator
+#[test_log::test(sqlx::test)]
+async fn test_ok() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = serde_json::from_str(include_str!("./block_345000003.json")).unwrap();
+        let block = convert_block(Slot::from(345000003), block)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        tx.execute(
+            r#"
+insert into solana.address (id, address, created_at, updated_at)
+values  (0, '0000000000000000000000000000000000000000000', '2025-05-28 08:00:00.000000 +00:00', '2025-05-28 08:00:00.000000 +00:00');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, description, image, website, creator_id, block_id, block_time, created_at, updated_at)
+values  (1, 0, 'So11111111111111111111111111111111111111112', 'Wrapped SOL', 'WSOL', 9, null, null, null, null, null, null, null, null, '2025-05-28 08:00:00.000000 +00:00', '2025-05-28 08:00:00.000000 +00:00'),
+        (1000, 0, '513vgiWR1dkvL9VaTVXdJzu3pPzEnYKD1P8zgJPW1wkU', 'Liquid', 'LIQ', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-28 08:00:00.000000 +00:00', '2025-05-28 08:00:00.000000 +00:00');
+
+insert into solana.token_pair (id, base_id, quote_id)
+values  (2000, 1000, 1);
+"#,
+        )
+        .await
+        .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let state = setup(pool.clone());
+        index_block(state, block).await;
+
+        assert_sql!(&pool, "(select count(*) from pumpswap.liquidity) = 3");
+        assert_sql!(&pool, "(select count(distinct token_pair_id) from pumpswap.liquidity) = 1");
+        assert_sql!(&pool, "(select token_pair_id from pumpswap.liquidity limit 1) = 2000");
+        assert_sql!(&pool, "(select a.address from pumpswap.liquidity l join solana.address a on a.id = l.pool_id limit 1) = 'EBfpm5BkmHZLy6kkoLKnZCvFyiHR1KF5isSxLHsnCf7r'");
+
+        // create pool
+        assert_sql!(&pool, "(select kind from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = 'create_pool'");
+        assert_sql!(&pool, "(select slot from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = 345000003");
+        assert_sql!(&pool, "(select a.address from pumpswap.liquidity l join solana.address a on a.id = l.address_id where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = 'FPu8tpwDoYtDdYpRswoPScdFE64YE5JVy5xvhg5J3PEG'");
+        assert_sql!(&pool, "(select amount_base from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = '200000.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = '80.000000000000'");
+        assert_sql!(&pool, "(select lp_amount from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = 4000000000000");
+        assert_sql!(&pool, "(select lp_supply from pumpswap.liquidity where signature = 'jHjWMus9eiACG7n8ECyfYzVoexbvGyx1gwNS9XMJapYcYc7hFvMEHZJZ34GrzAwvFgYU2NU3H7HMQSssK5Ne3cQ') = 4000000000000");
+
+        // deposit
+        assert_sql!(&pool, "(select kind from pumpswap.liquidity where signature = '5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j') = 'deposit'");
+        assert_sql!(&pool, "(select a.address from pumpswap.liquidity l join solana.address a on a.id = l.address_id where signature = '5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j') = 'FhXQBGqHFq7WB2astrVsXW3zgQYMz5ry1bAw92ARczn1'");
+        assert_sql!(&pool, "(select amount_quote from pumpswap.liquidity where signature = '5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j') = '20.000000000000'");
+        assert_sql!(&pool, "(select lp_supply from pumpswap.liquidity where signature = '5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j') = 5000000000000");
+        assert_sql!(&pool, "(select quote_reserves from pumpswap.liquidity where signature = '5hujKcezUNkC38dWnrWXUVV8NWESRA6r4J4QoZMMe4xt9fbzAFH9LWvBVG55paKFhG2zp5mnqmg2xVkDj8HncW4j') = '100.000000000000'");
+
+        // withdraw
+        assert_sql!(&pool, "(select kind from pumpswap.liquidity where signature = 'rEvmx6UFToRKtueHxGwCgGkPNtDqP8vJAUSRhn1m6USGohsuLVcdBLLez7hB98Mf3iSdjRJSeSei1qxNtc8izxF') = 'withdraw'");
+        assert_sql!(&pool, "(select amount_base from pumpswap.liquidity where signature = 'rEvmx6UFToRKtueHxGwCgGkPNtDqP8vJAUSRhn1m6USGohsuLVcdBLLez7hB98Mf3iSdjRJSeSei1qxNtc8izxF') = '125000.000000000000'");
+        assert_sql!(&pool, "(select amount_quote from pumpswap.liquidity where signature = 'rEvmx6UFToRKtueHxGwCgGkPNtDqP8vJAUSRhn1m6USGohsuLVcdBLLez7hB98Mf3iSdjRJSeSei1qxNtc8izxF') = '50.000000000000'");
+        assert_sql!(&pool, "(select lp_amount from pumpswap.liquidity where signature = 'rEvmx6UFToRKtueHxGwCgGkPNtDqP8vJAUSRhn1m6USGohsuLVcdBLLez7hB98Mf3iSdjRJSeSei1qxNtc8izxF') = 2500000000000");
+
+        // the lp supply of the pool is the one after the withdrawal
+        assert_sql!(&pool, "(select count(*) from pumpswap.lp_supply) = 1");
+        assert_sql!(&pool, "(select lp_supply from pumpswap.lp_supply) = 2500000000000");
+        assert_sql!(&pool, "(select base_reserves from pumpswap.lp_supply) = '125000.000000000000'");
+        assert_sql!(&pool, "(select quote_reserves from pumpswap.lp_supply) = '50.000000000000'");
+    })
+    .await
+}
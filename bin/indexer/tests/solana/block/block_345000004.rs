@@ -0,0 +1,81 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::index_block;
+use solana::convert::convert_block;
+use sqlx::Executor;
+
+// synthesized block with failed transactions
+// 3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q - pumpfun buy failing on slippage
+// 33q5E5LAtgJx4Foj9tAHEv4QbFkMsDduQmHwmJvoMKsG1reyToyc8wnHK5tUkq1Lh8GAwNZu6APvztNhfDmq7vMx - retry of the same buy by the same wallet
+// 3XZAtR4qEH6wd651rgH4AtBiZVFXnSAfrCCsSxEV8eQ49Sb94pwE7w5Ku3hL7eFuG2u3gAWmzGoBoscFur2oqCN - pumpfun buy of another wallet failing
+// 3QNGys6MonYzNT6V32vmjiZJX5rx3oCQD2dfRiekr65HxG3TuTjdwn9nAJ3dhNLm3RBxeZkyA7DPQ5uUBbsRpW59 - pumpfun sell failing on slippage
+// 4ccaksYyhf2hKLZvZfSgSfVvJG6H1qq6H4VLAXMMUq2uoQuWaPMzXtCydQ4zPJnTHHRDoz89ZJWWvLQWg2opxQys - orca swap failing on slippage
+// 5XAY6qUvdvErnDTypN6TToUCJk7Nurcu47bjZzEEDpj2XqZ4gcwuWbqLFZFAdqyvP5jtLFrFYSXS1kKeAHxdeqZ7 - unsupported program failing
+#[test_log::test(sqlx::test)]
+async fn test_ok() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = serde_json::from_str(include_str!("./block_345000004.json")).unwrap();
+        let block = convert_block(Slot::from(345000004), block)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        tx.execute(
+            r#"
+insert into solana.address (id, address, created_at, updated_at)
+values  (0, '0000000000000000000000000000000000000000000', '2025-05-29 08:00:00.000000 +00:00', '2025-05-29 08:00:00.000000 +00:00');
+
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, description, image, website, creator_id, block_id, block_time, created_at, updated_at)
+values  (1, 0, 'So11111111111111111111111111111111111111112', 'Wrapped SOL', 'WSOL', 9, null, null, null, null, null, null, null, null, '2025-05-29 08:00:00.000000 +00:00', '2025-05-29 08:00:00.000000 +00:00'),
+        (1000, 0, '3BtVDtN8Hgr3yMu69Hm2gB51hFhT8XWWuGM6jzLCRwyh', 'Sniped', 'SNIPE', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-29 08:00:00.000000 +00:00', '2025-05-29 08:00:00.000000 +00:00'),
+        (1001, 0, '3NBAgEdRW3q4nkftMUr7kcgS1X8jdkk9WjJZggS8GtLd', 'Whirl', 'WHIRL', 6, 1000000000.000000000000, null, null, null, null, null, null, null, '2025-05-29 08:00:00.000000 +00:00', '2025-05-29 08:00:00.000000 +00:00');
+
+insert into solana.token_pair (id, base_id, quote_id)
+values  (2000, 1000, 1);
+"#,
+        )
+        .await
+        .unwrap();
+
+        tx.commit().await.unwrap();
+
+        let state = setup(pool.clone());
+        index_block(state, block).await;
+
+        assert_sql!(&pool, "(select count(*) from solana.failed_transaction) = 4");
+        assert_sql!(&pool, "(select count(*) from solana.failed_transaction where signature = '5XAY6qUvdvErnDTypN6TToUCJk7Nurcu47bjZzEEDpj2XqZ4gcwuWbqLFZFAdqyvP5jtLFrFYSXS1kKeAHxdeqZ7') = 0");
+        // retries of a wallet count once
+        assert_sql!(&pool, "(select count(*) from solana.failed_transaction where signature = '33q5E5LAtgJx4Foj9tAHEv4QbFkMsDduQmHwmJvoMKsG1reyToyc8wnHK5tUkq1Lh8GAwNZu6APvztNhfDmq7vMx') = 0");
+        assert_sql!(&pool, "(select count(*) from solana.failed_transaction where slot = 345000004) = 4");
+
+        // pumpfun buys and sells, decoded from their instructions
+        assert_sql!(&pool, "(select venue from solana.failed_transaction where signature = '3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q') = 'pumpfun'");
+        assert_sql!(&pool, "(select token_pair_id from solana.failed_transaction where signature = '3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q') = 2000");
+        assert_sql!(&pool, "(select is_buy from solana.failed_transaction where signature = '3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q')");
+        assert_sql!(&pool, "(select error from solana.failed_transaction where signature = '3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q') = 'TooMuchSolRequired'");
+        assert_sql!(&pool, "(select a.address from solana.failed_transaction f join solana.address a on a.id = f.address_id where signature = '3UGBZtkav6ZsnKsqZPFpmfVjJ6WY3jbSfexmAbgN37cAet15VkupEyNhTgQniyzmWWEtSwzxFp9tijGc74WL623Q') = 'HQrjAcBfATZPaUhMwKSS94A4Qg1MWjupx1qHDvtAztsz'");
+
+        assert_sql!(&pool, "(select error from solana.failed_transaction where signature = '3XZAtR4qEH6wd651rgH4AtBiZVFXnSAfrCCsSxEV8eQ49Sb94pwE7w5Ku3hL7eFuG2u3gAWmzGoBoscFur2oqCN') = 'custom program error: 0x1'");
+        assert_sql!(&pool, "(select a.address from solana.failed_transaction f join solana.address a on a.id = f.address_id where signature = '3XZAtR4qEH6wd651rgH4AtBiZVFXnSAfrCCsSxEV8eQ49Sb94pwE7w5Ku3hL7eFuG2u3gAWmzGoBoscFur2oqCN') = '5MSmGt9A7KRLMVCMwMrZWoQFqhnqx2AGy9geKxEDHVd4'");
+
+        assert_sql!(&pool, "(select not is_buy from solana.failed_transaction where signature = '3QNGys6MonYzNT6V32vmjiZJX5rx3oCQD2dfRiekr65HxG3TuTjdwn9nAJ3dhNLm3RBxeZkyA7DPQ5uUBbsRpW59')");
+        assert_sql!(&pool, "(select error from solana.failed_transaction where signature = '3QNGys6MonYzNT6V32vmjiZJX5rx3oCQD2dfRiekr65HxG3TuTjdwn9nAJ3dhNLm3RBxeZkyA7DPQ5uUBbsRpW59') = 'TooLittleSolReceived'");
+
+        // orca does not decode failed swaps, the token is taken from the token balances
+        assert_sql!(&pool, "(select venue from solana.failed_transaction where signature = '4ccaksYyhf2hKLZvZfSgSfVvJG6H1qq6H4VLAXMMUq2uoQuWaPMzXtCydQ4zPJnTHHRDoz89ZJWWvLQWg2opxQys') = 'orca'");
+        assert_sql!(&pool, "(select is_buy is null from solana.failed_transaction where signature = '4ccaksYyhf2hKLZvZfSgSfVvJG6H1qq6H4VLAXMMUq2uoQuWaPMzXtCydQ4zPJnTHHRDoz89ZJWWvLQWg2opxQys')");
+        assert_sql!(&pool, "(select error from solana.failed_transaction where signature = '4ccaksYyhf2hKLZvZfSgSfVvJG6H1qq6H4VLAXMMUq2uoQuWaPMzXtCydQ4zPJnTHHRDoz89ZJWWvLQWg2opxQys') = 'exceeds desired slippage limit'");
+        assert_sql!(&pool, "(select tp.base_id from solana.failed_transaction f join solana.token_pair tp on tp.id = f.token_pair_id where signature = '4ccaksYyhf2hKLZvZfSgSfVvJG6H1qq6H4VLAXMMUq2uoQuWaPMzXtCydQ4zPJnTHHRDoz89ZJWWvLQWg2opxQys') = 1001");
+
+        // failed transactions are not swaps
+        assert_sql!(&pool, "(select count(*) from pumpfun.swap) = 0");
+        assert_sql!(&pool, "(select count(*) from orca.swap) = 0");
+    })
+    .await
+}
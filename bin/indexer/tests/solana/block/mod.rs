@@ -1,9 +1,10 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
-use base::repo::{AddressRepo, BlockRepo, TokenBalanceRepo, TokenRepo, WalletRepo};
+use base::repo::{AddressRepo, BlockRepo, TokenBalanceRepo, TokenPairRepo, TokenRepo, WalletRepo};
 use base::test::NeverCalledTokenInfoLoader;
 use indexer::solana::block::state::{State, StateInner};
+use indexer::solana::block::venue::{VenuePrograms, VenueRegistry};
 use solana::pumpswap::repo::NeverCalledPoolInfoLoader;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -19,6 +20,11 @@ mod block_329064506;
 mod block_334886841;
 mod block_334958000;
 mod block_336313076;
+mod block_345000000;
+mod block_345000001;
+mod block_345000002;
+mod block_345000003;
+mod block_345000004;
 
 pub(crate) fn setup(pool: PgPool) -> State {
     let token_repo = TokenRepo::testing_read_only();
@@ -40,6 +46,7 @@ pub(crate) fn setup(pool: PgPool) -> State {
     State(Arc::new(StateInner {
         block_repo: BlockRepo::new(),
         token_repo: token_repo.clone(),
+        token_pair_repo: TokenPairRepo::new(token_repo.clone()),
         address_repo: AddressRepo::new(),
         token_balance_repo: TokenBalanceRepo::new(),
         wallet_repo: WalletRepo::new_no_secret(),
@@ -48,6 +55,7 @@ pub(crate) fn setup(pool: PgPool) -> State {
         pumpswap_swap_repo,
         pumpup_swap_repo,
         jupiter_swap_repo,
+        venues: VenueRegistry::new(VenuePrograms::default()),
     }))
 }
 
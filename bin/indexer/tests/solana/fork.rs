@@ -0,0 +1,139 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use crate::solana::gap::ConfirmedSlots;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::{index_block_with_cursor, Cursor};
+use indexer::solana::fork::{Commitment, ForkWatcher};
+use sqlx::Executor;
+
+#[test_log::test(sqlx::test)]
+async fn test_abandoned_fork_gets_rolled_back() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+        index_block_with_cursor(
+            setup(pool.clone()),
+            block,
+            Cursor::Live(Commitment::Processed),
+        )
+        .await;
+
+        assert_sql!(&pool, "(select count(*) from solana.block_pending) = 1");
+        assert_sql!(&pool, "(select count(*) from solana.block) = 1");
+        assert_sql!(&pool, "(select count(*) from solana.token_balance) > 0");
+
+        let watcher = ForkWatcher {
+            pool: pool.clone(),
+            // the cluster moved on without 326027759
+            source: ConfirmedSlots(vec![326027760]),
+        };
+
+        let settlement = watcher.watch().await.unwrap();
+        assert!(settlement.confirmed.is_empty());
+        assert_eq!(settlement.abandoned, vec![Slot::from(326027759)]);
+
+        assert_sql!(&pool, "(select count(*) from solana.block_pending) = 0");
+        assert_sql!(&pool, "(select count(*) from solana.block) = 0");
+        assert_sql!(&pool, "(select count(*) from solana.token_balance) = 0");
+        assert_sql!(&pool, "(select count(*) from pumpfun.swap) = 0");
+        // none of the curves got swapped before, their current state goes with the swaps
+        assert_sql!(&pool, "(select count(*) from pumpfun.current) = 0");
+        assert_sql!(&pool, "(select count(*) from solana.token_revision) = 0");
+        assert_sql!(
+            &pool,
+            "(select slot from solana.block_rollback) = 326027759 and (select rows from solana.block_rollback) > 0"
+        );
+
+        // candles get recalculated from the block time of the rolled back block on
+        assert_sql!(
+            &pool,
+            "(select from_timestamp from solana.aggregate_range) = (select timestamp from solana.block_rollback)"
+        );
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_confirmed_block_is_kept() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+        index_block_with_cursor(
+            setup(pool.clone()),
+            block,
+            Cursor::Live(Commitment::Processed),
+        )
+        .await;
+
+        let watcher = ForkWatcher {
+            pool: pool.clone(),
+            source: ConfirmedSlots(vec![326027759, 326027760]),
+        };
+
+        let settlement = watcher.watch().await.unwrap();
+        assert_eq!(settlement.confirmed, vec![Slot::from(326027759)]);
+        assert!(settlement.abandoned.is_empty());
+
+        assert_sql!(&pool, "(select count(*) from solana.block_pending) = 0");
+        assert_sql!(&pool, "(select count(*) from solana.block) = 1");
+        assert_sql!(&pool, "(select count(*) from solana.block_rollback) = 0");
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_confirmed_commitment_is_not_pending() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+        index_block_with_cursor(
+            setup(pool.clone()),
+            block,
+            Cursor::Live(Commitment::Confirmed),
+        )
+        .await;
+
+        assert_sql!(&pool, "(select count(*) from solana.block_pending) = 0");
+        assert_sql!(
+            &pool,
+            "(select slot from solana.indexer where id = 1) = 326027759"
+        );
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_rollback_restores_token_creator() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+insert into solana.address (id, address) values (1, 'Gb4ZDCisirfKPRxKDXqXSVwSJHv9sV415Ew3zN55UaFw'), (2, 'D8x8cwZJsQZgKFNDVX1Qeu2CiLvjcgQeuK9Eki8GuAtQ');
+
+insert into solana.token (id, mint, name, symbol, decimals, supply, creator_id, block_id, block_time) values
+    (1000, 'CniPCE4b3s8gSUPhUiyMjXnytrEqUrMfSsnbBjLCpump', 'PWEASE', 'pwease', 6, 1000000000, 1, 326027700, '2025-03-10 12:00:00 +00:00'),
+    (1001, 'CPBQqugWWCcyehezHf4uSJtET2kmYNqPuhyb57H5pump', 'GREED 3', 'GREED 3', 6, 1000000000, null, null, null);
+
+insert into solana.block_pending (slot) values (326027759);
+
+-- the abandoned slot claims both tokens
+update solana.token set creator_id = 2, block_id = 326027759, block_time = '2025-03-10 12:05:00 +00:00';
+"#,
+        )
+        .await
+        .unwrap();
+
+        assert_sql!(&pool, "(select count(*) from solana.token_revision) = 2");
+
+        pool.execute("select solana.rollback_slot(326027759)")
+            .await
+            .unwrap();
+
+        assert_sql!(&pool, "(select creator_id from solana.token where id = 1000) = 1");
+        assert_sql!(&pool, "(select block_id from solana.token where id = 1000) = 326027700");
+        assert_sql!(&pool, "(select creator_id is null and block_id is null from solana.token where id = 1001)");
+        assert_sql!(&pool, "(select count(*) from solana.token_revision) = 0");
+    })
+    .await
+}
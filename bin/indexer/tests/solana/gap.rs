@@ -0,0 +1,128 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::backfill::FixtureSource;
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use async_trait::async_trait;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::backfill::Backfiller;
+use indexer::solana::gap::{Auditor, Repairer, SlotSource};
+use indexer::solana::indexer::IndexerRepo;
+use std::sync::atomic::AtomicUsize;
+
+/// Knows which slots of a fixed set got confirmed
+pub(crate) struct ConfirmedSlots(pub(crate) Vec<i64>);
+
+#[async_trait]
+impl SlotSource for ConfirmedSlots {
+    async fn confirmed_slots(&self, from: Slot, to: Slot) -> Result<Vec<Slot>, String> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|slot| **slot >= from.0 && **slot <= to.0)
+            .copied()
+            .map(Slot::from)
+            .collect())
+    }
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_audit_and_repair() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        IndexerRepo::set_solana_indexer_slot(&pool, Slot::from(326028000))
+            .await
+            .unwrap();
+        IndexerRepo::set_slot_audit_slot(&pool, Slot::from(326027749))
+            .await
+            .unwrap();
+
+        let auditor = Auditor {
+            pool: pool.clone(),
+            // the block source does not know 326027755 either
+            source: ConfirmedSlots(vec![326027755, 326027759]),
+            window: 20,
+        };
+
+        let audit = auditor.audit().await.unwrap().unwrap();
+        assert_eq!(audit.confirmed, 2);
+        assert_eq!(audit.skipped, 18);
+        assert_eq!(
+            audit.missing,
+            vec![(326027755, 326027755), (326027759, 326027759)]
+        );
+
+        assert_sql!(
+            &pool,
+            "(select slot from solana.indexer where id = 4) = 326027769"
+        );
+        assert_sql!(&pool, "(select missing from solana.slot_audit) = 2");
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.slot_gap where status = 'QUEUED') = 2"
+        );
+
+        let repairer = Repairer {
+            backfiller: Backfiller {
+                state: setup(pool.clone()),
+                source: FixtureSource {
+                    block,
+                    failures: AtomicUsize::new(0),
+                },
+                concurrency: 1,
+            },
+            batch: 10,
+        };
+
+        assert_eq!(repairer.repair().await.unwrap(), 2);
+
+        assert_sql!(&pool, "(select slot from solana.block) = 326027759");
+        assert_sql!(
+            &pool,
+            "(select status from solana.slot_gap where from_slot = 326027759) = 'REPAIRED'"
+        );
+        assert_sql!(
+            &pool,
+            "(select status from solana.slot_gap where from_slot = 326027755) = 'FAILED'"
+        );
+
+        // the live cursor is left alone
+        assert_sql!(
+            &pool,
+            "(select slot from solana.indexer where id = 1) = 326028000"
+        );
+
+        // repairs do not show up as backfills
+        assert_sql!(&pool, "(select count(*) from solana.backfill) = 0");
+
+        // nothing left to repair, the next window finds the repaired slot
+        assert_eq!(repairer.repair().await.unwrap(), 0);
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_audit_stays_behind_live_indexer() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        IndexerRepo::set_solana_indexer_slot(&pool, Slot::from(1_000))
+            .await
+            .unwrap();
+        IndexerRepo::set_slot_audit_slot(&pool, Slot::from(900))
+            .await
+            .unwrap();
+
+        let auditor = Auditor {
+            pool: pool.clone(),
+            source: ConfirmedSlots(vec![]),
+            window: 1_000,
+        };
+
+        assert_eq!(auditor.audit().await.unwrap(), None);
+        assert_sql!(&pool, "(select count(*) from solana.slot_audit) = 0");
+    })
+    .await
+}
@@ -0,0 +1,290 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use common::Signal;
+use futures::stream::{iter, pending, Stream};
+use futures::StreamExt;
+use indexer::solana::block::index_block;
+use indexer::solana::fork::Commitment;
+use indexer::solana::geyser::{GeyserBlockStream, GeyserBlockStreamConfig};
+use indexer::solana::indexer::IndexerRepo;
+use solana::stream::BlockStream;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::spawn;
+use tokio::time::timeout;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status, Streaming};
+use yellowstone_grpc_proto::geyser::geyser_server::{Geyser, GeyserServer};
+use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, CompiledInstruction, GetBlockHeightRequest, GetBlockHeightResponse,
+    GetLatestBlockhashRequest, GetLatestBlockhashResponse, GetSlotRequest, GetSlotResponse,
+    GetVersionRequest, GetVersionResponse, IsBlockhashValidRequest, IsBlockhashValidResponse,
+    Message, MessageHeader, PingRequest, PongResponse, SubscribeRequest, SubscribeUpdate,
+    SubscribeUpdateBlock, SubscribeUpdatePing, SubscribeUpdateTransactionInfo, Transaction,
+    TransactionStatusMeta, UnixTimestamp,
+};
+
+/// Replays a fixed list of updates to every subscriber and remembers what got requested
+#[derive(Clone, Default)]
+struct FakeGeyser {
+    updates: Vec<SubscribeUpdate>,
+    requests: Arc<Mutex<Vec<SubscribeRequest>>>,
+}
+
+type UpdateStream = Pin<Box<dyn Stream<Item = Result<SubscribeUpdate, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Geyser for FakeGeyser {
+    type SubscribeStream = UpdateStream;
+
+    async fn subscribe(
+        &self,
+        request: Request<Streaming<SubscribeRequest>>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let mut requests = request.into_inner();
+        let first = requests
+            .message()
+            .await?
+            .ok_or(Status::invalid_argument("subscribe request expected"))?;
+        self.requests.lock().unwrap().push(first);
+
+        // keeps the subscription open once all updates got sent
+        let updates = iter(self.updates.clone().into_iter().map(Ok)).chain(pending());
+        Ok(Response::new(Box::pin(updates)))
+    }
+
+    async fn ping(&self, _: Request<PingRequest>) -> Result<Response<PongResponse>, Status> {
+        Err(Status::unimplemented("ping"))
+    }
+
+    async fn get_latest_blockhash(
+        &self,
+        _: Request<GetLatestBlockhashRequest>,
+    ) -> Result<Response<GetLatestBlockhashResponse>, Status> {
+        Err(Status::unimplemented("get_latest_blockhash"))
+    }
+
+    async fn get_block_height(
+        &self,
+        _: Request<GetBlockHeightRequest>,
+    ) -> Result<Response<GetBlockHeightResponse>, Status> {
+        Err(Status::unimplemented("get_block_height"))
+    }
+
+    async fn get_slot(
+        &self,
+        _: Request<GetSlotRequest>,
+    ) -> Result<Response<GetSlotResponse>, Status> {
+        Err(Status::unimplemented("get_slot"))
+    }
+
+    async fn is_blockhash_valid(
+        &self,
+        _: Request<IsBlockhashValidRequest>,
+    ) -> Result<Response<IsBlockhashValidResponse>, Status> {
+        Err(Status::unimplemented("is_blockhash_valid"))
+    }
+
+    async fn get_version(
+        &self,
+        _: Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        Err(Status::unimplemented("get_version"))
+    }
+}
+
+async fn serve(geyser: FakeGeyser) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    spawn(async move {
+        Server::builder()
+            .add_service(GeyserServer::new(geyser))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+    addr
+}
+
+fn block(slot: u64, blockhash: &str, signature: u8) -> SubscribeUpdate {
+    SubscribeUpdate {
+        filters: vec!["indexer".to_string()],
+        update_oneof: Some(UpdateOneof::Block(SubscribeUpdateBlock {
+            slot,
+            blockhash: blockhash.to_string(),
+            parent_slot: slot - 1,
+            parent_blockhash: "HUPrBhHv9TQC18vCTSRnjoi25fdep3twfAb6Shra761".to_string(),
+            block_time: Some(UnixTimestamp {
+                timestamp: 1742500000 + slot as i64 - 326027759,
+            }),
+            transactions: vec![SubscribeUpdateTransactionInfo {
+                signature: vec![signature; 64],
+                is_vote: false,
+                transaction: Some(Transaction {
+                    signatures: vec![vec![signature; 64]],
+                    message: Some(Message {
+                        header: Some(MessageHeader {
+                            num_required_signatures: 1,
+                            num_readonly_signed_accounts: 0,
+                            num_readonly_unsigned_accounts: 1,
+                        }),
+                        account_keys: vec![vec![signature; 32], vec![0; 32]],
+                        recent_blockhash: vec![1; 32],
+                        instructions: vec![CompiledInstruction {
+                            program_id_index: 1,
+                            accounts: vec![0],
+                            data: vec![],
+                        }],
+                        ..Default::default()
+                    }),
+                }),
+                meta: Some(TransactionStatusMeta {
+                    fee: 5000,
+                    pre_balances: vec![10_000, 1],
+                    post_balances: vec![5_000, 1],
+                    ..Default::default()
+                }),
+                index: 0,
+            }],
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+}
+
+fn ping() -> SubscribeUpdate {
+    SubscribeUpdate {
+        update_oneof: Some(UpdateOneof::Ping(SubscribeUpdatePing {})),
+        ..Default::default()
+    }
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_stream_and_index() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let geyser = FakeGeyser {
+            updates: vec![
+                block(326027760, "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm", 1),
+                ping(),
+                block(326027761, "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin", 2),
+            ],
+            requests: Arc::default(),
+        };
+        let addr = serve(geyser.clone()).await;
+
+        let (mut blocks, _handle) = GeyserBlockStream::new(
+            GeyserBlockStreamConfig {
+                url: format!("http://{addr}"),
+                token: None,
+                commitment: Commitment::Processed,
+                capture: None,
+            },
+            pool.clone(),
+        )
+        .stream(Signal::default())
+        .await;
+
+        let first = timeout(Duration::from_secs(5), blocks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.slot, Slot::from(326027760));
+
+        let second = timeout(Duration::from_secs(5), blocks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.slot, Slot::from(326027761));
+
+        {
+            let requests = geyser.requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(
+                requests[0].commitment,
+                Some(CommitmentLevel::Processed as i32)
+            );
+            let filter = requests[0].blocks.get("indexer").unwrap();
+            assert_eq!(filter.include_transactions, Some(true));
+            // nothing indexed yet, starts at the tip
+            assert_eq!(requests[0].from_slot, None);
+        }
+
+        index_block(setup(pool.clone()), first).await;
+        index_block(setup(pool.clone()), second).await;
+
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.block where slot in (326027760, 326027761)) = 2"
+        );
+        assert_sql!(
+            &pool,
+            "(select slot from solana.indexer where id = 1) = 326027761"
+        );
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_reconnect() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        IndexerRepo::set_solana_indexer_slot(&pool, Slot::from(326027759))
+            .await
+            .unwrap();
+
+        // nothing listens yet, the stream keeps retrying until the server is up
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let (mut blocks, _handle) = GeyserBlockStream::new(
+            GeyserBlockStreamConfig {
+                url: format!("http://{addr}"),
+                token: None,
+                commitment: Commitment::Confirmed,
+                capture: None,
+            },
+            pool.clone(),
+        )
+        .stream(Signal::default())
+        .await;
+
+        let geyser = FakeGeyser {
+            updates: vec![block(
+                326027760,
+                "AoUrZMMWTMqyZKq7PYrYBdsjNfYs9er3RB42KAvKTkxm",
+                1,
+            )],
+            requests: Arc::default(),
+        };
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = geyser.clone();
+        spawn(async move {
+            Server::builder()
+                .add_service(GeyserServer::new(server))
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let block = timeout(Duration::from_secs(10), blocks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.slot, Slot::from(326027760));
+
+        // resumes right after the last indexed slot
+        let requests = geyser.requests.lock().unwrap();
+        assert_eq!(requests[0].from_slot, Some(326027760));
+    })
+    .await
+}
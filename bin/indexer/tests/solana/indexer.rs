@@ -40,6 +40,24 @@ async fn test_set_solana_indexer_slot_multiple_times() {
     .await
 }
 
+#[test_log::test(sqlx::test)]
+async fn test_set_solana_indexer_slot_does_not_move_backwards() {
+    run_test_on_empty_db(|mut tx| async move {
+        IndexerRepo::set_solana_indexer_slot(&mut tx, 100)
+            .await
+            .unwrap();
+
+        // a block which arrived after a later slot got written
+        IndexerRepo::set_solana_indexer_slot(&mut tx, 99)
+            .await
+            .unwrap();
+
+        let indexer = IndexerRepo::get_solana_indexer_slot(&mut tx).await.unwrap();
+        assert_eq!(indexer.slot, 100);
+    })
+    .await
+}
+
 #[test_log::test(sqlx::test)]
 async fn test_set_wallet_swap_slot_first_time() {
     run_test_on_empty_db(|mut tx| async move {
@@ -73,3 +91,31 @@ async fn test_set_wallet_swap_slot_multiple_times() {
     })
     .await
 }
+
+#[test_log::test(sqlx::test)]
+async fn test_set_token_holder_slot_first_time() {
+    run_test_on_empty_db(|mut tx| async move {
+        IndexerRepo::set_token_holder_slot(&mut tx, 42)
+            .await
+            .unwrap();
+
+        let slot = IndexerRepo::get_token_holder_slot(&mut tx).await.unwrap();
+        assert_eq!(slot, Some(42.into()));
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_set_token_holder_slot_multiple_times() {
+    run_test_on_empty_db(|mut tx| async move {
+        IndexerRepo::set_token_holder_slot(&mut tx, 3).await.unwrap();
+
+        IndexerRepo::set_token_holder_slot(&mut tx, 10)
+            .await
+            .unwrap();
+
+        let slot = IndexerRepo::get_token_holder_slot(&mut tx).await.unwrap();
+        assert_eq!(slot, Some(10.into()));
+    })
+    .await
+}
@@ -0,0 +1,193 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use base::assert_sql;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::metadata::{
+    Enricher, FetchError, FetchResult, HttpFetcher, MetadataFetcher, TokenMetadata,
+    MAX_BODY_BYTES,
+};
+use serde_json::json;
+use sqlx::Executor;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::sleep;
+
+/// Gateways on loopback: /down always fails, /slow answers after a second and /up serves the metadata
+async fn gateways() -> String {
+    let metadata = || async {
+        Json(json!({
+            "name": "Father of Trollface",
+            "symbol": "TrollDad",
+            "description": "the father of all trolls",
+            "image": "https://ipfs.io/ipfs/QmImage",
+            "showName": true,
+            "createdOn": "https://pump.fun",
+            "twitter": "https://x.com/trolldad",
+            "website": ""
+        }))
+    };
+
+    let app = Router::new()
+        .route(
+            "/down/ipfs/{*cid}",
+            get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        )
+        .route(
+            "/slow/ipfs/{*cid}",
+            get(move || async move {
+                sleep(Duration::from_secs(1)).await;
+                metadata().await
+            }),
+        )
+        .route("/up/ipfs/QmMetadata", get(move || metadata()))
+        .route("/up/ipfs/QmBroken", get(|| async { "not json" }))
+        .route(
+            "/up/ipfs/QmLarge",
+            get(|| async { " ".repeat(MAX_BODY_BYTES + 1) }),
+        );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    url
+}
+
+fn fetcher(url: &str, gateways: &[&str]) -> HttpFetcher {
+    HttpFetcher::new(
+        gateways.iter().map(|g| format!("{url}/{g}")).collect(),
+        Duration::from_millis(200),
+        1,
+    )
+    .allow_private_hosts()
+}
+
+fn expected() -> TokenMetadata {
+    TokenMetadata {
+        description: Some("the father of all trolls".to_string()),
+        image: Some("https://ipfs.io/ipfs/QmImage".to_string()),
+        website: None,
+        twitter: Some("https://x.com/trolldad".to_string()),
+        telegram: None,
+    }
+}
+
+/// Answers from memory, so that the enricher can be tested without any http
+struct StubFetcher;
+
+#[async_trait]
+impl MetadataFetcher for StubFetcher {
+    async fn fetch(&self, uri: &str) -> FetchResult<TokenMetadata> {
+        match uri {
+            "ipfs://QmMetadata" => Ok(expected()),
+            "ipfs://QmUnknown" => Err(FetchError::Status(404)),
+            "http://127.0.0.1/metadata.json" => Err(FetchError::Rejected(
+                "host resolves to non public address 127.0.0.1".to_string(),
+            )),
+            _ => Err(FetchError::UnsupportedUri),
+        }
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_fetch_falls_back_to_next_gateway() {
+    let url = gateways().await;
+
+    let result = fetcher(&url, &["down", "slow", "up"])
+        .fetch("https://ipfs.io/ipfs/QmMetadata")
+        .await;
+
+    assert_eq!(result, Ok(expected()));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_fetch_all_gateways_fail() {
+    let url = gateways().await;
+
+    let result = fetcher(&url, &["down", "up"])
+        .fetch("ipfs://QmUnknown")
+        .await;
+
+    assert_eq!(result, Err(FetchError::Status(404)));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_fetch_invalid_metadata() {
+    let url = gateways().await;
+
+    let result = fetcher(&url, &["up"]).fetch("ipfs://QmBroken").await;
+
+    assert!(matches!(result, Err(FetchError::Invalid(_))));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_fetch_too_large() {
+    let url = gateways().await;
+
+    let result = fetcher(&url, &["up"]).fetch("ipfs://QmLarge").await;
+
+    assert_eq!(result, Err(FetchError::TooLarge));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_fetch_private_host_rejected() {
+    let url = gateways().await;
+
+    let result = HttpFetcher::new(vec![], Duration::from_millis(200), 0)
+        .fetch(&format!("{url}/up/ipfs/QmMetadata"))
+        .await;
+
+    assert!(matches!(result, Err(FetchError::Rejected(_))));
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_enrich() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+insert into solana.token (id, version, mint, name, symbol, decimals, supply, metadata, created_at) values
+    (1000, 0, 'BeQhc5E87KE7LahiR7849rj4V6oqqzqWTfrhThLYpump', 'Father of Trollface', 'TrollDad', 6, 1000000000, 'ipfs://QmMetadata', '2025-05-01 10:00:00+00'),
+    (1001, 0, 'BGoVP5SFh8MAmuaaPG1eCd4w9GKhMzEYkRfJrA7Spump', '2027 bird meme', '2027', 6, 1000000000, 'ipfs://QmUnknown', '2025-05-01 11:00:00+00'),
+    (1002, 0, 'KdDy3cWdSv3T2coQadd3doNoSWmEbZc1722ZgBqpump', 'Core Memory', 'Unlocked', 6, 1000000000, 'ar://unsupported', '2025-05-01 12:00:00+00'),
+    (1003, 0, '4rYpX8fVZq3eM5T8Zc9RX1gRm6ZQ2Hy6Lk9uYt7Gpump', 'Localhost', 'LOCAL', 6, 1000000000, 'http://127.0.0.1/metadata.json', '2025-05-01 13:00:00+00');
+"#,
+        )
+        .await
+        .unwrap();
+
+        let enricher = Enricher {
+            fetcher: StubFetcher,
+            batch: 3,
+            max_attempts: 5,
+        };
+
+        // the tokens waiting the longest come first
+        assert_eq!(enricher.enrich(&pool).await.unwrap(), 3);
+        assert_sql!(&pool, "(select count(*) from solana.token_metadata where token_id = 1003) = 0");
+
+        assert_sql!(&pool, "(select description from solana.token where id = 1000) = 'the father of all trolls'");
+        assert_sql!(&pool, "(select twitter from solana.token where id = 1000) = 'https://x.com/trolldad'");
+        assert_sql!(&pool, "(select website is null from solana.token where id = 1000)");
+        assert_sql!(&pool, "(select status from solana.token_metadata where token_id = 1000) = 'FETCHED'");
+
+        assert_sql!(&pool, "(select status from solana.token_metadata where token_id = 1001) = 'FAILED'");
+        assert_sql!(&pool, "(select next_attempt_at > now() from solana.token_metadata where token_id = 1001)");
+        assert_sql!(&pool, "(select last_error from solana.token_metadata where token_id = 1001) = 'unexpected status: 404'");
+
+        assert_sql!(&pool, "(select status from solana.token_metadata where token_id = 1002) = 'DEAD'");
+
+        // a rejected host is not asked again
+        assert_eq!(enricher.enrich(&pool).await.unwrap(), 1);
+        assert_sql!(&pool, "(select status from solana.token_metadata where token_id = 1003) = 'DEAD'");
+        assert_sql!(&pool, "(select last_error from solana.token_metadata where token_id = 1003) = 'rejected: host resolves to non public address 127.0.0.1'");
+
+        // nothing is due until the backoff passed
+        assert_eq!(enricher.enrich(&pool).await.unwrap(), 0);
+    })
+    .await
+}
@@ -1,5 +1,16 @@
 // Copyright (c) nyanbot.com 2025.
 // This file is licensed under the AGPL-3.0-or-later.
 
+mod backfill;
 mod block;
+mod fixture;
+mod fork;
+mod gap;
+mod geyser;
 mod indexer;
+mod metadata;
+mod notify;
+mod pipeline;
+mod replay;
+mod token_holder;
+mod venue;
@@ -0,0 +1,47 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::notify::{NotifyRepo, CHANNEL_BLOCK_INDEXED};
+use sqlx::postgres::PgListener;
+
+#[test_log::test(sqlx::test)]
+async fn test_block_indexed_without_swaps() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let mut listener = PgListener::connect_with(&pool).await.unwrap();
+        listener.listen(CHANNEL_BLOCK_INDEXED).await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        NotifyRepo::block_indexed(&mut tx, 42).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let notification = listener.recv().await.unwrap();
+        assert_eq!(notification.channel(), CHANNEL_BLOCK_INDEXED);
+
+        let payload: serde_json::Value = serde_json::from_str(notification.payload()).unwrap();
+        assert_eq!(payload["slot"], 42);
+        assert_eq!(payload["token_pairs"], serde_json::json!([]));
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_block_indexed_not_delivered_on_rollback() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let mut listener = PgListener::connect_with(&pool).await.unwrap();
+        listener.listen(CHANNEL_BLOCK_INDEXED).await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        NotifyRepo::block_indexed(&mut tx, 1).await.unwrap();
+        tx.rollback().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        NotifyRepo::block_indexed(&mut tx, 2).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let notification = listener.recv().await.unwrap();
+        let payload: serde_json::Value = serde_json::from_str(notification.payload()).unwrap();
+        assert_eq!(payload["slot"], 2);
+    })
+    .await
+}
@@ -0,0 +1,55 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::block::pipeline::Pipeline;
+use indexer::solana::block::Cursor;
+use indexer::solana::fork::Commitment;
+use tokio::sync::mpsc::channel;
+
+#[test_log::test(sqlx::test)]
+async fn test_blocks_get_written_in_order() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        // the empty blocks get parsed way faster than the first one
+        let mut next = block.clone();
+        next.slot = Slot::from(326027760);
+        next.transactions.clear();
+
+        let mut last = next.clone();
+        last.slot = Slot::from(326027761);
+
+        let (blocks_tx, blocks_rx) = channel(10);
+        blocks_tx.send(block).await.unwrap();
+        blocks_tx.send(next).await.unwrap();
+        blocks_tx.send(last).await.unwrap();
+        drop(blocks_tx);
+
+        let (indexed_tx, mut indexed_rx) = channel(10);
+        let pipeline = Pipeline::new(setup(pool.clone()), Cursor::Live(Commitment::Confirmed), 2);
+        pipeline.run(blocks_rx, indexed_tx).await;
+
+        let mut indexed = vec![];
+        while let Some(slot) = indexed_rx.recv().await {
+            indexed.push(slot.0);
+        }
+        assert_eq!(indexed, vec![326027759, 326027760, 326027761]);
+
+        assert_eq!(pipeline.metrics.total.count(), 3);
+        assert_eq!(pipeline.metrics.parse.count(), 3);
+        assert_eq!(pipeline.metrics.write.count(), 3);
+
+        assert_sql!(&pool, "(select count(*) from solana.block) = 3");
+        assert_sql!(
+            &pool,
+            "(select slot from solana.indexer where id = 1) = 326027761"
+        );
+        assert_sql!(&pool, "(select count(*) from solana.token_balance) > 0");
+    })
+    .await
+}
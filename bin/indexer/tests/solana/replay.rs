@@ -0,0 +1,66 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use base::assert_sql;
+use base::model::solana::Slot;
+use base::testing::run_test_with_pool_on_empty_db;
+use common::Signal;
+use indexer::solana::block::{index_block_with_cursor, Cursor};
+use indexer::solana::replay::{BlockCapture, Pace, ReplayBlockStream, ReplayBlockStreamConfig};
+use solana::stream::BlockStream;
+use solana_transaction_status::UiConfirmedBlock;
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::time::Duration;
+use tokio::time::timeout;
+
+#[test_log::test(sqlx::test)]
+async fn test_capture_and_replay() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        block_326027759(&pool).await;
+
+        let dir = temp_dir().join(format!("indexer-replay-{}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+
+        let block: UiConfirmedBlock =
+            serde_json::from_str(include_str!("./block/block_326027759.json")).unwrap();
+        BlockCapture {
+            dir: dir.clone(),
+            gzip: true,
+        }
+        .write(Slot::from(326027759), &block)
+        .unwrap();
+
+        let (mut blocks, handle) = ReplayBlockStream::new(ReplayBlockStreamConfig {
+            path: dir.clone(),
+            pace: Pace::None,
+        })
+        .stream(Signal::default())
+        .await;
+
+        let block = timeout(Duration::from_secs(5), blocks.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.slot, Slot::from(326027759));
+
+        // the stream ends once everything got replayed
+        assert!(blocks.recv().await.is_none());
+        handle.await.unwrap();
+
+        index_block_with_cursor(setup(pool.clone()), block, Cursor::Replay).await;
+
+        assert_sql!(&pool, "(select slot from solana.block) = 326027759");
+        // replays leave the live cursor alone
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.indexer where id = 1) = 0"
+        );
+
+        remove_dir_all(dir).unwrap();
+    })
+    .await
+}
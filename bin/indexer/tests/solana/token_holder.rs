@@ -0,0 +1,153 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use base::assert_sql;
+use base::model::Mint;
+use base::testing::run_test_with_pool_on_empty_db;
+use indexer::solana::indexer::IndexerRepo;
+use indexer::solana::token_holder::{refresh, HolderBalanceRepo};
+use sqlx::Executor;
+
+#[test_log::test(sqlx::test)]
+async fn test_refresh_starts_with_first_balance() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+            insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+                (22675, 0, 'BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump', 'MAD WOLF', 'HOWL', 6, 1000.000000000000, '2025-03-15 04:10:25');
+
+            insert into solana.address (id, address) values
+                (100, 'CbYf9QNrkVgNRCMTDiVdvzMqSzXh8AAgnrKAoTfEACdh'),
+                (101, 'FGYgFJSxZTGzaLwzUL9YZqK2yUZ8seofCwGq8BPEw4o8');
+
+            -- a plain transfer, long before the indexer got started
+            insert into solana.token_holder_balance (slot, token_id, address_id, balance) values
+                (1000, 22675, 100, 600),
+                (1050, 22675, 100, 400),
+                (1050, 22675, 101, 200);
+        "#,
+        )
+        .await
+        .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        IndexerRepo::set_solana_indexer_slot(&mut tx, 1050)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        refresh(pool.clone()).await;
+
+        assert_sql!(
+            &pool,
+            "(select holder_count from solana.token_holder_summary where token_id = 22675) = 2"
+        );
+        assert_sql!(
+            &pool,
+            "(select balance from solana.token_holder where token_id = 22675 and address_id = 100) = 400"
+        );
+
+        // folded balances are settled and got pruned
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.token_holder_balance) = 0"
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let slot = IndexerRepo::get_token_holder_slot(&mut tx).await.unwrap();
+        assert_eq!(slot, Some(1050.into()));
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_refresh_stays_behind_pending_blocks() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+            insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+                (22675, 0, 'BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump', 'MAD WOLF', 'HOWL', 6, 1000.000000000000, '2025-03-15 04:10:25');
+
+            insert into solana.address (id, address) values
+                (100, 'CbYf9QNrkVgNRCMTDiVdvzMqSzXh8AAgnrKAoTfEACdh'),
+                (101, 'FGYgFJSxZTGzaLwzUL9YZqK2yUZ8seofCwGq8BPEw4o8');
+
+            insert into solana.token_holder_balance (slot, token_id, address_id, balance) values
+                (1000, 22675, 100, 600),
+                (1050, 22675, 100, 400),
+                (1050, 22675, 101, 200);
+
+            -- indexed at processed commitment, its fork might still get abandoned
+            insert into solana.block_pending (slot) values (1050);
+        "#,
+        )
+        .await
+        .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        IndexerRepo::set_solana_indexer_slot(&mut tx, 1050)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        refresh(pool.clone()).await;
+
+        assert_sql!(
+            &pool,
+            "(select holder_count from solana.token_holder_summary where token_id = 22675) = 1"
+        );
+        assert_sql!(
+            &pool,
+            "(select balance from solana.token_holder where token_id = 22675 and address_id = 100) = 600"
+        );
+
+        assert_sql!(
+            &pool,
+            "(select count(*) from solana.token_holder_balance where slot = 1050) = 2"
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let slot = IndexerRepo::get_token_holder_slot(&mut tx).await.unwrap();
+        assert_eq!(slot, Some(1049.into()));
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_list_known_tokens_skips_quote_tokens() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        pool.execute(
+            r#"
+            insert into solana.token (id, version, mint, name, symbol, decimals, supply, block_time) values
+                (22675, 0, 'BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump', 'MAD WOLF', 'HOWL', 6, 1000.000000000000, '2025-03-15 04:10:25'),
+                (22676, 0, 'C3DwDjT17gDvvCYC2nsdGHxDHVmQRdhKfpAdqQ29pump', 'Retard Finder Coin', 'RFC', 6, 1000.000000000000, '2025-03-15 04:10:25');
+
+            insert into solana.token_pair (id, base_id, quote_id) values
+                (22675, 22675, 1);
+        "#,
+        )
+        .await
+        .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let tokens = HolderBalanceRepo::list_known_tokens(
+            &mut tx,
+            vec![
+                Mint::from("BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump".to_string()),
+                Mint::from("C3DwDjT17gDvvCYC2nsdGHxDHVmQRdhKfpAdqQ29pump".to_string()),
+                Mint::from("So11111111111111111111111111111111111111112".to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens.get(&Mint::from(
+                "BKb2WhrivhpSYEfgra2SfwW5jssuXunApRoobmpBpump".to_string()
+            )),
+            Some(&22675.into())
+        );
+    })
+    .await
+}
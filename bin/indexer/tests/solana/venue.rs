@@ -0,0 +1,103 @@
+// Copyright (c) nyanbot.com 2025.
+// This file is licensed under the AGPL-3.0-or-later.
+
+use crate::solana::block::setup;
+use crate::solana::fixture::block_326027759;
+use async_trait::async_trait;
+use base::model::solana::{Block, Transaction};
+use base::model::PublicKey;
+use base::testing::run_test_with_pool_on_empty_db;
+use common::repo::Tx;
+use indexer::solana::block::parse::parse_block;
+use indexer::solana::block::state::State;
+use indexer::solana::block::venue::{VenueIndexer, VenuePrograms, VenueRegistry};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Counts the transactions calling into its program
+struct CountingVenue {
+    program_id: PublicKey,
+    persisted: Arc<Mutex<Vec<usize>>>,
+}
+
+#[async_trait]
+impl VenueIndexer for CountingVenue {
+    type Parsed = usize;
+
+    fn name(&self) -> &'static str {
+        "counting"
+    }
+
+    fn program_id(&self) -> &PublicKey {
+        &self.program_id
+    }
+
+    fn parse(&self, _block: &Block, transactions: &[&Transaction]) -> Self::Parsed {
+        transactions.len()
+    }
+
+    async fn persist(&self, _tx: &mut Tx<'_>, _state: &State, parsed: Self::Parsed) {
+        self.persisted.lock().unwrap().push(parsed);
+    }
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_register_venue() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+        let state = setup(pool.clone());
+
+        let persisted = Arc::new(Mutex::new(vec![]));
+        let registry = VenueRegistry::default().register(CountingVenue {
+            program_id: VenuePrograms::default().pumpfun,
+            persisted: persisted.clone(),
+        });
+        assert_eq!(registry.names(), vec!["counting"]);
+
+        let parsed = parse_block(&registry, block);
+        assert_eq!(parsed.venues.len(), 1);
+        assert!(!parsed.tracked.is_empty());
+
+        let mut tx = pool.begin().await.unwrap();
+        registry.persist(&mut tx, &state, parsed.venues).await;
+        tx.commit().await.unwrap();
+
+        let persisted = persisted.lock().unwrap().clone();
+        assert_eq!(persisted.len(), 1);
+        assert!(persisted[0] > 0);
+    })
+    .await
+}
+
+#[test_log::test(sqlx::test)]
+async fn test_default_venues() {
+    run_test_with_pool_on_empty_db(|pool| async move {
+        let block = block_326027759(&pool).await;
+
+        let registry = VenueRegistry::new(VenuePrograms::default());
+        assert_eq!(
+            registry.names(),
+            vec![
+                "pumpfun",
+                "pumpswap",
+                "pumpup",
+                "jupiter",
+                "meteora_dlmm",
+                "meteora_damm",
+                "meteora_damm_v1",
+                "orca"
+            ]
+        );
+
+        // a venue which is not registered does not get its balances tracked
+        let unknown = VenueRegistry::default().register(CountingVenue {
+            program_id: PublicKey::from_str("9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP")
+                .unwrap(),
+            persisted: Arc::default(),
+        });
+        let parsed = parse_block(&unknown, block);
+        assert!(parsed.tracked.is_empty());
+        assert!(parsed.mints.is_empty());
+    })
+    .await
+}
@@ -0,0 +1,32 @@
+-- latest balance of a holder of a tracked token within a slot, recorded for every transaction moving
+-- tokens - not only the ones touching a supported venue. Rows are pruned once folded into solana.token_holder
+create table solana.token_holder_balance
+(
+    slot       int8            not null,
+    token_id   int8            not null references solana.token (id),
+    address_id int8            not null references solana.address (id),
+    balance    numeric(36, 12) not null,
+    primary key (slot, token_id, address_id)
+);
+
+create table solana.token_holder
+(
+    token_id   int8            not null references solana.token (id),
+    address_id int8            not null references solana.address (id),
+    balance    numeric(36, 12) not null,
+    slot       int8            not null,
+    updated_at timestamptz     not null default now(),
+    primary key (token_id, address_id)
+);
+
+create index token_holder_token_id_balance_idx on solana.token_holder (token_id, balance desc) where balance > 0;
+
+create table solana.token_holder_summary
+(
+    token_id            int8            not null primary key references solana.token (id),
+    holder_count        int8            not null,
+    top_holders_balance numeric(36, 12) not null,
+    creator_balance     numeric(36, 12) not null,
+    slot                int8            not null,
+    updated_at          timestamptz     not null default now()
+);
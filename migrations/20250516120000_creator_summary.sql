@@ -0,0 +1,33 @@
+create materialized view solana.creator_summary as
+with token_pair as (select t.id as token_id, t.creator_id, t.supply, t.block_time, tp.id as token_pair_id
+                    from solana.token t
+                             join solana.token_pair tp on tp.base_id = t.id
+                    where t.creator_id is not null),
+     peak as (select c.token_pair_id, max(c.price_high) as price_high
+              from pumpfun.candle_1d c
+              group by c.token_pair_id),
+     first_sell as (select s.token_pair_id, min(s.timestamp) as timestamp
+                    from pumpfun.swap s
+                             join token_pair tp on tp.token_pair_id = s.token_pair_id and s.address_id = tp.creator_id
+                    where s.is_buy = false
+                    group by s.token_pair_id),
+     -- one row per token, so that tokens traded in several pairs do not weigh more in the medians
+     token as (select tp.token_id,
+                      tp.creator_id,
+                      bool_or(coalesce(c.complete, false))                    as complete,
+                      max(p.price_high) * min(tp.supply)                      as peak_market_cap,
+                      extract(epoch from min(fs.timestamp) - min(tp.block_time)) as sell_seconds
+               from token_pair tp
+                        left join pumpfun.current c on c.id = tp.token_pair_id
+                        left join peak p on p.token_pair_id = tp.token_pair_id
+                        left join first_sell fs on fs.token_pair_id = tp.token_pair_id
+               group by tp.token_id, tp.creator_id)
+select t.creator_id,
+       count(*)                                                        as token_count,
+       count(*) filter (where t.complete)                              as graduated_count,
+       percentile_cont(0.5) within group (order by t.peak_market_cap) as median_peak_market_cap,
+       percentile_cont(0.5) within group (order by t.sell_seconds)    as median_sell_seconds
+from token t
+group by t.creator_id;
+
+create unique index creator_summary_creator_id_idx on solana.creator_summary (creator_id);
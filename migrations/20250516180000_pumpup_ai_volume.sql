@@ -0,0 +1,21 @@
+-- traded amount of the ai token per pumpup token pair within the timeframe up until now,
+-- maintained by the aggregator next to the pumpup summaries
+do
+$$
+    declare
+        v_timeframe text;
+    begin
+        foreach v_timeframe in array array ['1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table pumpup.summary_ai_%1$s
+(
+    token_pair_id int8           not null primary key references solana.token_pair (id),
+    volume        numeric(36, 12) not null,
+    volume_buy    numeric(36, 12) not null,
+    volume_sell   numeric(36, 12) not null,
+    updated_at    timestamptz    not null default now()
+)$f$, v_timeframe);
+            end loop;
+    end
+$$;
@@ -0,0 +1,49 @@
+alter table solana.rule
+    add column trigger          text not null default 'ONCE' check (trigger in ('ONCE', 'COOLDOWN', 'EDGE', 'MAX')),
+    add column trigger_cooldown int8 check (trigger_cooldown > 0),
+    add column trigger_max      int8 check (trigger_max > 0),
+    add constraint rule_trigger_cooldown_check check (trigger != 'COOLDOWN' or trigger_cooldown is not null),
+    add constraint rule_trigger_max_check check (trigger != 'MAX' or trigger_max is not null);
+
+-- a rule can be invoked multiple times per token pair, depending on its trigger policy
+do
+$$
+    declare
+        v_constraint text;
+    begin
+        select c.conname
+        into v_constraint
+        from pg_constraint c
+        where c.conrelid = 'solana.invocation'::regclass
+          and c.contype = 'u'
+          and (select array_agg(a.attname::text order by a.attname)
+               from pg_attribute a
+               where a.attrelid = c.conrelid
+                 and a.attnum = any (c.conkey)) = array ['rule_id', 'token_pair_id'];
+
+        if v_constraint is null then
+            raise exception 'unique constraint on solana.invocation (rule_id, token_pair_id) not found';
+        end if;
+
+        execute format('alter table solana.invocation drop constraint %I', v_constraint);
+    end
+$$;
+
+create index if not exists invocation_rule_id_token_pair_id_idx on solana.invocation (rule_id, token_pair_id);
+
+create table solana.rule_trigger
+(
+    rule_id       int8        not null references solana.rule (id) on delete cascade,
+    token_pair_id int8        not null references solana.token_pair (id),
+    count         int8        not null,
+    -- whether the condition was met the last time the rule was evaluated, required for edge triggering
+    matched       boolean     not null,
+    triggered_at  timestamptz not null,
+    updated_at    timestamptz not null default now(),
+    primary key (rule_id, token_pair_id)
+);
+
+insert into solana.rule_trigger (rule_id, token_pair_id, count, matched, triggered_at)
+select rule_id, token_pair_id, count(*), true, max(created_at)
+from solana.invocation
+group by rule_id, token_pair_id;
@@ -0,0 +1,13 @@
+create function solana.notify_request_submitted() returns trigger as
+$$
+begin
+    perform pg_notify('request_submitted', new.id::text);
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger request_submitted
+    after insert
+    on solana.request
+    for each row
+execute function solana.notify_request_submitted();
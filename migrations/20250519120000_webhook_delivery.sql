@@ -0,0 +1,19 @@
+create table solana.webhook_delivery
+(
+    id              int8 generated always as identity primary key,
+    rule_id         int8        not null references solana.rule (id) on delete cascade,
+    token_pair_id   int8        not null references solana.token_pair (id),
+    url             text        not null,
+    -- encrypted, see SecretCipher of the engine
+    secret_encrypted text        not null,
+    payload         jsonb       not null,
+    -- PENDING, DELIVERED or DEAD once all attempts failed
+    status          text        not null default 'PENDING' check (status in ('PENDING', 'DELIVERED', 'DEAD')),
+    attempts        int4        not null default 0,
+    next_attempt_at timestamptz not null default now(),
+    last_error      text,
+    created_at      timestamptz not null default now(),
+    updated_at      timestamptz not null default now()
+);
+
+create index webhook_delivery_pending_idx on solana.webhook_delivery (next_attempt_at) where status = 'PENDING';
@@ -0,0 +1,29 @@
+create table solana.token_lifecycle
+(
+    token_id               int8        not null primary key references solana.token (id),
+    pumpfun_token_pair_id  int8        not null references solana.token_pair (id),
+    pumpswap_token_pair_id int8 references solana.token_pair (id),
+    graduated_slot         int8        not null,
+    graduated_at           timestamptz not null,
+    updated_at             timestamptz not null default now()
+);
+
+create index token_lifecycle_pumpfun_token_pair_id_idx on solana.token_lifecycle (pumpfun_token_pair_id);
+create index token_lifecycle_pumpswap_token_pair_id_idx on solana.token_lifecycle (pumpswap_token_pair_id);
+
+create function solana.notify_token_graduated() returns trigger as
+$$
+begin
+    perform pg_notify('token_graduated', json_build_object(
+            'slot', new.graduated_slot,
+            'token_pairs', json_build_array(new.pumpfun_token_pair_id)
+                                         )::text);
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger token_graduated
+    after insert
+    on solana.token_lifecycle
+    for each row
+execute function solana.notify_token_graduated();
@@ -0,0 +1,15 @@
+alter table solana.token
+    add column twitter  text,
+    add column telegram text;
+
+create table solana.token_metadata
+(
+    token_id        int8        not null primary key references solana.token (id),
+    status          text        not null check (status in ('FETCHED', 'FAILED', 'DEAD')),
+    attempts        int4        not null default 0,
+    next_attempt_at timestamptz,
+    last_error      text,
+    updated_at      timestamptz not null default now()
+);
+
+create index token_metadata_next_attempt_at_idx on solana.token_metadata (next_attempt_at) where status = 'FAILED';
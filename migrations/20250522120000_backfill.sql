@@ -0,0 +1,27 @@
+create table solana.backfill
+(
+    id         int8        not null generated always as identity primary key,
+    from_slot  int8        not null,
+    to_slot    int8        not null,
+    -- last slot of the range which got indexed or skipped
+    slot       int8        not null,
+    status     text        not null default 'RUNNING' check (status in ('RUNNING', 'DONE')),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    unique (from_slot, to_slot),
+    check (from_slot <= to_slot)
+);
+
+-- block times whose swaps got indexed after the aggregator passed them, e.g. by a backfill.
+-- the aggregator recalculates the candles of these ranges
+create table solana.aggregate_range
+(
+    id              int8        not null generated always as identity primary key,
+    from_timestamp  timestamptz not null,
+    to_timestamp    timestamptz not null,
+    created_at      timestamptz not null default now(),
+    recalculated_at timestamptz,
+    check (from_timestamp <= to_timestamp)
+);
+
+create index aggregate_range_pending_idx on solana.aggregate_range (id) where recalculated_at is null;
@@ -0,0 +1,31 @@
+-- audited windows of slots, compared against the confirmed slots of the cluster
+create table solana.slot_audit
+(
+    id         int8        not null generated always as identity primary key,
+    from_slot  int8        not null,
+    to_slot    int8        not null,
+    -- slots the cluster confirmed a block for
+    confirmed  int4        not null,
+    -- slots the leader did not produce a block for
+    skipped    int4        not null,
+    -- confirmed slots without an indexed block
+    missing    int4        not null,
+    created_at timestamptz not null default now()
+);
+
+-- contiguous ranges of missing slots and their repair status
+create table solana.slot_gap
+(
+    id         int8        not null generated always as identity primary key,
+    from_slot  int8        not null,
+    to_slot    int8        not null,
+    status     text        not null default 'QUEUED' check (status in ('QUEUED', 'REPAIRED', 'FAILED')),
+    attempts   int4        not null default 0,
+    last_error text,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    unique (from_slot, to_slot),
+    check (from_slot <= to_slot)
+);
+
+create index slot_gap_status_idx on solana.slot_gap (status) where status = 'QUEUED';
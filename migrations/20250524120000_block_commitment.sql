@@ -0,0 +1,162 @@
+-- blocks indexed at processed commitment, which are not confirmed yet
+create table solana.block_pending
+(
+    slot       int8        not null primary key,
+    indexed_at timestamptz not null default now()
+);
+
+-- blocks which got rolled back as their fork got abandoned
+create table solana.block_rollback
+(
+    id         int8        not null generated always as identity primary key,
+    slot       int8        not null,
+    -- block time, aggregates from then on are affected
+    timestamp  timestamptz,
+    rows       int8        not null,
+    created_at timestamptz not null default now()
+);
+
+-- tables holding rows indexed for a slot, they get removed when the slot gets rolled back.
+-- every venue registers its own tables, a misspelled table fails the migration registering it
+create table solana.slot_table
+(
+    name regclass not null primary key
+);
+
+insert into solana.slot_table (name)
+values ('pumpfun.swap'),
+       ('pumpswap.swap'),
+       ('pumpswap.micro_swap'),
+       ('pumpup.swap'),
+       ('jupiter.swap'),
+       ('solana.token_balance'),
+       ('solana.token_holder_balance'),
+       ('solana.wallet_balance_token'),
+       ('solana.wallet_balance_sol');
+
+-- creator and block of a token before a slot set them, a rolled back slot restores them from here.
+-- rows get removed once their block got confirmed
+create table solana.token_revision
+(
+    token_id   int8 not null references solana.token (id),
+    slot       int8 not null,
+    creator_id int8,
+    block_id   int8,
+    block_time timestamptz,
+    primary key (token_id, slot)
+);
+
+create index token_revision_slot_idx on solana.token_revision (slot);
+
+insert into solana.slot_table (name)
+values ('solana.token_revision');
+
+create function solana.record_token_revision() returns trigger as
+$$
+begin
+    insert into solana.token_revision (token_id, slot, creator_id, block_id, block_time)
+    values (new.id, new.block_id, old.creator_id, old.block_id, old.block_time)
+    on conflict (token_id, slot) do nothing;
+    return new;
+end;
+$$ language plpgsql;
+
+create trigger token_block_changed
+    after update of block_id
+    on solana.token
+    for each row
+    -- restoring an earlier block moves backwards and is not recorded
+    when (new.block_id > old.block_id or (old.block_id is null and new.block_id is not null))
+execute function solana.record_token_revision();
+
+-- pumpfun.current holds the latest state of a bonding curve, rolled back swaps restore it
+-- from the latest remaining swap of the curve. Curves without any remaining swap got created
+-- within the rolled back slots and lose their current state.
+create function pumpfun.restore_current() returns trigger as
+$$
+begin
+    with rolled_back as (select c.id
+                         from pumpfun.current c
+                         where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)),
+         latest as (select distinct on (s.token_pair_id) s.token_pair_id,
+                                                         s.slot,
+                                                         s.price,
+                                                         s.virtual_base_reserves,
+                                                         s.virtual_quote_reserves
+                    from pumpfun.swap s
+                             join rolled_back rb on rb.id = s.token_pair_id
+                    order by s.token_pair_id, s.slot desc, s.id desc)
+    update pumpfun.current c
+    set slot                   = l.slot,
+        virtual_base_reserves  = l.virtual_base_reserves,
+        virtual_quote_reserves = l.virtual_quote_reserves,
+        -- 793.1m tokens are sold through the curve, 279.9m stay virtual reserves
+        progress               = least(100, greatest(0, 100 - (l.virtual_base_reserves - 279900000000000) * 100 / 793100000000000)),
+        complete               = c.complete and l.virtual_base_reserves <= 279900000000000,
+        price                  = l.price,
+        -- supply and sol price stay the ones of the rolled back state
+        price_usd              = l.price * c.price_usd / nullif(c.price, 0),
+        market_cap             = c.market_cap * l.price / nullif(c.price, 0),
+        market_cap_usd         = c.market_cap_usd * l.price / nullif(c.price, 0)
+    from latest l
+    where c.id = l.token_pair_id;
+
+    delete
+    from pumpfun.current c
+    where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)
+      and not exists (select 1 from pumpfun.swap s where s.token_pair_id = c.id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger swap_rolled_back
+    after delete
+    on pumpfun.swap
+    referencing old table as removed
+    for each statement
+execute function pumpfun.restore_current();
+
+-- removes everything indexed for the slot, returns the number of removed rows.
+-- state derived from several slots is reverted as well: current states get restored from the
+-- remaining swaps, the wallet swap and token holder refreshers never pass a pending block, and
+-- candles get recalculated by the aggregator from the rollback's aggregate range on
+create function solana.rollback_slot(p_slot int8) returns int8 as
+$$
+declare
+    v_table regclass;
+    v_rows  int8;
+    v_total int8 := 0;
+begin
+    -- tokens minted in the slot stay, as rows of other slots might refer to them already.
+    -- creator and block fall back to what they were before the slot set them
+    update solana.token t
+    set creator_id = r.creator_id,
+        block_id   = r.block_id,
+        block_time = r.block_time
+    from solana.token_revision r
+    where r.token_id = t.id
+      and r.slot = p_slot
+      and t.block_id = p_slot;
+
+    update solana.token t
+    set creator_id = null,
+        block_id   = null,
+        block_time = null
+    where t.block_id = p_slot;
+
+    for v_table in select name from solana.slot_table
+        loop
+            execute format('delete from %s where slot = $1', v_table) using p_slot;
+            get diagnostics v_rows = row_count;
+            v_total := v_total + v_rows;
+        end loop;
+
+    delete from solana.block where slot = p_slot;
+    get diagnostics v_rows = row_count;
+    v_total := v_total + v_rows;
+
+    delete from solana.block_pending where slot = p_slot;
+    return v_total;
+end;
+$$ language plpgsql;
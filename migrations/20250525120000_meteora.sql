@@ -0,0 +1,133 @@
+create schema if not exists meteora;
+
+-- dlmm, damm v2 and damm v1 pools trading against sol, the id is the address of the pool
+create table meteora.pool
+(
+    id            int8        not null primary key references solana.address (id),
+    program       text        not null check (program in ('dlmm', 'damm', 'damm_v1')),
+    token_pair_id int8        not null references solana.token_pair (id),
+    slot          int8,
+    timestamp     timestamptz,
+    created_at    timestamptz not null default now()
+);
+
+create index pool_token_pair_id_idx on meteora.pool (token_pair_id);
+
+create table meteora.swap
+(
+    id            int8           not null generated always as identity primary key,
+    slot          int8           not null,
+    timestamp     timestamptz    not null,
+    pool_id       int8           not null references meteora.pool (id),
+    token_pair_id int8           not null references solana.token_pair (id),
+    address_id    int8           not null references solana.address (id),
+    amount_base   numeric(36, 12) not null,
+    amount_quote  numeric(36, 12) not null,
+    price         numeric(36, 12) not null,
+    is_buy        boolean        not null,
+    signature     text           not null
+);
+
+create index swap_slot_idx on meteora.swap (slot);
+create index swap_token_pair_id_timestamp_idx on meteora.swap (token_pair_id, timestamp);
+create index swap_timestamp_idx on meteora.swap (timestamp);
+
+-- latest price of a token pair, across all of its pools
+create table meteora.current
+(
+    id         int8           not null primary key references solana.token_pair (id),
+    slot       int8           not null,
+    price      numeric(36, 12) not null,
+    updated_at timestamptz    not null default now()
+);
+
+do
+$$
+    declare
+        v_timeframe text;
+    begin
+        foreach v_timeframe in array array ['1s', '1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table meteora.candle_%1$s
+(
+    token_pair_id int8           not null references solana.token_pair (id),
+    timestamp     timestamptz    not null,
+    price_open    numeric(36, 12) not null,
+    price_high    numeric(36, 12) not null,
+    price_low     numeric(36, 12) not null,
+    price_close   numeric(36, 12) not null,
+    volume        numeric(36, 12) not null,
+    swap          int8           not null,
+    swap_buy      int8           not null,
+    swap_sell     int8           not null,
+    updated_at    timestamptz    not null default now(),
+    primary key (token_pair_id, timestamp)
+)$f$, v_timeframe);
+            end loop;
+
+        foreach v_timeframe in array array ['1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table meteora.summary_%1$s
+(
+    token_pair_id int8           not null primary key references solana.token_pair (id),
+    amount_base   numeric(36, 12) not null,
+    amount_quote  numeric(36, 12) not null,
+    volume_buy    numeric(36, 12) not null,
+    volume_sell   numeric(36, 12) not null,
+    swap          int8           not null,
+    swap_buy      int8           not null,
+    swap_sell     int8           not null,
+    trader        int8           not null,
+    updated_at    timestamptz    not null default now()
+)$f$, v_timeframe);
+
+                execute format($f$
+create table meteora.twap_%1$s
+(
+    token_pair_id int8           not null primary key references solana.token_pair (id),
+    twap          numeric(36, 12) not null,
+    updated_at    timestamptz    not null default now()
+)$f$, v_timeframe);
+            end loop;
+    end
+$$;
+
+insert into solana.slot_table (name)
+values ('meteora.swap');
+
+-- rolled back swaps restore the current price from the latest remaining swap of the token pair,
+-- token pairs without any remaining swap lose their current price
+create function meteora.restore_current() returns trigger as
+$$
+begin
+    with rolled_back as (select c.id
+                         from meteora.current c
+                         where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)),
+         latest as (select distinct on (s.token_pair_id) s.token_pair_id, s.slot, s.price
+                    from meteora.swap s
+                             join rolled_back rb on rb.id = s.token_pair_id
+                    order by s.token_pair_id, s.slot desc, s.id desc)
+    update meteora.current c
+    set slot       = l.slot,
+        price      = l.price,
+        updated_at = now()
+    from latest l
+    where c.id = l.token_pair_id;
+
+    delete
+    from meteora.current c
+    where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)
+      and not exists (select 1 from meteora.swap s where s.token_pair_id = c.id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger swap_rolled_back
+    after delete
+    on meteora.swap
+    referencing old table as removed
+    for each statement
+execute function meteora.restore_current();
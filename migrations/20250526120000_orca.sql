@@ -0,0 +1,148 @@
+create schema if not exists orca;
+
+-- whirlpools trading against sol, the id is the address of the pool
+create table orca.pool
+(
+    id                 int8           not null primary key references solana.address (id),
+    token_pair_id      int8           not null references solana.token_pair (id),
+    -- the base token is token b of the pool, the sqrt price is the one of token a in token b
+    inverted           boolean        not null,
+    slot               int8,
+    timestamp          timestamptz,
+    -- state after the last indexed swap
+    sqrt_price         numeric(39, 0),
+    tick_current_index int4,
+    price              numeric(36, 12),
+    updated_slot       int8,
+    created_at         timestamptz    not null default now(),
+    updated_at         timestamptz    not null default now()
+);
+
+create index pool_token_pair_id_idx on orca.pool (token_pair_id);
+
+create table orca.swap
+(
+    id            int8            not null generated always as identity primary key,
+    slot          int8            not null,
+    timestamp     timestamptz     not null,
+    pool_id       int8            not null references orca.pool (id),
+    token_pair_id int8            not null references solana.token_pair (id),
+    address_id    int8            not null references solana.address (id),
+    amount_base   numeric(36, 12) not null,
+    amount_quote  numeric(36, 12) not null,
+    -- price of the pool after the swap
+    price         numeric(36, 12) not null,
+    sqrt_price    numeric(39, 0)  not null,
+    tick_before   int4            not null,
+    tick_after    int4            not null,
+    is_buy        boolean         not null,
+    signature     text            not null
+);
+
+create index swap_slot_idx on orca.swap (slot);
+create index swap_token_pair_id_timestamp_idx on orca.swap (token_pair_id, timestamp);
+create index swap_timestamp_idx on orca.swap (timestamp);
+
+-- latest price of a token pair, across all of its pools
+create table orca.current
+(
+    id         int8            not null primary key references solana.token_pair (id),
+    slot       int8            not null,
+    price      numeric(36, 12) not null,
+    updated_at timestamptz     not null default now()
+);
+
+do
+$$
+    declare
+        v_timeframe text;
+    begin
+        foreach v_timeframe in array array ['1s', '1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table orca.candle_%1$s
+(
+    token_pair_id         int8            not null references solana.token_pair (id),
+    timestamp             timestamptz     not null,
+    price_open            numeric(36, 12) not null,
+    price_high            numeric(36, 12) not null,
+    price_low             numeric(36, 12) not null,
+    price_close           numeric(36, 12) not null,
+    volume                numeric(36, 12) not null,
+    swap                  int8            not null,
+    swap_buy              int8            not null,
+    swap_sell             int8            not null,
+    market_cap_open       numeric(36, 12),
+    market_cap_high       numeric(36, 12),
+    market_cap_low        numeric(36, 12),
+    market_cap_close      numeric(36, 12),
+    price_open_usd        numeric(36, 12),
+    price_high_usd        numeric(36, 12),
+    price_low_usd         numeric(36, 12),
+    price_close_usd       numeric(36, 12),
+    market_cap_open_usd   numeric(36, 12),
+    market_cap_high_usd   numeric(36, 12),
+    market_cap_low_usd    numeric(36, 12),
+    market_cap_close_usd  numeric(36, 12),
+    volume_usd            numeric(36, 12),
+    updated_at            timestamptz     not null default now(),
+    primary key (token_pair_id, timestamp)
+)$f$, v_timeframe);
+            end loop;
+    end
+$$;
+
+insert into solana.slot_table (name)
+values ('orca.swap');
+
+-- rolled back swaps restore the pool state and the current price from the latest remaining swaps,
+-- pools and token pairs without any remaining swap lose them
+create function orca.restore_current() returns trigger as
+$$
+begin
+    with rolled_back as (select p.id
+                         from orca.pool p
+                         where exists (select 1 from removed r where r.pool_id = p.id and r.slot = p.updated_slot)),
+         latest as (select distinct on (s.pool_id) s.pool_id, s.slot, s.sqrt_price, s.tick_after, s.price
+                    from orca.swap s
+                             join rolled_back rb on rb.id = s.pool_id
+                    order by s.pool_id, s.slot desc, s.id desc)
+    update orca.pool p
+    set sqrt_price         = l.sqrt_price,
+        tick_current_index = l.tick_after,
+        price              = l.price,
+        updated_slot       = l.slot,
+        updated_at         = now()
+    from rolled_back rb
+             left join latest l on l.pool_id = rb.id
+    where p.id = rb.id;
+
+    with rolled_back as (select c.id
+                         from orca.current c
+                         where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)),
+         latest as (select distinct on (s.token_pair_id) s.token_pair_id, s.slot, s.price
+                    from orca.swap s
+                             join rolled_back rb on rb.id = s.token_pair_id
+                    order by s.token_pair_id, s.slot desc, s.id desc)
+    update orca.current c
+    set slot       = l.slot,
+        price      = l.price,
+        updated_at = now()
+    from latest l
+    where c.id = l.token_pair_id;
+
+    delete
+    from orca.current c
+    where exists (select 1 from removed r where r.token_pair_id = c.id and r.slot = c.slot)
+      and not exists (select 1 from orca.swap s where s.token_pair_id = c.id);
+
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger swap_rolled_back
+    after delete
+    on orca.swap
+    referencing old table as removed
+    for each statement
+execute function orca.restore_current();
@@ -0,0 +1,60 @@
+-- complete, withdraw and migrate instructions of the pumpfun bonding curves
+create table pumpfun.lifecycle
+(
+    id               int8        not null generated always as identity primary key,
+    slot             int8        not null,
+    timestamp        timestamptz not null,
+    kind             text        not null check (kind in ('complete', 'withdraw', 'migrate')),
+    token_pair_id    int8        not null references solana.token_pair (id),
+    bonding_curve_id int8        not null references solana.address (id),
+    address_id       int8        not null references solana.address (id),
+    -- pumpswap pool the curve got migrated into
+    pool_id          int8 references solana.address (id),
+    signature        text        not null
+);
+
+create index lifecycle_slot_idx on pumpfun.lifecycle (slot);
+create index lifecycle_token_pair_id_idx on pumpfun.lifecycle (token_pair_id);
+create index lifecycle_pool_id_idx on pumpfun.lifecycle (pool_id);
+
+alter table solana.token_lifecycle
+    add column pumpswap_pool_id int8 references solana.address (id),
+    add column migrated_slot    int8,
+    add column migrated_at      timestamptz;
+
+create index token_lifecycle_pumpswap_pool_id_idx on solana.token_lifecycle (pumpswap_pool_id);
+
+insert into solana.slot_table (name)
+values ('pumpfun.lifecycle');
+
+-- token_lifecycle is derived from the lifecycle events, reverts what a rolled back event moved along
+create function pumpfun.revert_lifecycle() returns trigger as
+$$
+begin
+    if old.kind = 'migrate' then
+        update solana.token_lifecycle
+        set pumpswap_pool_id = null,
+            migrated_slot    = null,
+            migrated_at      = null,
+            updated_at       = now()
+        where pumpfun_token_pair_id = old.token_pair_id
+          and migrated_slot = old.slot;
+    end if;
+
+    if old.kind in ('complete', 'migrate') then
+        delete
+        from solana.token_lifecycle
+        where pumpfun_token_pair_id = old.token_pair_id
+          and graduated_slot = old.slot
+          and (migrated_slot is null or migrated_slot = old.slot);
+    end if;
+
+    return old;
+end;
+$$ language plpgsql;
+
+create trigger lifecycle_reverted
+    after delete
+    on pumpfun.lifecycle
+    for each row
+execute function pumpfun.revert_lifecycle();
@@ -0,0 +1,69 @@
+-- create pool, deposit and withdraw events of the pumpswap pools trading against sol
+create table pumpswap.liquidity
+(
+    id             int8           not null generated always as identity primary key,
+    slot           int8           not null,
+    timestamp      timestamptz    not null,
+    kind           text           not null check (kind in ('create_pool', 'deposit', 'withdraw')),
+    pool_id        int8           not null references solana.address (id),
+    token_pair_id  int8           not null references solana.token_pair (id),
+    address_id     int8           not null references solana.address (id),
+    amount_base    numeric(36, 12) not null,
+    amount_quote   numeric(36, 12) not null,
+    -- raw lp token amount minted by create pool and deposit, burned by withdraw
+    lp_amount      numeric(20, 0) not null,
+    -- raw lp token supply after the event
+    lp_supply      numeric(20, 0) not null,
+    base_reserves  numeric(36, 12) not null,
+    quote_reserves numeric(36, 12) not null,
+    signature      text           not null
+);
+
+create index liquidity_slot_idx on pumpswap.liquidity (slot);
+create index liquidity_pool_id_id_idx on pumpswap.liquidity (pool_id, id);
+create index liquidity_token_pair_id_timestamp_idx on pumpswap.liquidity (token_pair_id, timestamp);
+create index liquidity_timestamp_idx on pumpswap.liquidity (timestamp);
+
+-- lp token supply and reserves of each pool as of its latest liquidity event, derived from the
+-- events so that rolled back slots do not leave a stale supply behind
+create view pumpswap.lp_supply as
+select distinct on (pool_id)
+    pool_id,
+    token_pair_id,
+    lp_supply,
+    base_reserves,
+    quote_reserves,
+    slot,
+    timestamp
+from pumpswap.liquidity
+order by pool_id, id desc;
+
+do
+$$
+    declare
+        v_timeframe text;
+    begin
+        foreach v_timeframe in array array ['1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table pumpswap.liquidity_%1$s
+(
+    token_pair_id      int8           not null primary key references solana.token_pair (id),
+    deposit            int8           not null,
+    withdraw           int8           not null,
+    provider           int8           not null,
+    quote_added        numeric(36, 12) not null,
+    quote_removed      numeric(36, 12) not null,
+    quote_change       numeric(36, 12) not null,
+    lp_minted          numeric(20, 0) not null,
+    lp_burned          numeric(20, 0) not null,
+    -- lp tokens burned within the window, relative to the largest supply seen within it
+    lp_burned_percent  numeric(9, 4)  not null,
+    updated_at         timestamptz    not null default now()
+)$f$, v_timeframe);
+            end loop;
+    end
+$$;
+
+insert into solana.slot_table (name)
+values ('pumpswap.liquidity');
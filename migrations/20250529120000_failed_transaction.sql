@@ -0,0 +1,48 @@
+-- transactions calling into the program of a supported venue which did not succeed
+create table solana.failed_transaction
+(
+    id            int8        not null generated always as identity primary key,
+    slot          int8        not null,
+    timestamp     timestamptz not null,
+    venue         text        not null,
+    -- token pair the transaction was about to trade, if it could be told
+    token_pair_id int8 references solana.token_pair (id),
+    address_id    int8        not null references solana.address (id),
+    -- decoded for pumpfun and pumpswap only, null for the other venues
+    is_buy        boolean,
+    error         text        not null,
+    signature     text        not null
+);
+
+create index failed_transaction_slot_idx on solana.failed_transaction (slot);
+create index failed_transaction_token_pair_id_timestamp_idx on solana.failed_transaction (token_pair_id, timestamp);
+create index failed_transaction_timestamp_idx on solana.failed_transaction (timestamp);
+
+do
+$$
+    declare
+        v_timeframe text;
+    begin
+        foreach v_timeframe in array array ['1m', '5m', '15m', '1h', '6h', '1d']
+            loop
+                execute format($f$
+create table solana.failed_transaction_%1$s
+(
+    token_pair_id int8        not null primary key references solana.token_pair (id),
+    failed        int8        not null,
+    -- pumpfun and pumpswap only
+    failed_buy    int8        not null,
+    -- pumpfun and pumpswap only
+    failed_sell   int8        not null,
+    -- unique wallets with a failed transaction
+    wallet        int8        not null,
+    -- unique wallets with a failed buy, pumpfun and pumpswap only
+    wallet_buy    int8        not null,
+    updated_at    timestamptz not null default now()
+)$f$, v_timeframe);
+            end loop;
+    end
+$$;
+
+insert into solana.slot_table (name)
+values ('solana.failed_transaction');